@@ -0,0 +1,41 @@
+//! Compares the throughput of pageable (`Vec`) and pinned (`PinnedBuffer`)
+//! host memory for buffer transfers.
+//!
+//! Increase `ITERS` or `DATASET_SIZE` for more stable timings.
+
+extern crate ocl;
+
+use std::time::Instant;
+use ocl::{ProQue, PinnedBuffer};
+
+const DATASET_SIZE: usize = 1 << 22;
+const ITERS: usize = 10;
+
+
+fn main() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(DATASET_SIZE)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let mut pageable = vec![1.0f32; buffer.len()];
+    let mut pinned = PinnedBuffer::<f32>::new(pro_que.queue(), buffer.len()).unwrap();
+
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        buffer.write(&pageable).enq().unwrap();
+        buffer.read(&mut pageable).enq().unwrap();
+    }
+    let pageable_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        pinned.upload_to(&buffer).unwrap();
+        pinned.download_from(&buffer).unwrap();
+    }
+    let pinned_elapsed = start.elapsed();
+
+    println!("Round trip of {} bytes x {}: pageable: {:?}, pinned: {:?}",
+        buffer.len() * 4, ITERS, pageable_elapsed, pinned_elapsed);
+}
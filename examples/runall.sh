@@ -9,6 +9,7 @@ cargo run --example img_formats "$@"
 cargo run --example info_core "$@"
 cargo run --example info "$@"
 # cargo run --example map_buffers "$@"
+cargo run --example pinned_transfers "$@"
 cargo run --example threads "$@"
 cargo run --example timed "$@"
 cargo run --example trivial "$@"
@@ -0,0 +1,75 @@
+//! Custom derives for the `ocl` library.
+//!
+//! See the `KernelArgs` derive.
+
+extern crate proc_macro;
+extern crate syn;
+#[macro_use] extern crate quote;
+
+use proc_macro::TokenStream;
+
+
+/// Implements `ocl::traits::KernelArgs` for a struct.
+///
+/// Each field is bound, in declaration order, to consecutive kernel
+/// arguments using `ocl::traits::LaunchArg`. Fields marked with
+/// `#[kernel_arg(skip)]` are ignored.
+#[proc_macro_derive(KernelArgs, attributes(kernel_arg))]
+pub fn derive_kernel_args(input: TokenStream) -> TokenStream {
+    let source = input.to_string();
+    let ast = syn::parse_derive_input(&source).expect("#[derive(KernelArgs)]: Invalid input.");
+    impl_kernel_args(&ast).parse().expect("#[derive(KernelArgs)]: Invalid output.")
+}
+
+fn impl_kernel_args(ast: &syn::DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let fields = match ast.body {
+        syn::Body::Struct(syn::VariantData::Struct(ref fields)) |
+        syn::Body::Struct(syn::VariantData::Tuple(ref fields)) => fields,
+        _ => panic!("#[derive(KernelArgs)] is only defined for structs with fields."),
+    };
+
+    let binds: Vec<_> = fields.iter().enumerate()
+        .filter(|&(_, field)| !is_skipped(field))
+        .map(|(field_idx, field)| {
+            let field_name = match field.ident {
+                Some(ref ident) => ident.clone(),
+                None => syn::Ident::new(field_idx),
+            };
+
+            quote! {
+                try!(self.#field_name.set_at(kernel, arg_idx));
+                arg_idx += 1;
+            }
+        })
+        .collect();
+
+    quote! {
+        impl #impl_generics ::ocl::traits::KernelArgs for #name #ty_generics #where_clause {
+            #[allow(unused_mut)]
+            fn bind(&self, kernel: &mut ::ocl::Kernel, start_idx: u32) -> ::ocl::Result<u32> {
+                // Method call syntax (auto-referencing) allows both owned and
+                // borrowed buffer fields:
+                use ::ocl::traits::LaunchArg;
+                let mut arg_idx = start_idx;
+                #(#binds)*
+                Ok(arg_idx)
+            }
+        }
+    }
+}
+
+/// Returns true if `field` is marked with `#[kernel_arg(skip)]`.
+fn is_skipped(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|attr| match attr.value {
+        syn::MetaItem::List(ref ident, ref items) if ident == "kernel_arg" => {
+            items.iter().any(|item| match *item {
+                syn::NestedMetaItem::MetaItem(syn::MetaItem::Word(ref word)) => word == "skip",
+                _ => panic!("#[derive(KernelArgs)]: Unknown 'kernel_arg' attribute option."),
+            })
+        },
+        _ => false,
+    })
+}
@@ -0,0 +1,61 @@
+//! Tests binding parameter structs to kernel arguments.
+
+#[macro_use] extern crate ocl_derive;
+extern crate ocl;
+
+use ocl::{ProQue, Buffer};
+use ocl::traits::KernelArgs;
+
+static SRC: &'static str = r#"
+    __kernel void scale_add(__global float const* src, float scale, int offset,
+            __global float* dst)
+    {
+        uint idx = get_global_id(0);
+        dst[idx] = src[idx] * scale + (float)offset;
+    }
+"#;
+
+#[derive(KernelArgs)]
+struct Params<'a> {
+    src: &'a Buffer<f32>,
+    scale: f32,
+    #[kernel_arg(skip)]
+    #[allow(dead_code)]
+    label: &'static str,
+    offset: i32,
+    dst: Buffer<f32>,
+}
+
+#[test]
+fn derive_kernel_args() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let src = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(ocl::flags::MEM_READ_ONLY | ocl::flags::MEM_COPY_HOST_PTR)
+        .dims(pro_que.dims().clone())
+        .host_data(&vec![2.0f32; pro_que.dims().to_len()])
+        .build().unwrap();
+
+    let params = Params {
+        src: &src,
+        scale: 3.0,
+        label: "not an argument",
+        offset: 5,
+        dst: pro_que.create_buffer::<f32>().unwrap(),
+    };
+
+    let mut kernel = pro_que.create_kernel("scale_add").unwrap();
+    assert_eq!(params.bind(&mut kernel, 0).unwrap(), 4);
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0.0f32; params.dst.len()];
+    params.dst.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 2.0 * 3.0 + 5.0));
+
+    // Binding with an offset past the end must fail:
+    assert!(params.bind(&mut kernel, 1).is_err());
+}
@@ -1,5 +1,5 @@
 use futures::{Future, Poll, Async};
-use core::{OclPrm, MemMap as MemMapCore, Mem};
+use core::{OclPrm, MemMap as MemMapCore, Mem, MapFlags};
 use standard::{Event, Queue, EventList};
 use super::{Error as AsyncError, Result as AsyncResult, MemMap};
 
@@ -19,6 +19,8 @@ pub struct FutureMemMap<T: OclPrm> {
     buffer: Option<Mem>,
     queue: Option<Queue>,
     callback_is_set: bool,
+    offset: usize,
+    map_flags: MapFlags,
 }
 
 impl<T: OclPrm> FutureMemMap<T> {
@@ -35,9 +37,19 @@ impl<T: OclPrm> FutureMemMap<T> {
             buffer: Some(buffer),
             queue: Some(queue),
             callback_is_set: false,
+            offset: 0,
+            map_flags: MapFlags::empty(),
         }
     }
 
+    /// Records the offset and flags of the map command which created this
+    /// future (see `MemMap::remap`).
+    #[doc(hidden)]
+    pub unsafe fn _set_map_region(&mut self, offset: usize, map_flags: MapFlags) {
+        self.offset = offset;
+        self.map_flags = map_flags;
+    }
+
     /// Set an event wait list for the unmap command.
     ///
     /// Setting a wait list here will disallow any wait list from being set
@@ -96,8 +108,12 @@ impl<T: OclPrm> FutureMemMap<T> {
 
         match joined {
             Some((core, buffer, queue)) => {
-                unsafe { Ok(MemMap::new(core, self.len, self.unmap_wait_list.take(),
-                    self.unmap_target_event.take(), buffer, queue )) }
+                unsafe {
+                    let mut mem_map = MemMap::new(core, self.len, self.unmap_wait_list.take(),
+                        self.unmap_target_event.take(), buffer, queue);
+                    mem_map._set_map_region(self.offset, self.map_flags);
+                    Ok(mem_map)
+                }
             },
             _ => Err("FutureMemMap::create_unmap_target_event: No queue and/or buffer found!".into()),
         }
@@ -1,5 +1,9 @@
+use std::ptr;
+use std::mem;
 use std::ops::{Deref, DerefMut};
-use core::{self, OclPrm, ClWaitListPtr, ClNullEventPtr, MemMap as MemMapCore, Mem as MemCore, AsMem};
+use std::sync::{Arc, Mutex};
+use core::{self, OclPrm, ClWaitListPtr, ClNullEventPtr, MemMap as MemMapCore, Mem as MemCore, AsMem,
+    MapFlags};
 use standard::{ClWaitListPtrEnum, ClNullEventPtrEnum, Event, EventList, Queue};
 use async::{Result as AsyncResult};
 
@@ -97,6 +101,10 @@ pub struct MemMap<T> where T: OclPrm {
     unmap_target_event: Option<Event>,
     callback_is_set: bool,
     is_unmapped: bool,
+    blocking_unmap: bool,
+    auto_unmap: bool,
+    offset: usize,
+    map_flags: MapFlags,
 }
 
 impl<T> MemMap<T>  where T: OclPrm {
@@ -112,6 +120,10 @@ impl<T> MemMap<T>  where T: OclPrm {
             unmap_target_event: unmap_target_event,
             callback_is_set: false,
             is_unmapped: false,
+            blocking_unmap: cfg!(feature = "async_block"),
+            auto_unmap: true,
+            offset: 0,
+            map_flags: MapFlags::empty(),
         }
     }
 
@@ -154,16 +166,14 @@ impl<T> MemMap<T>  where T: OclPrm {
                         unsafe { enew.clone_from(&origin_event) }
                 }
 
-                if cfg!(not(feature = "async_block")) {
+                if !self.blocking_unmap {
                     // Async version:
                     if self.unmap_target_event.is_some() {
-                        #[cfg(not(feature = "async_block"))]
                         self.register_event_trigger(&origin_event)?;
 
                         // `origin_event` will be reconstructed by the callback
                         // function using `UserEvent::from_raw` and `::drop`
                         // will be run there. Do not also run it here.
-                        #[cfg(not(feature = "async_block"))]
                         ::std::mem::forget(origin_event);
                     }
                 } else {
@@ -181,7 +191,6 @@ impl<T> MemMap<T>  where T: OclPrm {
         }
     }
 
-    #[cfg(not(feature = "async_block"))]
     fn register_event_trigger(&mut self, event: &Event) -> AsyncResult<()> {
         debug_assert!(self.is_unmapped && self.unmap_target_event.is_some());
 
@@ -203,6 +212,150 @@ impl<T> MemMap<T>  where T: OclPrm {
         }
     }
 
+    /// Selects how the unmap target event (see
+    /// `FutureMemMap::create_unmap_target_event`) is completed once the
+    /// unmap command has completed.
+    ///
+    /// When `true`, unmapping blocks the calling thread until the unmap
+    /// command completes then sets the target event complete. When `false`,
+    /// a callback is registered to set the target event complete without
+    /// blocking.
+    ///
+    /// Defaults to `true` if the `async_block` feature is enabled and
+    /// `false` otherwise. Has no effect once unmapped.
+    pub fn set_blocking_unmap(&mut self, blocking: bool) {
+        self.blocking_unmap = blocking;
+    }
+
+    /// Returns true if unmapping will block until the unmap command
+    /// completes (see `::set_blocking_unmap`).
+    #[inline] pub fn is_blocking_unmap(&self) -> bool { self.blocking_unmap }
+
+    /// Copies the mapped contents into a new `Vec`, enqueues an unmap
+    /// command, and returns the copy.
+    ///
+    /// The copy is made before the unmap command is enqueued, while the
+    /// mapped memory is still valid.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if this map has already been unmapped or if the
+    /// unmap command fails to enqueue.
+    ///
+    pub fn into_vec(mut self) -> AsyncResult<Vec<T>> {
+        if self.is_unmapped {
+            return Err("ocl::MemMap::into_vec: Already unmapped.".into());
+        }
+
+        let vec = self.to_vec();
+        try!(self.unmap().enq());
+        Ok(vec)
+    }
+
+    /// Records the offset and flags of the map command which created this
+    /// map so that it can be re-issued by `::remap`.
+    ///
+    /// Maps created with `::new` are otherwise assumed to begin at offset
+    /// zero and to use the default flags.
+    #[doc(hidden)]
+    pub unsafe fn _set_map_region(&mut self, offset: usize, map_flags: MapFlags) {
+        self.offset = offset;
+        self.map_flags = map_flags;
+    }
+
+    /// Re-issues the (blocking) map command which created this map, once
+    /// unmapped, mapping the same region of the same buffer.
+    ///
+    /// Commands enqueued on the same in-order queue before this call
+    /// (including the preceding unmap) complete before the region is
+    /// remapped and their results are visible through this map.
+    ///
+    /// Any unmap target event has already been triggered by the previous
+    /// unmap and is discarded.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if this map has not been unmapped.
+    ///
+    pub fn remap(&mut self) -> AsyncResult<()> {
+        if !self.is_unmapped {
+            return Err("MemMap::remap: Memory is still mapped.".into());
+        }
+
+        let core = unsafe {
+            try!(core::enqueue_map_buffer::<T, _, _, _>(&self.queue, &self.buffer, true,
+                self.map_flags, self.offset, self.len, None::<&Event>, None::<&mut Event>))
+        };
+
+        self.core = core;
+        self.unmap_target_event = None;
+        self.callback_is_set = false;
+        self.is_unmapped = false;
+        Ok(())
+    }
+
+    /// Selects whether or not an unmap command is enqueued automatically when
+    /// this map is dropped without having been unmapped.
+    ///
+    /// Defaults to `true`. Any error which occurs while enqueuing the
+    /// drop-time unmap is discarded. When set to `false`, dropping a map
+    /// which has not been unmapped leaves the memory object mapped (the
+    /// mapped region remains valid until the buffer is released) and no
+    /// unmap target event is ever triggered.
+    pub fn set_auto_unmap(&mut self, auto_unmap: bool) {
+        self.auto_unmap = auto_unmap;
+    }
+
+    /// Returns true if an unmap command will be enqueued when this map is
+    /// dropped (see `::set_auto_unmap`).
+    #[inline] pub fn is_auto_unmap(&self) -> bool { self.auto_unmap }
+
+    /// Consumes this map without unmapping it, returning the core map, its
+    /// length, the mapped memory object, and the queue used to map it.
+    ///
+    /// The memory object remains mapped until an unmap command is enqueued
+    /// manually (i.e. with `core::enqueue_unmap_mem_object`). Any unmap
+    /// target event (see `FutureMemMap::create_unmap_target_event`) will
+    /// never be triggered by this map and any commands waiting on it will
+    /// never run unless it is set complete manually.
+    pub fn leak(self) -> (MemMapCore<T>, usize, MemCore, Queue) {
+        unsafe {
+            let core = ptr::read(&self.core);
+            let buffer = ptr::read(&self.buffer);
+            let queue = ptr::read(&self.queue);
+            let len = self.len;
+
+            // Release the remaining fields then skip `Drop::drop`:
+            drop(ptr::read(&self.unmap_wait_list));
+            drop(ptr::read(&self.unmap_target_event));
+            mem::forget(self);
+
+            (core, len, buffer, queue)
+        }
+    }
+
+    /// Converts this map into a read-only map which may be shared between
+    /// threads.
+    ///
+    /// The mapped memory is unmapped when the last `Arc` is dropped (see
+    /// `SharedMemMap`). Any unmap wait list and unmap target event set on
+    /// this map are retained and used by that unmap.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if this map has already been unmapped.
+    ///
+    pub fn into_shared(self) -> Arc<SharedMemMap<T>> {
+        assert!(!self.is_unmapped, "MemMap::into_shared: Mapped memory has been unmapped \
+            and cannot be shared.");
+
+        Arc::new(SharedMemMap {
+            ptr: self.as_ptr(),
+            len: self.len,
+            map: Mutex::new(self),
+        })
+    }
+
     /// Returns a reference to the unmap target event if it has been set.
     pub fn unmap_target_event(&self) -> Option<&Event> {
         self.unmap_target_event.as_ref()
@@ -217,6 +370,18 @@ impl<T> MemMap<T>  where T: OclPrm {
     /// the memory referenced by this `MemMap` to become invalid.
     #[inline] pub fn is_unmapped(&self) -> bool { self.is_unmapped }
 
+    /// Returns the flags used by the map command which created this map (if
+    /// recorded, otherwise empty).
+    #[inline] pub fn map_flags(&self) -> MapFlags { self.map_flags }
+
+    /// Returns true if this map was created for writing only (i.e. with
+    /// `BufferMapCmd::write` or `::write_invalidate`).
+    ///
+    /// The contents of write-invalidate maps are undefined until written.
+    pub fn is_write_only(&self) -> bool {
+        !self.map_flags.is_empty() && !self.map_flags.contains(::flags::MAP_READ)
+    }
+
     /// Returns a pointer to the host mapped memory.
     #[inline] pub fn as_ptr(&self) -> *const T { self.core.as_ptr() }
 
@@ -245,7 +410,7 @@ impl<T> DerefMut for MemMap<T> where T: OclPrm {
 
 impl<T: OclPrm> Drop for MemMap<T> {
     fn drop(&mut self) {
-        if !self.is_unmapped {
+        if !self.is_unmapped && self.auto_unmap {
             self.enqueue_unmap::<&Event, &mut Event>(None, None, None).ok();
         }
     }
@@ -257,6 +422,77 @@ impl<T: OclPrm> AsMem<T> for MemMap<T> {
     }
 }
 
+
+
+/// A read-only, thread-safe mapping created by `MemMap::into_shared`.
+///
+/// Dereferences immutably to `[T]` and is intended to be shared between
+/// threads within an `Arc`. The mapped memory is valid for as long as any
+/// `Arc` remains and the unmap command is enqueued exactly once, when the
+/// last one is dropped. The map is held behind a mutex which is locked for
+/// the duration of that enqueue.
+///
+/// No host writes may be made through a shared map. Commands which write to
+/// the mapped region (on the device) while it is shared produce undefined
+/// results, as with any mapped memory.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+pub struct SharedMemMap<T: OclPrm> {
+    map: Mutex<MemMap<T>>,
+    ptr: *const T,
+    len: usize,
+}
+
+impl<T: OclPrm> SharedMemMap<T> {
+    /// Returns the number of elements in the mapped region.
+    #[inline] pub fn len(&self) -> usize { self.len }
+
+    /// Returns a pointer to the host mapped memory.
+    #[inline] pub fn as_ptr(&self) -> *const T { self.ptr }
+
+    /// Returns a clone of the queue used to map (and unmap) the memory.
+    pub fn queue(&self) -> Queue {
+        match self.map.lock() {
+            Ok(map) => map.queue().clone(),
+            Err(poisoned) => poisoned.into_inner().queue().clone(),
+        }
+    }
+}
+
+impl<T: OclPrm> Deref for SharedMemMap<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { ::std::slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl<T: OclPrm> Drop for SharedMemMap<T> {
+    fn drop(&mut self) {
+        let mut map = match self.map.lock() {
+            Ok(map) => map,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if !map.is_unmapped && map.auto_unmap {
+            map.enqueue_unmap::<&Event, &mut Event>(None, None, None).ok();
+        }
+    }
+}
+
+impl<T: OclPrm> ::std::fmt::Debug for SharedMemMap<T> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("SharedMemMap")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+unsafe impl<T: OclPrm> Send for SharedMemMap<T> {}
+unsafe impl<T: OclPrm> Sync for SharedMemMap<T> {}
+
 // impl<'a, T: OclPrm> AsMem<T> for &'a mut MemMap<T> {
 //     fn as_mem(&self) -> &MemCore {
 //         self.core.as_mem()
@@ -10,7 +10,7 @@ use futures::future;
 pub use self::error::{Error, Result};
 // pub use self::rw_vec::{RwVec, Guard, FutureGuard, RwGuard, FutureRwGuard};
 pub use self::rw_vec::{RwVec, ReadGuard, WriteGuard, FutureRwGuard, FutureReader, FutureWriter};
-pub use self::mem_map::MemMap;
+pub use self::mem_map::{MemMap, SharedMemMap};
 pub use self::future_mem_map::FutureMemMap;
 
 pub type FutureResult<T> = future::FutureResult<T, self::Error>;
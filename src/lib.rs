@@ -55,6 +55,67 @@ extern crate rand;
 extern crate futures;
 extern crate crossbeam;
 pub extern crate ocl_core as core;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "image")]
+extern crate image;
+#[cfg(feature = "trace")]
+#[macro_use]
+extern crate log;
+
+/// Emits a trace record for an OpenCL API call when the `trace` feature is
+/// enabled.
+///
+/// Usage: `trace_cl!("clFunctionName", &result, "arg: {}", arg)`.
+#[cfg(feature = "trace")]
+macro_rules! trace_cl {
+    ($func:expr, $result:expr, $fmt:expr $(, $arg:expr)*) => (
+        trace!(concat!("{}(", $fmt, ") -> {}"), $func $(, $arg)*, match *$result {
+            Ok(_) => String::from("CL_SUCCESS"),
+            Err(ref err) => format!("{}", err),
+        })
+    )
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace_cl {
+    ($($arg:tt)*) => (())
+}
+
+/// Adds arguments to a freshly created kernel, positionally, then enqueues
+/// it with the given global work size.
+///
+/// Usage: `launch!(kernel, gws, (buf_a, buf_b, 1.5f32))` where each argument
+/// implements `LaunchArg` (buffer and image references and scalars).
+///
+/// The number of arguments provided is counted at compile time. Because a
+/// kernel's signature is only known once its program has been built, that
+/// count is checked against the kernel's argument count at runtime, before
+/// anything is set or enqueued.
+/// Evaluates to `ocl::Result<Kernel>`, returning the kernel (with all
+/// arguments set) for reuse.
+///
+/// The kernel must not have any arguments set beforehand and must have a
+/// default queue (such as one created with `ProQue::create_kernel`).
+///
+/// [UNSTABLE]: This macro may be renamed.
+#[macro_export]
+macro_rules! launch {
+    (@count) => (0u32);
+    (@count $head:expr $(, $tail:expr)*) => (1u32 + launch!(@count $($tail),*));
+    ($kernel:expr, $gws:expr, ($($arg:expr),* $(,)*)) => ({
+        let kernel: $crate::Kernel = $kernel;
+        let arg_count = launch!(@count $($arg),*);
+
+        if arg_count != kernel.num_args() {
+            Err($crate::Error::from(format!("ocl::launch!: Kernel '{}' takes {} argument(s) but {} \
+                were provided.", kernel.name(), kernel.num_args(), arg_count)))
+        } else {
+            let kernel = kernel$(.arg($arg))*.gws($gws);
+            kernel.enq().map(|_| kernel)
+        }
+    });
+}
 
 #[cfg(test)]
 mod tests;
@@ -62,9 +123,17 @@ mod standard;
 pub mod async;
 
 pub use self::standard::{Platform, Device, Context, Program, Queue, Kernel, Buffer, Image, Event,
-    EventList, EventArray, Sampler, SpatialDims, ProQue};
+    EventList, EventArray, Sampler, SpatialDims, ProQue, CommandBuffer,
+    CommandBufferKhr, StreamPipeline, Tile, ConstantBuffer, PingPong,
+    PinnedBuffer, ProgramBuildError, BuildDiagnostic, BuildDiagnosticSeverity, ProfilingSummary,
+    ProfilingNanos, SyncKernel, PciBusInfo, ImageLimits, Pipeline, MemoryUsage,
+    CountingQueue, Texture, WaitStrategy};
+pub use self::standard::{describe_system, SystemInfo, PlatformDesc, DeviceDesc,
+    verify_address_bits, nd_range, read_all, join_events, JoinEvents};
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::standard::{SvmBuffer, SvmCmd};
 pub use self::async::{MemMap, FutureMemMap, RwVec, ReadGuard, WriteGuard, FutureRwGuard,
-    FutureReader, FutureWriter};
+    FutureReader, FutureWriter, SharedMemMap};
 pub use core::error::{Error, Result};
 #[doc(no_inline)]
 pub use core::ffi;
@@ -124,7 +193,8 @@ pub mod prm {
 pub mod traits {
     //! Commonly used traits.
 
-    pub use standard::{WorkDims, MemLen, IntoMarker, IntoRawEventArray};
+    pub use standard::{WorkDims, MemLen, Dimensioned, IntoMarker, IntoRawEventArray, DefineValue,
+        LaunchArg, KernelArgs, ImageComponent, ReducePrm, EnqueueOn};
     pub use core::{OclPrm, OclScl, OclVec};
 }
 
@@ -133,7 +203,8 @@ pub mod builders {
 
     pub use standard::{ContextBuilder, BuildOpt, ProgramBuilder, ImageBuilder, ProQueBuilder,
         DeviceSpecifier, BufferCmdKind, BufferCmdDataShape, BufferCmd, BufferReadCmd,
-        BufferWriteCmd, BufferMapCmd, ImageCmdKind, ImageCmd, KernelCmd, BufferBuilder};
+        BufferWriteCmd, BufferMapCmd, ImageCmdKind, ImageCmd, KernelCmd, BufferBuilder,
+        QueueBuilder, Priority, Throttle};
     pub use standard::{ClNullEventPtrEnum, ClWaitListPtrEnum};
     pub use core::{ImageFormat, ImageDescriptor, ContextProperties};
     // #[cfg(not(release))] pub use standard::BufferTest;
@@ -177,7 +248,8 @@ pub mod flags {
 pub mod enums {
     //! Enumerators for settings and information requests.
 
-    pub use standard::{DeviceSpecifier, BufferCmdKind, BufferCmdDataShape};
+    pub use standard::{DeviceSpecifier, BufferCmdKind, BufferCmdDataShape, DebugArg,
+        EventStatus};
 
     // API enums.
     pub use core::{ImageChannelOrder, ImageChannelDataType, Cbool, Polling, PlatformInfo,
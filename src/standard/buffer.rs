@@ -1,15 +1,80 @@
 //! Interfaces with a buffer.
 
 use std;
+use std::mem;
+use std::sync::Arc;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut, Range};
-use ffi::cl_GLuint;
+use std::io::Write;
+use ffi::{cl_GLuint, cl_mem};
 use core::{self, Error as OclError, Result as OclResult, OclPrm, Mem as MemCore,
     MemFlags, MemInfo, MemInfoResult, BufferRegion, MapFlags, AsMem, MemCmdRw,
     MemCmdAll, ClNullEventPtr};
 use ::{Context, Queue, SpatialDims, FutureMemMap, MemMap, Event, RwVec, WriteGuard,
     FutureRwGuard, FutureReader, FutureWriter};
-use standard::{ClNullEventPtrEnum, ClWaitListPtrEnum};
+use standard::{self, ClNullEventPtrEnum, ClWaitListPtrEnum};
+#[cfg(feature = "ndarray")]
+use ndarray::{Array2, ArrayView, Dimension};
+
+
+/// The approximate size of each chunk read by `Buffer::read_to_writer`.
+const READ_TO_WRITER_CHUNK_BYTES: usize = 1 << 20;
+
+
+/// A sink which computes a 64-bit FNV-1a hash of everything written to it.
+///
+/// Used by `Buffer::content_hash`.
+struct Fnv1aHasher(u64);
+
+impl Fnv1aHasher {
+    fn new() -> Fnv1aHasher {
+        Fnv1aHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Write for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) -> ::std::io::Result<usize> {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> ::std::io::Result<()> {
+        Ok(())
+    }
+}
+
+
+/// Warns if `flags` would map a buffer created with `mem_flags` containing
+/// `MEM_HOST_WRITE_ONLY` (see `BufferBuilder::host_write_combined`) for
+/// reading. `mem_flags` are the flags cached by the buffer (`None` if
+/// unknown, in which case nothing is checked).
+#[cfg(feature = "trace")]
+fn warn_map_readable(mem_flags: Option<MemFlags>, flags: MapFlags) {
+    if !flags.is_empty() && !flags.contains(::flags::MAP_READ) { return; }
+
+    if mem_flags.map(|f| f.contains(::flags::MEM_HOST_WRITE_ONLY)).unwrap_or(false) {
+        warn!("ocl::BufferMapCmd::enq: Mapping a buffer created with 'MEM_HOST_WRITE_ONLY' \
+            (such as a write-combined buffer) for reading. Reading such memory from the host is \
+            very slow, if permitted at all. Map with '::write' or '::write_invalidate' instead.");
+    }
+}
+
+#[cfg(not(feature = "trace"))]
+fn warn_map_readable(_: Option<MemFlags>, _: MapFlags) {}
+
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
 
 
 fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
@@ -23,6 +88,44 @@ fn check_len(mem_len: usize, data_len: usize, offset: usize) -> OclResult<()> {
     }
 }
 
+/// Returns an error if a copy of `len` elements from `src_offset` in `src` to
+/// `dst_offset` in `dst` is within a single buffer and the two ranges overlap.
+fn check_copy_overlap(src: &MemCore, dst: &MemCore, src_offset: usize, dst_offset: usize,
+        len: usize) -> OclResult<()>
+{
+    if src.as_ptr() == dst.as_ptr() && len > 0 && src_offset < dst_offset + len &&
+        dst_offset < src_offset + len
+    {
+        OclError::err_string(format!("ocl::Buffer::copy: The source ({}..{}) and destination \
+            ({}..{}) ranges of a copy within the same buffer overlap, which is undefined \
+            behavior. Copy through a separate staging buffer instead (or use \
+            '::allow_overlap' if the implementation is known to support it).", src_offset,
+            src_offset + len, dst_offset, dst_offset + len))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns an error if a copy of `len` elements to `dst_offset` would exceed
+/// the length of `dst`.
+fn check_copy_dst_len<T: OclPrm>(dst: &MemCore, dst_offset: usize, len: usize)
+        -> OclResult<()>
+{
+    let dst_len = match core::get_mem_object_info(dst, MemInfo::Size) {
+        MemInfoResult::Size(size) => size / mem::size_of::<T>(),
+        MemInfoResult::Error(err) => return Err(*err),
+        _ => panic!("ocl::Buffer::copy: Unexpected 'MemInfoResult' variant."),
+    };
+
+    if dst_offset > dst_len || len > dst_len - dst_offset {
+        OclError::err_string(format!("ocl::Buffer::copy: The destination range ({}..{}) \
+            exceeds the length of the destination buffer ({}).", dst_offset, dst_offset + len,
+            dst_len))
+    } else {
+        Ok(())
+    }
+}
+
 /// A queue or context reference.
 #[derive(Debug, Clone)]
 pub enum QueCtx<'o> {
@@ -141,6 +244,9 @@ pub struct BufferCmd<'c, T> where T: 'c {
     ewait: Option<ClWaitListPtrEnum<'c>>,
     enew: Option<ClNullEventPtrEnum<'c>>,
     mem_len: usize,
+    mem_flags: Option<MemFlags>,
+    allow_overlap: bool,
+    profiled: bool,
 }
 
 /// [UNSTABLE]: All methods still in a state of flux.
@@ -160,6 +266,9 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
             ewait: None,
             enew: None,
             mem_len: mem_len,
+            mem_flags: None,
+            allow_overlap: false,
+            profiled: false,
         }
     }
 
@@ -263,12 +372,19 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
     /// If `.block(..)` has been set it will be ignored.
     ///
     /// `dst_offset` defaults to `0`, `len` defaults to the full length of the
-    /// source buffer.
+    /// source buffer. Both (along with the source offset) may also be set
+    /// with `::src_offset`, `::dst_offset`, and `::len`.
     ///
     /// ## Errors
     ///
     /// If this is a rectangular copy, `dst_offset` and `len` must be None.
     ///
+    /// For linear copies, the source and destination ranges must lie within
+    /// their respective buffers.
+    ///
+    /// For linear copies within a single buffer, the source and destination
+    /// ranges may not overlap (see `::allow_overlap`).
+    ///
     /// ## Panics
     ///
     /// The command operation kind must not have already been specified
@@ -292,6 +408,66 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
         self
     }
 
+    /// Disables the check, made when enqueuing a copy within a single
+    /// buffer, that the source and destination ranges do not overlap.
+    ///
+    /// Overlapping copies within the same buffer are not permitted by
+    /// OpenCL (`CL_MEM_COPY_OVERLAP`) and their results are undefined on
+    /// implementations which do not detect them.
+    ///
+    /// [UNSTABLE]: May be changed or removed.
+    pub fn allow_overlap(mut self) -> BufferCmd<'c, T> {
+        self.allow_overlap = true;
+        self
+    }
+
+    /// Sets the offset (in elements) within the source buffer at which a
+    /// linear copy begins.
+    ///
+    /// Equivalent to `::offset`.
+    ///
+    /// ## Panics
+    ///
+    /// The 'shape' may not have already been set to rectangular by the
+    /// `::rect` function.
+    pub fn src_offset(self, src_offset: usize) -> BufferCmd<'c, T> {
+        self.offset(src_offset)
+    }
+
+    /// Sets the offset (in elements) within the destination buffer at which
+    /// a linear copy begins, replacing the `dst_offset` passed to `::copy`.
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must already have been set to copy with
+    /// `::copy`.
+    pub fn dst_offset(mut self, dst_offset: usize) -> BufferCmd<'c, T> {
+        match self.kind {
+            BufferCmdKind::Copy { dst_offset: ref mut offset, .. } => *offset = Some(dst_offset),
+            _ => panic!("ocl::BufferCmd::dst_offset(): Destination offsets are only valid \
+                for copy commands. Call '::copy' first."),
+        }
+        self
+    }
+
+    /// Sets the number of elements copied by a linear copy, replacing the
+    /// `len` passed to `::copy`.
+    ///
+    /// Defaults to the length of the source buffer (less the source offset).
+    ///
+    /// ## Panics
+    ///
+    /// The command operation kind must already have been set to copy with
+    /// `::copy`.
+    pub fn len(mut self, len: usize) -> BufferCmd<'c, T> {
+        match self.kind {
+            BufferCmdKind::Copy { len: ref mut copy_len, .. } => *copy_len = Some(len),
+            _ => panic!("ocl::BufferCmd::len(): Copy lengths are only valid for copy \
+                commands. Call '::copy' first."),
+        }
+        self
+    }
+
     /// Specifies that this command will be a copy to image operation.
     ///
     /// If `.block(..)` has been set it will be ignored.
@@ -370,6 +546,46 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
         self
     }
 
+    /// Specifies that this command should be enqueued on the
+    /// profiling-enabled queue associated with the queue set when the
+    /// command is enqueued (see `Queue::enable_profiling_sibling`).
+    ///
+    /// ### Errors
+    ///
+    /// Enqueuing returns an error unless the queue either has profiling
+    /// enabled or has a profiling sibling.
+    ///
+    pub fn profiled(mut self) -> BufferCmd<'c, T> {
+        self.profiled = true;
+        self
+    }
+
+    /// Returns the queue to enqueue this command on, accounting for
+    /// `::profiled`, and checks the order of the wait list against it (see
+    /// the `event_order_debug` feature).
+    fn resolve_queue(&self, fn_name: &'static str) -> OclResult<&'c Queue> {
+        let queue = match self.queue {
+            Some(q) => q,
+            None => return Err(format!("ocl::{}: No queue set.", fn_name).into()),
+        };
+
+        let queue = if !self.profiled {
+            queue
+        } else {
+            match queue.profiled() {
+                Some(q) => q,
+                None => return OclError::err_string(format!("ocl::{}: The queue does not have \
+                    profiling enabled or a profiling sibling. Use \
+                    'Queue::enable_profiling_sibling' to create one.", fn_name)),
+            }
+        };
+
+        if let Some(ref ewait) = self.ewait {
+            try!(ewait.check_order(fn_name, queue));
+        }
+        Ok(queue)
+    }
+
     /// Specifies whether or not to block the current thread until completion.
     ///
     /// Ignored if this is not a read or write operation.
@@ -474,10 +690,7 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
 
     /// Enqueues this command.
     pub fn enq(self) -> OclResult<()> {
-        let queue = match self.queue {
-            Some(q) => q,
-            None => return Err("BufferCmd::enq: No queue set.".into()),
-        };
+        let queue = try!(self.resolve_queue("BufferCmd::enq"));
 
         match self.kind {
             BufferCmdKind::Copy { dst_buffer, dst_offset, len } => {
@@ -486,10 +699,19 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
                         let len = len.unwrap_or(self.mem_len);
                         try!(check_len(self.mem_len, len, offset));
                         let dst_offset = dst_offset.unwrap_or(0);
+                        try!(check_copy_dst_len::<T>(dst_buffer, dst_offset, len));
+                        if !self.allow_overlap {
+                            try!(check_copy_overlap(self.obj_core, dst_buffer, offset,
+                                dst_offset, len));
+                        }
 
-                        core::enqueue_copy_buffer::<T, _, _, _>(queue,
+                        let res = core::enqueue_copy_buffer::<T, _, _, _>(queue,
                             self.obj_core, dst_buffer, offset, dst_offset, len,
-                            self.ewait, self.enew)
+                            self.ewait, self.enew);
+                        trace_cl!("clEnqueueCopyBuffer", &res, "queue: {:?}, src: {:?}, \
+                            dst: {:?}, src_offset: {}, dst_offset: {}, len: {}", queue.as_ptr(),
+                            self.obj_core.as_ptr(), dst_buffer.as_ptr(), offset, dst_offset, len);
+                        res
                     },
                     BufferCmdDataShape::Rect { src_origin, dst_origin, region,
                         src_row_pitch_bytes, src_slc_pitch_bytes, dst_row_pitch_bytes,
@@ -518,8 +740,12 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
 
                         try!(check_len(self.mem_len, len, offset));
 
-                        core::enqueue_fill_buffer(queue, self.obj_core, pattern,
-                            offset, len, self.ewait, self.enew, Some(&queue.device_version()))
+                        let res = core::enqueue_fill_buffer(queue, self.obj_core, pattern,
+                            offset, len, self.ewait, self.enew, Some(&queue.device_version()));
+                        trace_cl!("clEnqueueFillBuffer", &res, "queue: {:?}, buffer: {:?}, \
+                            offset: {}, len: {}", queue.as_ptr(), self.obj_core.as_ptr(), offset,
+                            len);
+                        res
                     },
                     BufferCmdDataShape::Rect { .. } => OclError::err_string(
                         "ocl::BufferCmd::enq(): Rectangular fill is not a valid operation. \
@@ -539,6 +765,59 @@ impl<'c, T> BufferCmd<'c, T> where T: 'c + OclPrm {
             _ => unimplemented!(),
         }
     }
+
+    /// Enqueues this copy command and returns an event which may be used as
+    /// a future resolving upon completion of the copy (see `Event`).
+    ///
+    /// The queue is flushed after the copy is enqueued so that the returned
+    /// event can complete without any further action. A zero-length copy
+    /// enqueues nothing and returns an already complete (user) event. Any
+    /// destination passed to `::enew` also receives the event.
+    ///
+    /// ### Errors
+    ///
+    /// Only linear (not rectangular) copies are supported (`::copy`).
+    ///
+    pub fn into_future(mut self) -> OclResult<Event> {
+        let queue = try!(self.resolve_queue("BufferCmd::into_future"));
+
+        let event = match (self.kind, self.shape) {
+            (BufferCmdKind::Copy { dst_buffer, dst_offset, len },
+                BufferCmdDataShape::Lin { offset }) =>
+            {
+                let len = len.unwrap_or(self.mem_len);
+                try!(check_len(self.mem_len, len, offset));
+                try!(check_copy_dst_len::<T>(dst_buffer, dst_offset.unwrap_or(0), len));
+                if !self.allow_overlap {
+                    try!(check_copy_overlap(self.obj_core, dst_buffer, offset,
+                        dst_offset.unwrap_or(0), len));
+                }
+
+                if len == 0 {
+                    let event = try!(Event::user(&queue.context()));
+                    try!(event.set_complete());
+                    event
+                } else {
+                    let mut copy_event = Event::empty();
+                    try!(core::enqueue_copy_buffer::<T, _, _, _>(queue, self.obj_core,
+                        dst_buffer, offset, dst_offset.unwrap_or(0), len, self.ewait.take(),
+                        Some(&mut copy_event)));
+                    try!(queue.flush());
+                    copy_event
+                }
+            },
+            (BufferCmdKind::Copy { .. }, _) => return Err("ocl::BufferCmd::into_future: \
+                Rectangular copies are not supported. Use '::enq' with '::enew' instead.".into()),
+            _ => return Err("ocl::BufferCmd::into_future: Only copy commands are supported. \
+                Use '.copy(...)' before calling '.into_future()'.".into()),
+        };
+
+        if let Some(ref mut enew) = self.enew.take() {
+            unsafe { enew.clone_from(&event) }
+        }
+
+        Ok(event)
+    }
 }
 
 
@@ -615,6 +894,15 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
         self
     }
 
+    /// Specifies that this command should be enqueued on the
+    /// profiling-enabled queue associated with the currently set queue.
+    ///
+    /// See `BufferCmd::profiled`.
+    pub fn profiled(mut self) -> BufferReadCmd<'c, 'd, T> {
+        self.cmd = self.cmd.profiled();
+        self
+    }
+
     /// Specifies whether or not to block the current thread until completion.
     ///
     /// Ignored if this is not a read or write operation.
@@ -760,10 +1048,7 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
             "Unable to enqueue buffer read command: Invalid src_offset and/or len.")) }
 
         let mut enqueue_with_data = |dst: &mut [T]| {
-            let queue = match self.cmd.queue {
-                Some(q) => q,
-                None => return Err("BufferReadCmd::enq: No queue set.".into()),
-            };
+            let queue = try!(self.cmd.resolve_queue("BufferReadCmd::enq"));
 
             match self.cmd.kind {
                 BufferCmdKind::Read => {
@@ -771,8 +1056,14 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
                         BufferCmdDataShape::Lin { offset } => {
                             try!(check_len(self.cmd.mem_len, dst.len(), offset));
 
-                            unsafe { core::enqueue_read_buffer(queue, self.cmd.obj_core, self.cmd.block,
-                                offset, dst, self.cmd.ewait.take(), self.cmd.enew.take()) }
+                            let len = dst.len();
+                            let res = unsafe { core::enqueue_read_buffer(queue, self.cmd.obj_core,
+                                self.cmd.block, offset, dst, self.cmd.ewait.take(),
+                                self.cmd.enew.take()) };
+                            trace_cl!("clEnqueueReadBuffer", &res, "queue: {:?}, buffer: {:?}, \
+                                block: {}, offset: {}, len: {}", queue.as_ptr(),
+                                self.cmd.obj_core.as_ptr(), self.cmd.block, offset, len);
+                            res
                         },
                         BufferCmdDataShape::Rect { src_origin, dst_origin, region, src_row_pitch_bytes,
                                 src_slc_pitch_bytes, dst_row_pitch_bytes, dst_slc_pitch_bytes } =>
@@ -815,10 +1106,7 @@ impl<'c, 'd, T> BufferReadCmd<'c, 'd, T> where T: OclPrm {
     /// (such as `RwVec`) must have been passed to `::read`.
     ///
     pub fn enq_async(mut self) -> OclResult<FutureRwGuard<T, WriteGuard<T>>> {
-        let queue = match self.cmd.queue {
-            Some(q) => q,
-            None => return Err("BufferCmd::enq: No queue set.".into()),
-        };
+        let queue = try!(self.cmd.resolve_queue("BufferCmd::enq"));
 
         match self.cmd.kind {
             BufferCmdKind::Read => {
@@ -948,6 +1236,15 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
         self
     }
 
+    /// Specifies that this command should be enqueued on the
+    /// profiling-enabled queue associated with the currently set queue.
+    ///
+    /// See `BufferCmd::profiled`.
+    pub fn profiled(mut self) -> BufferWriteCmd<'c, 'd, T> {
+        self.cmd = self.cmd.profiled();
+        self
+    }
+
     /// Specifies whether or not to block the current thread until completion.
     ///
     /// Ignored if this is not a read or write operation.
@@ -1093,10 +1390,7 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
             "Unable to enqueue buffer write command: Invalid src_offset and/or len.")) }
 
         let mut enqueue_with_data = |src: &[T]| {
-            let queue = match self.cmd.queue {
-                Some(q) => q,
-                None => return Err("BufferCmd::enq: No queue set.".into()),
-            };
+            let queue = try!(self.cmd.resolve_queue("BufferCmd::enq"));
 
             match self.cmd.kind {
                 BufferCmdKind::Write => {
@@ -1104,8 +1398,13 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
                         BufferCmdDataShape::Lin { offset } => {
                             try!(check_len(self.cmd.mem_len, src.len(), offset));
 
-                            core::enqueue_write_buffer(queue, self.cmd.obj_core, self.cmd.block,
-                                offset, src, self.cmd.ewait.take(), self.cmd.enew.take())
+                            let res = core::enqueue_write_buffer(queue, self.cmd.obj_core,
+                                self.cmd.block, offset, src, self.cmd.ewait.take(),
+                                self.cmd.enew.take());
+                            trace_cl!("clEnqueueWriteBuffer", &res, "queue: {:?}, buffer: {:?}, \
+                                block: {}, offset: {}, len: {}", queue.as_ptr(),
+                                self.cmd.obj_core.as_ptr(), self.cmd.block, offset, src.len());
+                            res
                         },
                         BufferCmdDataShape::Rect { src_origin, dst_origin, region,
                             src_row_pitch_bytes, src_slc_pitch_bytes, dst_row_pitch_bytes,
@@ -1147,10 +1446,7 @@ impl<'c, 'd, T> BufferWriteCmd<'c, 'd, T> where T: OclPrm {
     /// A data destination container appropriate for an asynchronous operation
     /// (such as `RwVec`) must have been passed to `::write`.
     pub fn enq_async(mut self) -> OclResult<FutureRwGuard<T, WriteGuard<T>>> {
-        let queue = match self.cmd.queue {
-            Some(q) => q,
-            None => return Err("BufferCmd::enq: No queue set.".into()),
-        };
+        let queue = try!(self.cmd.resolve_queue("BufferCmd::enq"));
 
         match self.cmd.kind {
             BufferCmdKind::Write => {
@@ -1276,14 +1572,23 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
     /// `MEM_ALLOC_HOST_PTR` or `MEM_USE_HOST_PTR` flags. Only use this when
     /// you will be overwriting the entire contents of the mapped region
     /// otherwise you will send stale or junk data to the device.
+    ///
+    /// The current contents of the region are not copied to the host when
+    /// mapping and the resulting `MemMap` is valid for writing only
+    /// (`MemMap::is_write_only`). Reading from it (before every element has
+    /// been written) returns undefined values. Combine with `::offset` and
+    /// `::len` to overwrite only a sub-region.
     pub fn write_invalidate(mut self) -> BufferMapCmd<'c, T> {
         self.flags = Some(::flags::MAP_WRITE_INVALIDATE_REGION);
         self
     }
 
-    /// Specifies the length of the region to map.
+    /// Specifies the length of the region to map (in elements).
     ///
-    /// If unspecified the entire buffer will be mapped.
+    /// If unspecified, the remainder of the buffer (starting at the offset
+    /// set by `::offset`) will be mapped.
+    ///
+    /// The resulting `MemMap` will have this length.
     //
     // * TODO: Consider taking an `Into<SpatialDims>` argument and possibly
     //   renaming method.
@@ -1298,12 +1603,21 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
         self
     }
 
-    /// Sets the linear offset for an operation.
+    /// Specifies that this command should be enqueued on the
+    /// profiling-enabled queue associated with the currently set queue.
     ///
-    /// ## Panics
+    /// See `BufferCmd::profiled`.
+    pub fn profiled(mut self) -> BufferMapCmd<'c, T> {
+        self.cmd = self.cmd.profiled();
+        self
+    }
+
+    /// Sets the linear offset (in elements) of the region to map.
     ///
-    /// The 'shape' may not have already been set to rectangular by the
-    /// `::rect` function.
+    /// The resulting `MemMap` will point to the element at this offset. Use
+    /// with `::len` to map only a sub-range of the buffer.
+    ///
+    /// Defaults to 0 if not set.
     pub fn offset(self, offset: usize)  -> BufferMapCmd<'c, T> {
         BufferMapCmd { cmd: self.cmd.offset(offset), ..self }
     }
@@ -1346,10 +1660,7 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
 
     /// Enqueues a map command.
     pub fn enq(mut self) -> OclResult<MemMap<T>> {
-        let queue = match self.cmd.queue {
-            Some(q) => q,
-            None => return Err("BufferCmd::enq: No queue set.".into()),
-        };
+        let queue = try!(self.cmd.resolve_queue("BufferCmd::enq"));
 
         let flags = self.flags.unwrap_or(MapFlags::empty());
 
@@ -1357,20 +1668,27 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
             if let BufferCmdDataShape::Lin { offset } = self.cmd.shape {
                 let len = match self.len {
                     Some(l) => l,
-                    None => self.cmd.mem_len,
+                    None => self.cmd.mem_len.saturating_sub(offset),
                 };
 
                 check_len(self.cmd.mem_len, len, offset)?;
+                warn_map_readable(self.cmd.mem_flags, flags);
 
                 unsafe {
                     let mm_core = core::enqueue_map_buffer::<T, _, _, _>(queue,
                         self.cmd.obj_core, true, flags, offset, len, self.cmd.ewait.take(),
-                        self.cmd.enew.take())?;
+                        self.cmd.enew.take());
+                    trace_cl!("clEnqueueMapBuffer", &mm_core, "queue: {:?}, buffer: {:?}, \
+                        block: true, flags: {:?}, offset: {}, len: {}", queue.as_ptr(),
+                        self.cmd.obj_core.as_ptr(), flags, offset, len);
+                    let mm_core = mm_core?;
 
                     let unmap_event = None;
 
-                    Ok(MemMap::new(mm_core, len, None, unmap_event, self.cmd.obj_core.clone(),
-                        queue.clone()))
+                    let mut mem_map = MemMap::new(mm_core, len, None, unmap_event,
+                        self.cmd.obj_core.clone(), queue.clone());
+                    mem_map._set_map_region(offset, flags);
+                    Ok(mem_map)
                 }
             } else {
                 OclError::err_string("ocl::BufferCmd::enq_map(): A rectangular map is \
@@ -1385,10 +1703,7 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
     /// completion of that map command and containing a reference to the
     /// mapped memory.
     pub fn enq_async(mut self) -> OclResult<FutureMemMap<T>> {
-        let queue = match self.cmd.queue {
-            Some(q) => q,
-            None => return Err("BufferCmd::enq: No queue set.".into()),
-        };
+        let queue = try!(self.cmd.resolve_queue("BufferCmd::enq"));
 
         let flags = self.flags.unwrap_or(MapFlags::empty());
 
@@ -1396,10 +1711,11 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
             if let BufferCmdDataShape::Lin { offset } = self.cmd.shape {
                 let len = match self.len {
                     Some(l) => l,
-                    None => self.cmd.mem_len,
+                    None => self.cmd.mem_len.saturating_sub(offset),
                 };
 
                 check_len(self.cmd.mem_len, len, offset)?;
+                warn_map_readable(self.cmd.mem_flags, flags);
 
                 let future = unsafe {
                     let mut map_event = Event::empty();
@@ -1415,8 +1731,10 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
                         self_enew.clone_from(&map_event)
                     }
 
-                    FutureMemMap::new(mm_core, len, map_event, self.cmd.obj_core.clone(),
-                        queue.clone())
+                    let mut future = FutureMemMap::new(mm_core, len, map_event,
+                        self.cmd.obj_core.clone(), queue.clone());
+                    future._set_map_region(offset, flags);
+                    future
                 };
 
                 Ok(future)
@@ -1431,6 +1749,35 @@ impl<'c, T> BufferMapCmd<'c, T> where T: OclPrm {
 }
 
 
+/// Host memory owned by a buffer created with `Buffer::from_vec`.
+///
+/// If the memory object is still referenced elsewhere (by a kernel argument
+/// or sub-buffer, for example) when this is dropped, the backing `Vec` is
+/// leaked rather than freed so that the device never accesses freed memory.
+#[derive(Debug)]
+struct HostVec<T> {
+    vec: Option<Vec<T>>,
+    obj_core: MemCore,
+}
+
+impl<T> HostVec<T> {
+    /// Returns the reference count of the associated memory object.
+    fn mem_ref_count(&self) -> u32 {
+        match core::get_mem_object_info(&self.obj_core, MemInfo::ReferenceCount) {
+            MemInfoResult::ReferenceCount(count) => count,
+            _ => u32::max_value(),
+        }
+    }
+}
+
+impl<T> Drop for HostVec<T> {
+    fn drop(&mut self) {
+        if let Some(vec) = self.vec.take() {
+            if self.mem_ref_count() > 1 { mem::forget(vec); }
+        }
+    }
+}
+
 
 /// A chunk of memory physically located on a device, such as a GPU.
 ///
@@ -1445,6 +1792,7 @@ pub struct Buffer<T: OclPrm> {
     dims: SpatialDims,
     len: usize,
     flags: MemFlags,
+    host_vec: Option<Arc<HostVec<T>>>,
     _data: PhantomData<T>,
 }
 
@@ -1492,7 +1840,18 @@ impl<T: OclPrm> Buffer<T> {
             QueCtx::Context(c) => c,
         };
 
-        let obj_core = unsafe { core::create_buffer(ctx_ref, flags, len, host_data)? };
+        // Devices with at least 32 address bits can address anything smaller:
+        let size_bytes = len as u64 * mem::size_of::<T>() as u64;
+        if size_bytes > u32::max_value() as u64 {
+            for device in ctx_ref.devices() {
+                try!(device.verify_address_bits(size_bytes));
+            }
+        }
+
+        let obj_core = unsafe { core::create_buffer(ctx_ref, flags, len, host_data) };
+        trace_cl!("clCreateBuffer", &obj_core, "flags: {:?}, len: {}, host_data: {}", flags, len,
+            host_data.is_some());
+        let obj_core = obj_core?;
 
         let buf = Buffer {
             obj_core: obj_core,
@@ -1501,6 +1860,7 @@ impl<T: OclPrm> Buffer<T> {
             dims: dims,
             len: len,
             flags: flags,
+            host_vec: None,
             _data: PhantomData,
         };
 
@@ -1521,6 +1881,93 @@ impl<T: OclPrm> Buffer<T> {
         Ok(buf)
     }
 
+    /// Creates a new buffer which uses the memory allocated by `vec` as its
+    /// storage (`CL_MEM_USE_HOST_PTR`), taking ownership of it.
+    ///
+    /// Tying the lifetime of the host memory to the buffer makes zero-copy
+    /// use of host memory safe. Use `::into_vec` to reclaim the backing
+    /// storage once the buffer is no longer needed.
+    ///
+    /// `flags` defaults to `MEM_READ_WRITE`. `MEM_USE_HOST_PTR` is always
+    /// added.
+    ///
+    /// ### Errors
+    ///
+    /// `flags` may not contain `MEM_COPY_HOST_PTR` or `MEM_ALLOC_HOST_PTR`.
+    ///
+    pub fn from_vec(queue: Queue, vec: Vec<T>, flags: Option<MemFlags>) -> OclResult<Buffer<T>> {
+        let flags = flags.unwrap_or(::flags::MEM_READ_WRITE);
+
+        if flags.intersects(::flags::MEM_COPY_HOST_PTR | ::flags::MEM_ALLOC_HOST_PTR) {
+            return OclError::err_string("ocl::Buffer::from_vec: 'MEM_COPY_HOST_PTR' and \
+                'MEM_ALLOC_HOST_PTR' may not be specified.");
+        }
+
+        let flags = flags | ::flags::MEM_USE_HOST_PTR;
+        let len = vec.len();
+        let obj_core = unsafe { core::create_buffer(&queue.context(), flags, len,
+            Some(vec.as_slice())) };
+        trace_cl!("clCreateBuffer", &obj_core, "flags: {:?}, len: {}, host_ptr: {:?}", flags, len,
+            vec.as_ptr());
+        let obj_core = obj_core?;
+
+        Ok(Buffer {
+            obj_core: obj_core.clone(),
+            queue: Some(queue),
+            origin: None,
+            dims: len.into(),
+            len: len,
+            flags: flags,
+            host_vec: Some(Arc::new(HostVec { vec: Some(vec), obj_core: obj_core })),
+            _data: PhantomData,
+        })
+    }
+
+    /// Consumes this buffer and returns the host memory it was created with
+    /// (see `::from_vec`).
+    ///
+    /// The buffer is first mapped and unmapped (using the default queue) to
+    /// ensure that the host memory reflects any changes made on the device.
+    /// Blocks until all commands in the default queue have completed.
+    ///
+    /// ### Errors
+    ///
+    /// This buffer must have been created with `::from_vec`, must have no
+    /// remaining clones, and its memory object must not be referenced
+    /// elsewhere (such as by a kernel argument or a sub-buffer).
+    ///
+    pub fn into_vec(self) -> OclResult<Vec<T>> {
+        if self.host_vec.is_none() {
+            return OclError::err_string("ocl::Buffer::into_vec: This buffer was not \
+                created using 'Buffer::from_vec'.");
+        }
+
+        if let Some(ref queue) = self.queue {
+            // Dropping the map enqueues the unmap.
+            try!(self.map().read().enq());
+            try!(queue.finish());
+        }
+
+        let Buffer { obj_core, host_vec, .. } = self;
+        mem::drop(obj_core);
+
+        let host_vec = host_vec.expect("ocl::Buffer::into_vec: Host vec missing.");
+
+        let mut host_vec = match Arc::try_unwrap(host_vec) {
+            Ok(hv) => hv,
+            Err(_) => return OclError::err_string("ocl::Buffer::into_vec: This buffer still has \
+                clones in existence."),
+        };
+
+        if host_vec.mem_ref_count() > 1 {
+            return OclError::err_string("ocl::Buffer::into_vec: The memory object associated \
+                with this buffer is still in use (by a kernel argument or sub-buffer, for \
+                example).");
+        }
+
+        Ok(host_vec.vec.take().expect("ocl::Buffer::into_vec: Host vec missing."))
+    }
+
     /// Creates a buffer linked to a previously created OpenGL buffer object.
     ///
     /// [UNTESTED]
@@ -1560,6 +2007,7 @@ impl<T: OclPrm> Buffer<T> {
             len: len,
             _data: PhantomData,
             flags: flags,
+            host_vec: None,
         };
 
         Ok(buf)
@@ -1575,7 +2023,9 @@ impl<T: OclPrm> Buffer<T> {
     ///
     #[inline]
     pub fn cmd<'c>(&'c self) -> BufferCmd<'c, T> {
-        BufferCmd::new(self.queue.as_ref(), &self.obj_core, self.len)
+        let mut cmd = BufferCmd::new(self.queue.as_ref(), &self.obj_core, self.len);
+        cmd.mem_flags = Some(self.flags);
+        cmd
     }
 
     /// Returns a command builder used to read data.
@@ -1633,6 +2083,220 @@ impl<T: OclPrm> Buffer<T> {
         self.cmd().copy(dst_buffer, dst_offset, len)
     }
 
+    /// Copies the entire contents of this buffer into `dst_buffer`, a buffer
+    /// of the same length belonging to a different context.
+    ///
+    /// OpenCL provides no way to copy directly between contexts so the data
+    /// is staged through pinned host memory (a `MEM_ALLOC_HOST_PTR` buffer
+    /// created in the destination context): this buffer is read (blocking)
+    /// into the mapped staging memory, which is then unmapped and copied into
+    /// `dst_buffer`. This round trip through the host is unavoidable and the
+    /// transfer will be correspondingly slower than a same-context copy.
+    ///
+    /// `ewait` must contain only events from this buffer's context and
+    /// `enew` will be associated with the final (destination context) copy
+    /// command, completion of which marks the completion of the entire
+    /// operation.
+    ///
+    /// Both buffers must have a default queue set.
+    ///
+    /// [UNSTABLE]: This method may be moved to the command builder.
+    ///
+    pub fn copy_to_foreign<'e, Ewl, En>(&self, dst_buffer: &Buffer<T>, ewait: Option<Ewl>,
+            enew: Option<En>) -> OclResult<()>
+            where Ewl: Into<ClWaitListPtrEnum<'e>>, En: Into<ClNullEventPtrEnum<'e>>
+    {
+        if self.len != dst_buffer.len {
+            return OclError::err_string(format!("ocl::Buffer::copy_to_foreign: Buffer lengths \
+                differ (src: {}, dst: {}).", self.len, dst_buffer.len));
+        }
+
+        let dst_queue = match dst_buffer.queue {
+            Some(ref q) => q,
+            None => return OclError::err_string("ocl::Buffer::copy_to_foreign: The destination \
+                buffer has no default queue."),
+        };
+
+        if self.queue.is_none() {
+            return OclError::err_string("ocl::Buffer::copy_to_foreign: This buffer has no \
+                default queue.");
+        }
+
+        let staging = try!(Buffer::<T>::builder()
+            .queue(dst_queue.clone())
+            .flags(::flags::MEM_READ_WRITE | ::flags::MEM_ALLOC_HOST_PTR)
+            .dims(self.len)
+            .build());
+
+        let mut staging_map = try!(staging.map().write_invalidate().enq());
+        try!(self.read(&mut staging_map[..]).ewait_opt(ewait).enq());
+
+        let mut unmap_event = Event::empty();
+        try!(staging_map.unmap().enew(&mut unmap_event).enq());
+
+        staging.cmd().copy(dst_buffer, None, None)
+            .ewait(&unmap_event)
+            .enew_opt(enew)
+            .enq()
+    }
+
+    /// Reads the entire contents of this buffer, one chunk at a time, and
+    /// writes the raw bytes of each element to `writer`. Returns the total
+    /// number of bytes written.
+    ///
+    /// Each chunk is read (blocking) into a small, reused host vector so
+    /// that no host copy of the entire buffer is ever made. Bytes are written
+    /// in the host's native byte order (little-endian on all common
+    /// platforms).
+    ///
+    /// A default queue must be set for this buffer.
+    ///
+    pub fn read_to_writer<W: Write>(&self, writer: &mut W) -> OclResult<usize> {
+        let elem_size = mem::size_of::<T>();
+        let chunk_len = ::std::cmp::max(READ_TO_WRITER_CHUNK_BYTES / elem_size, 1);
+        let mut chunk = vec![T::default(); ::std::cmp::min(chunk_len, self.len)];
+        let mut offset = 0;
+
+        while offset < self.len {
+            let len = ::std::cmp::min(chunk.len(), self.len - offset);
+            try!(self.read(&mut chunk[..len]).offset(offset).len(len).enq());
+
+            let bytes = unsafe {
+                ::std::slice::from_raw_parts(chunk.as_ptr() as *const u8, len * elem_size)
+            };
+
+            try!(writer.write_all(bytes).map_err(|err| OclError::from(format!(
+                "ocl::Buffer::read_to_writer: Error writing to sink: {}", err))));
+
+            offset += len;
+        }
+
+        Ok(self.len * elem_size)
+    }
+
+    /// Returns a 64-bit hash of the current contents of this buffer.
+    ///
+    /// The hash (64-bit FNV-1a of the raw element bytes) is stable across
+    /// runs and processes on the same platform. Buffers of equal length with
+    /// identical contents always hash equal.
+    ///
+    /// The entire buffer is read from device memory (in chunks, see
+    /// `::read_to_writer`) each time this is called. A default queue must be
+    /// set for this buffer.
+    ///
+    pub fn content_hash(&self) -> OclResult<u64> {
+        let mut hasher = Fnv1aHasher::new();
+        try!(self.read_to_writer(&mut hasher));
+        Ok(hasher.0)
+    }
+
+    /// Reads and returns the single element at `idx` (blocking), such as the
+    /// result of a reduction or a flag, without allocating.
+    ///
+    /// A default queue must be set for this buffer.
+    ///
+    /// ### Errors
+    ///
+    /// `idx` must be less than the length of the buffer.
+    ///
+    pub fn read_one(&self, idx: usize) -> OclResult<T> {
+        if idx >= self.len {
+            return OclError::err_string(format!("ocl::Buffer::read_one: Index out of bounds \
+                (index: {}, buffer length: {}).", idx, self.len));
+        }
+
+        let mut val = [T::default()];
+        try!(self.read(&mut val[..]).offset(idx).len(1).enq());
+        Ok(val[0])
+    }
+
+    /// Reads the entire buffer (blocking) and folds its elements, in order,
+    /// into an accumulator starting from `init` using `f`.
+    ///
+    /// Intended for finishing reductions on the host, such as summing a
+    /// small buffer of per-work-group partial results:
+    ///
+    /// ```rust,ignore
+    /// let total = partials.read_and_fold(0.0f32, |acc, partial| acc + partial)?;
+    /// ```
+    ///
+    /// A default queue must be set for this buffer.
+    ///
+    pub fn read_and_fold<A, F>(&self, init: A, f: F) -> OclResult<A>
+            where F: FnMut(A, T) -> A
+    {
+        let mut vec = vec![T::default(); self.len];
+        try!(self.read(&mut vec).enq());
+        Ok(vec.into_iter().fold(init, f))
+    }
+
+    /// Reallocates this buffer with a length of `new_len`, preserving the
+    /// first `min(old_len, new_len)` elements (blocking).
+    ///
+    /// A new memory object is created with the same flags (less
+    /// `MEM_COPY_HOST_PTR`, but including `MEM_ALLOC_HOST_PTR`) and the
+    /// existing contents copied into it on the default queue. The
+    /// dimensions of the buffer become one dimensional. Elements beyond the
+    /// preserved prefix are uninitialized.
+    ///
+    /// Sub-buffers, clones of this buffer, and kernel arguments referring to
+    /// the old memory object are not updated and continue to refer to the
+    /// old memory object. Kernel arguments must be set again.
+    ///
+    /// ### Errors
+    ///
+    /// A default queue must be set, `new_len` must be non-zero, and this
+    /// buffer must not be a sub-buffer or use host memory
+    /// (`MEM_USE_HOST_PTR`).
+    ///
+    pub fn resize(&mut self, new_len: usize) -> OclResult<()> {
+        if new_len == 0 {
+            return Err("ocl::Buffer::resize: The new length must be non-zero.".into());
+        }
+        if self.is_sub_buffer() {
+            return Err("ocl::Buffer::resize: Sub-buffers cannot be resized.".into());
+        }
+        if self.flags.contains(::flags::MEM_USE_HOST_PTR) {
+            return Err("ocl::Buffer::resize: Buffers using host memory ('MEM_USE_HOST_PTR') \
+                cannot be resized.".into());
+        }
+
+        let new_obj_core = {
+            let queue = match self.queue {
+                Some(ref q) => q,
+                None => return Err("ocl::Buffer::resize: No default queue set.".into()),
+            };
+            let context = queue.context();
+
+            let size_bytes = new_len as u64 * mem::size_of::<T>() as u64;
+            if size_bytes > u32::max_value() as u64 {
+                for device in context.devices() {
+                    try!(device.verify_address_bits(size_bytes));
+                }
+            }
+
+            // Host pointer flags require a host pointer. `MEM_ALLOC_HOST_PTR`
+            // does not (and is kept so that pinned buffers stay pinned):
+            let flags = self.flags & !(::flags::MEM_COPY_HOST_PTR | ::flags::MEM_USE_HOST_PTR);
+            let new_obj_core = try!(unsafe {
+                core::create_buffer(&context, flags, new_len, None::<&[T]>)
+            });
+
+            let mut copy_event = Event::empty();
+            try!(core::enqueue_copy_buffer::<T, _, _, _>(queue, &self.obj_core, &new_obj_core,
+                0, 0, ::std::cmp::min(self.len, new_len), None::<Event>, Some(&mut copy_event)));
+            try!(copy_event.wait_for());
+
+            self.flags = flags;
+            new_obj_core
+        };
+
+        self.obj_core = new_obj_core;
+        self.dims = SpatialDims::One(new_len);
+        self.len = new_len;
+        Ok(())
+    }
+
     /// Returns the origin of the sub-buffer within its buffer if this is a
     /// sub-buffer.
     #[inline]
@@ -1707,6 +2371,22 @@ impl<T: OclPrm> Buffer<T> {
         &self.obj_core
     }
 
+    /// Returns the raw memory object handle and the length of this buffer
+    /// (in elements of `T`) for use with other OpenCL FFI.
+    ///
+    /// The handle is only valid for as long as this buffer (or a clone of
+    /// it) exists. The reference count is not incremented: call
+    /// `clRetainMemObject` on the handle to keep the memory object alive
+    /// beyond that.
+    ///
+    /// For sub-buffers, the handle refers to the sub-buffer itself (no
+    /// additional offset needs to be applied).
+    ///
+    #[inline]
+    pub fn raw_parts(&self) -> (cl_mem, usize) {
+        (self.obj_core.as_ptr(), self.len)
+    }
+
     /// Returns the memory flags used during the creation of this buffer.
     ///
     /// Saves the cost of having to look them up using `::mem_info`.
@@ -1787,7 +2467,10 @@ impl<T: OclPrm> Buffer<T> {
         }
 
         let obj_core = core::create_sub_buffer::<T>(self, flags,
-            &BufferRegion::new(origin_ofs, len))?;
+            &BufferRegion::new(origin_ofs, len));
+        trace_cl!("clCreateSubBuffer", &obj_core, "buffer: {:?}, flags: {:?}, origin: {}, len: {}",
+            self.obj_core.as_ptr(), flags, origin_ofs, len);
+        let obj_core = obj_core?;
 
         Ok(Buffer {
             obj_core: obj_core,
@@ -1796,10 +2479,55 @@ impl<T: OclPrm> Buffer<T> {
             dims: dims,
             len: len,
             flags: flags,
+            host_vec: None,
             _data: PhantomData,
         })
     }
 
+    /// Creates a sub-buffer beginning at the nearest offset at or below
+    /// `desired_offset` which satisfies the base address alignment of every
+    /// device in this buffer's context (`Device::mem_base_addr_align`).
+    ///
+    /// Returns the sub-buffer and the residual offset: the number of elements
+    /// between the start of the sub-buffer and `desired_offset`. The
+    /// sub-buffer is `residual + len` elements long so that element `i` of
+    /// the requested region is at index `residual + i` of the sub-buffer.
+    ///
+    /// When the size of `T` does not evenly divide the alignment, offsets
+    /// step by the least common multiple of the two (in bytes), so the
+    /// residual may exceed the alignment.
+    ///
+    /// See `::create_sub_buffer` for details about `flags_opt`.
+    ///
+    pub fn create_sub_buffer_aligned(&self, flags_opt: Option<MemFlags>, desired_offset: usize,
+            len: usize) -> OclResult<(Buffer<T>, usize)>
+    {
+        let context = match self.mem_info(MemInfo::Context) {
+            MemInfoResult::Context(context) => Context::from(context),
+            MemInfoResult::Error(err) => return Err(*err),
+            _ => panic!("ocl::Buffer::create_sub_buffer_aligned: Unexpected 'MemInfoResult' \
+                variant."),
+        };
+
+        let mut align_bytes = 1;
+        for device in context.devices() {
+            // `CL_DEVICE_MEM_BASE_ADDR_ALIGN` is specified in bits:
+            let device_align_bytes = try!(device.mem_base_addr_align()) as usize / 8;
+            align_bytes = ::std::cmp::max(align_bytes, device_align_bytes);
+        }
+
+        // The smallest number of elements spanning a multiple of the
+        // alignment (the least common multiple of the alignment and element
+        // size, in elements):
+        let elem_bytes = mem::size_of::<T>();
+        let align_len = align_bytes / gcd(align_bytes, elem_bytes);
+        let origin = desired_offset / align_len * align_len;
+        let residual = desired_offset - origin;
+
+        let sub_buffer = try!(self.create_sub_buffer(flags_opt, origin, residual + len));
+        Ok((sub_buffer, residual))
+    }
+
     /// Formats memory info.
     #[inline]
     fn fmt_mem_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -1817,6 +2545,57 @@ impl<T: OclPrm> Buffer<T> {
     }
 }
 
+#[cfg(feature = "ndarray")]
+impl<T: OclPrm> Buffer<T> {
+    /// Reads the first `shape.0 * shape.1` elements of this buffer into a new
+    /// two dimensional array (in C/row-major order) using the default queue,
+    /// blocking the current thread until completion.
+    ///
+    /// ### Errors
+    ///
+    /// The number of elements described by `shape` may not exceed the length
+    /// of this buffer. A default queue must be set.
+    ///
+    pub fn read_to_array2(&self, shape: (usize, usize)) -> OclResult<Array2<T>> {
+        let len = shape.0 * shape.1;
+        if len > self.len {
+            return OclError::err_string(format!("ocl::Buffer::read_to_array2: The array shape \
+                provided ({:?}) exceeds the length of the buffer ({}).", shape, self.len));
+        }
+
+        let mut vec = vec![T::default(); len];
+        try!(self.read(&mut vec).enq());
+
+        match Array2::from_shape_vec(shape, vec) {
+            Ok(array) => Ok(array),
+            Err(err) => OclError::err_string(format!("ocl::Buffer::read_to_array2: {}", err)),
+        }
+    }
+
+    /// Writes the contents of `array` (in logical C/row-major order) to this
+    /// buffer using the default queue, blocking the current thread until
+    /// completion.
+    ///
+    /// Arrays which are not contiguous and in standard layout (such as
+    /// transposed or sliced views) are first copied into a temporary
+    /// contiguous vector.
+    ///
+    /// ### Errors
+    ///
+    /// The length of `array` may not exceed the length of this buffer. A
+    /// default queue must be set.
+    ///
+    pub fn write_from_array<D: Dimension>(&self, array: &ArrayView<T, D>) -> OclResult<()> {
+        match array.as_slice() {
+            Some(slice) => self.write(slice).enq(),
+            None => {
+                let vec: Vec<T> = array.iter().cloned().collect();
+                self.write(&vec).enq()
+            },
+        }
+    }
+}
+
 impl<T: OclPrm> Deref for Buffer<T> {
     type Target = MemCore;
 
@@ -1880,7 +2659,8 @@ pub struct BufferBuilder<'a, T> where T: 'a {
     flags: Option<MemFlags>,
     host_data: Option<&'a [T]>,
     dims: Option<SpatialDims>,
-    fill_val: Option<(T, Option<ClNullEventPtrEnum<'a>>)>
+    fill_val: Option<(T, Option<ClNullEventPtrEnum<'a>>)>,
+    prefer_zero_copy: bool,
 }
 
 impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
@@ -1892,6 +2672,7 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
             host_data: None,
             dims: None,
             fill_val: None,
+            prefer_zero_copy: false,
         }
     }
 
@@ -1931,6 +2712,35 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Sets the device access flag to `MEM_READ_ONLY`, replacing any other
+    /// access flag (`MEM_READ_WRITE` or `MEM_WRITE_ONLY`) previously set.
+    ///
+    /// Other flags are preserved. `::read_only`, `::write_only`, and
+    /// `::read_write` are mutually exclusive; the last call wins. A
+    /// subsequent call to `::flags` overwrites all flags.
+    pub fn read_only(self) -> BufferBuilder<'a, T> {
+        self.access_flags(::flags::MEM_READ_ONLY)
+    }
+
+    /// Sets the device access flag to `MEM_WRITE_ONLY`, replacing any other
+    /// access flag previously set (see `::read_only`).
+    pub fn write_only(self) -> BufferBuilder<'a, T> {
+        self.access_flags(::flags::MEM_WRITE_ONLY)
+    }
+
+    /// Sets the device access flag to `MEM_READ_WRITE` (the default),
+    /// replacing any other access flag previously set (see `::read_only`).
+    pub fn read_write(self) -> BufferBuilder<'a, T> {
+        self.access_flags(::flags::MEM_READ_WRITE)
+    }
+
+    /// Replaces the device access flag.
+    fn access_flags(mut self, access: MemFlags) -> BufferBuilder<'a, T> {
+        self.flags = Some(standard::set_access_flags(self.flags.unwrap_or(MemFlags::empty()),
+            access));
+        self
+    }
+
     /// A slice use to designate a region of memory for use in combination of
     /// one of the two following flags:
     ///
@@ -1974,6 +2784,76 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Specifies host data to be copied into the new buffer when it is
+    /// created (`MEM_COPY_HOST_PTR`).
+    ///
+    /// Accepts anything which can be viewed as a slice, such as a `Vec<T>`,
+    /// an array, or a `Box<[T]>`. Adds `MEM_COPY_HOST_PTR` to the flags
+    /// (which otherwise default to `MEM_READ_WRITE`). Equivalent to calling
+    /// `::host_data` and setting the flag manually.
+    ///
+    /// If `::dims` is not set, the length of `data` is used.
+    pub fn copy_host_slice<'d, S>(mut self, data: &'d S) -> BufferBuilder<'a, T>
+            where 'd: 'a, S: AsRef<[T]> + ?Sized
+    {
+        let data = data.as_ref();
+        self.flags = Some(self.flags.unwrap_or(::flags::MEM_READ_WRITE)
+            | ::flags::MEM_COPY_HOST_PTR);
+        if self.dims.is_none() {
+            self.dims = Some(SpatialDims::One(data.len()));
+        }
+        self.host_data = Some(data);
+        self
+    }
+
+    /// Requests write-combined, host-accessible memory, intended for
+    /// upload-only staging buffers.
+    ///
+    /// Sets the flags to `MEM_READ_ONLY | MEM_ALLOC_HOST_PTR |
+    /// MEM_HOST_WRITE_ONLY` (kernels may only read the buffer and the host
+    /// may only write to it), replacing any flags previously set.
+    /// Implementations may then allocate uncached, write-combined memory
+    /// which is fast to write sequentially from the host.
+    ///
+    /// Reading from such memory on the host is very slow (and not permitted
+    /// by OpenCL): mapping the buffer for reading logs a warning when the
+    /// `trace` feature is enabled (the map itself will usually fail).
+    pub fn host_write_combined(mut self) -> BufferBuilder<'a, T> {
+        self.flags = Some(::flags::MEM_READ_ONLY | ::flags::MEM_ALLOC_HOST_PTR |
+            ::flags::MEM_HOST_WRITE_ONLY);
+        self
+    }
+
+    /// Selects flags allowing the host and device to share the buffer's
+    /// memory without copying when the device uses memory unified with the
+    /// host (see `Device::is_unified_memory`), as is typical of integrated
+    /// GPUs.
+    ///
+    /// When every device (of the default queue, or of the context if no
+    /// queue is set) reports unified memory, `MEM_ALLOC_HOST_PTR` is added to
+    /// the flags when building. The flags are otherwise left unchanged (see
+    /// `::zero_copy_flags`).
+    pub fn prefer_zero_copy(mut self) -> BufferBuilder<'a, T> {
+        self.prefer_zero_copy = true;
+        self
+    }
+
+    /// Returns the flags `::prefer_zero_copy` selects, given the flags
+    /// otherwise set, `flags`, and whether or not the device memory is
+    /// unified with the host, `unified_memory`.
+    ///
+    /// `MEM_ALLOC_HOST_PTR` is added for unified memory unless `flags`
+    /// contains `MEM_USE_HOST_PTR` (the two are mutually exclusive). Buffers
+    /// on discrete devices are best kept in device memory and `flags` is
+    /// returned unchanged.
+    pub fn zero_copy_flags(flags: MemFlags, unified_memory: bool) -> MemFlags {
+        if unified_memory && !flags.contains(::flags::MEM_USE_HOST_PTR) {
+            flags | ::flags::MEM_ALLOC_HOST_PTR
+        } else {
+            flags
+        }
+    }
+
     /// Sets the dimensions for this buffer.
     ///
     /// Typically a single integer value to set the total length is used
@@ -2036,7 +2916,24 @@ impl<'a, T> BufferBuilder<'a, T> where T: 'a + OclPrm {
                     None => panic!("ocl::BufferBuilder::build: The dimensions must be set with '.dims(...)'."),
                 };
 
-                Buffer::new(qo, self.flags, dims, self.host_data, self.fill_val)
+                let flags = if self.prefer_zero_copy {
+                    let devices = match qo {
+                        QueCtx::Queue(ref q) => vec![q.device()],
+                        QueCtx::Context(c) => c.devices(),
+                    };
+
+                    let mut unified_memory = !devices.is_empty();
+                    for device in devices.iter() {
+                        unified_memory &= try!(device.is_unified_memory());
+                    }
+
+                    Some(BufferBuilder::<T>::zero_copy_flags(
+                        self.flags.unwrap_or(::flags::MEM_READ_WRITE), unified_memory))
+                } else {
+                    self.flags
+                };
+
+                Buffer::new(qo, flags, dims, self.host_data, self.fill_val)
             },
             None => panic!("ocl::BufferBuilder::build: A context or default queue must be set \
                 with '.context(...)' or '.queue(...)'."),
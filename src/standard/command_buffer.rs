@@ -0,0 +1,137 @@
+//! A software emulated, replayable command buffer.
+
+use std;
+use core::OclPrm;
+use core::error::{Result as OclResult};
+use standard::{Queue, Kernel, Buffer, Event};
+use async::RwVec;
+
+type RecordedCmd = Box<FnMut(&Queue, Option<&Event>, &mut Event) -> OclResult<()>>;
+
+
+/// A recorded sequence of commands which can be replayed (re-enqueued) any
+/// number of times.
+///
+/// Commands are stored, along with their arguments, in the order they are
+/// recorded. Each call to `::replay` re-issues every command in that same
+/// order with each command waiting on the completion of the one before it.
+/// Only the event dependencies are resolved anew on each replay.
+///
+/// This is a software emulation of command buffers and does not make use of
+/// the `cl_khr_command_buffer` extension.
+///
+/// Kernels are recorded by cloning and must therefore have all of their
+/// arguments specified before being recorded. Changes to scalar arguments
+/// made to the original kernel after recording will be visible to the
+/// recorded copy (the underlying kernel object is shared).
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+pub struct CommandBuffer {
+    cmds: Vec<RecordedCmd>,
+}
+
+impl CommandBuffer {
+    /// Returns a new, empty command buffer.
+    pub fn new() -> CommandBuffer {
+        CommandBuffer { cmds: Vec::new() }
+    }
+
+    /// Records a kernel enqueue using the kernel's current global work
+    /// offset, global work size, and local work size.
+    ///
+    /// ## Panics
+    ///
+    /// All of the kernel's arguments must be specified.
+    pub fn kernel(&mut self, kernel: &Kernel) -> &mut CommandBuffer {
+        let kernel = kernel.clone();
+
+        self.cmds.push(Box::new(move |queue, ewait, enew| {
+            kernel.cmd().queue(queue).ewait_opt(ewait).enew(enew).enq()
+        }));
+        self
+    }
+
+    /// Records a (blocking) read from `buffer` into `dst`.
+    pub fn read<T: OclPrm>(&mut self, buffer: &Buffer<T>, dst: &RwVec<T>) -> &mut CommandBuffer {
+        let buffer = buffer.clone();
+        let dst = dst.clone();
+
+        self.cmds.push(Box::new(move |queue, ewait, enew| {
+            buffer.read(dst.clone()).queue(queue).ewait_opt(ewait).enew(enew).enq()
+        }));
+        self
+    }
+
+    /// Records a (blocking) write from `src` into `buffer`.
+    pub fn write<T: OclPrm>(&mut self, buffer: &Buffer<T>, src: &RwVec<T>) -> &mut CommandBuffer {
+        let buffer = buffer.clone();
+        let src = src.clone();
+
+        self.cmds.push(Box::new(move |queue, ewait, enew| {
+            buffer.write(src.clone()).queue(queue).ewait_opt(ewait).enew(enew).enq()
+        }));
+        self
+    }
+
+    /// Records a copy of the entire contents of `src` into `dst`.
+    pub fn copy<T: OclPrm>(&mut self, src: &Buffer<T>, dst: &Buffer<T>) -> &mut CommandBuffer {
+        let src = src.clone();
+        let dst = dst.clone();
+
+        self.cmds.push(Box::new(move |queue, ewait, enew| {
+            src.cmd().copy(&dst, None, None).queue(queue).ewait_opt(ewait).enew(enew).enq()
+        }));
+        self
+    }
+
+    /// Records a fill of the entire contents of `buffer` with `pattern`.
+    pub fn fill<T: OclPrm>(&mut self, buffer: &Buffer<T>, pattern: T) -> &mut CommandBuffer {
+        let buffer = buffer.clone();
+
+        self.cmds.push(Box::new(move |queue, ewait, enew| {
+            buffer.cmd().fill(pattern, None).queue(queue).ewait_opt(ewait).enew(enew).enq()
+        }));
+        self
+    }
+
+    /// Re-issues each recorded command, in order, on `queue`.
+    ///
+    /// Each command waits on the completion of the previous one. Returns the
+    /// event associated with the last command (an empty event if no commands
+    /// have been recorded).
+    pub fn replay(&mut self, queue: &Queue) -> OclResult<Event> {
+        let mut prev_event: Option<Event> = None;
+
+        for cmd in self.cmds.iter_mut() {
+            let mut new_event = Event::empty();
+            try!(cmd(queue, prev_event.as_ref(), &mut new_event));
+            prev_event = Some(new_event);
+        }
+
+        Ok(prev_event.unwrap_or_else(Event::empty))
+    }
+
+    /// Returns the number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.cmds.len()
+    }
+
+    /// Returns true if no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.cmds.is_empty()
+    }
+
+    /// Removes all recorded commands.
+    pub fn clear(&mut self) {
+        self.cmds.clear();
+    }
+}
+
+impl std::fmt::Debug for CommandBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CommandBuffer")
+            .field("len", &self.cmds.len())
+            .finish()
+    }
+}
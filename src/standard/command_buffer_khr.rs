@@ -0,0 +1,378 @@
+//! Native command buffers (`cl_khr_command_buffer`).
+//!
+//! The extension functions are not part of the core API and are loaded at
+//! runtime using `clGetExtensionFunctionAddressForPlatform`. Function
+//! signatures correspond to revision 0.9.5 (and later) of the extension
+//! specification.
+//!
+//! [UNSTABLE]: Provisional extension. Only kernel, copy, and fill commands are
+//! currently supported.
+
+use std;
+use std::ptr;
+use std::mem;
+use std::ffi::CString;
+use libc::{c_void, size_t};
+use num::FromPrimitive;
+use ffi::{self, cl_int, cl_uint, cl_ulong, cl_command_queue, cl_kernel, cl_mem, cl_event,
+    cl_platform_id};
+use core::{OclPrm, Status, DeviceInfo, DeviceInfoResult, ClWaitListPtr, ClNullEventPtr, AsMem};
+use core::error::{Result as OclResult, Error as OclError};
+use standard::{self, Queue, Kernel, Buffer, Device, WorkDims};
+
+/// The name of the extension as reported in a device's extension string.
+pub const EXTENSION_NAME: &'static str = "cl_khr_command_buffer";
+
+#[allow(non_camel_case_types)]
+type cl_command_buffer_khr = *mut c_void;
+#[allow(non_camel_case_types)]
+type cl_sync_point_khr = cl_uint;
+#[allow(non_camel_case_types)]
+type cl_mutable_command_khr = *mut c_void;
+#[allow(non_camel_case_types)]
+type cl_properties = cl_ulong;
+
+type CreateCommandBufferFn = extern "C" fn(cl_uint, *const cl_command_queue,
+    *const cl_properties, *mut cl_int) -> cl_command_buffer_khr;
+type FinalizeCommandBufferFn = extern "C" fn(cl_command_buffer_khr) -> cl_int;
+type ReleaseCommandBufferFn = extern "C" fn(cl_command_buffer_khr) -> cl_int;
+type EnqueueCommandBufferFn = extern "C" fn(cl_uint, *mut cl_command_queue,
+    cl_command_buffer_khr, cl_uint, *const cl_event, *mut cl_event) -> cl_int;
+type CommandNdRangeKernelFn = extern "C" fn(cl_command_buffer_khr, cl_command_queue,
+    *const cl_properties, cl_kernel, cl_uint, *const size_t, *const size_t, *const size_t,
+    cl_uint, *const cl_sync_point_khr, *mut cl_sync_point_khr, *mut cl_mutable_command_khr)
+    -> cl_int;
+type CommandCopyBufferFn = extern "C" fn(cl_command_buffer_khr, cl_command_queue,
+    *const cl_properties, cl_mem, cl_mem, size_t, size_t, size_t, cl_uint,
+    *const cl_sync_point_khr, *mut cl_sync_point_khr, *mut cl_mutable_command_khr) -> cl_int;
+type CommandFillBufferFn = extern "C" fn(cl_command_buffer_khr, cl_command_queue,
+    *const cl_properties, cl_mem, *const c_void, size_t, size_t, size_t, cl_uint,
+    *const cl_sync_point_khr, *mut cl_sync_point_khr, *mut cl_mutable_command_khr) -> cl_int;
+
+
+/// Returns an error unless the `len` elements starting at `offset` lie
+/// within a buffer of length `buffer_len`.
+fn check_range(offset: usize, len: usize, buffer_len: usize, fn_name: &'static str,
+        range_name: &'static str) -> OclResult<()>
+{
+    if offset.checked_add(len).map(|end| end > buffer_len).unwrap_or(true) {
+        OclError::err_string(format!("ocl::CommandBufferKhr::{}: The {} range (offset: {}, \
+            len: {}) exceeds the length of the buffer ({}).", fn_name, range_name, offset, len,
+            buffer_len))
+    } else {
+        Ok(())
+    }
+}
+
+/// Converts an error code into a result.
+fn eval_errcode(errcode: cl_int, fn_name: &'static str) -> OclResult<()> {
+    if errcode == 0 {
+        Ok(())
+    } else {
+        OclError::err_string(format!("ocl::CommandBufferKhr: '{}' failed with status: {:?} ({})",
+            fn_name, Status::from_i32(errcode), errcode))
+    }
+}
+
+/// Loads an extension function pointer by name.
+unsafe fn load_fn(platform: cl_platform_id, name: &'static str) -> OclResult<*mut c_void> {
+    let c_name = CString::new(name).expect("ocl::CommandBufferKhr: Invalid function name.");
+    let fn_ptr = ffi::clGetExtensionFunctionAddressForPlatform(platform, c_name.as_ptr());
+
+    if fn_ptr.is_null() {
+        OclError::err_string(format!("ocl::CommandBufferKhr: Unable to load extension \
+            function: '{}'.", name))
+    } else {
+        Ok(fn_ptr as *mut c_void)
+    }
+}
+
+
+/// Extension function pointers.
+#[derive(Clone, Copy)]
+struct CommandBufferFns {
+    create: CreateCommandBufferFn,
+    finalize: FinalizeCommandBufferFn,
+    release: ReleaseCommandBufferFn,
+    enqueue: EnqueueCommandBufferFn,
+    nd_range_kernel: CommandNdRangeKernelFn,
+    copy_buffer: CommandCopyBufferFn,
+    fill_buffer: CommandFillBufferFn,
+}
+
+impl CommandBufferFns {
+    fn load(platform: cl_platform_id) -> OclResult<CommandBufferFns> {
+        unsafe {
+            Ok(CommandBufferFns {
+                create: mem::transmute(try!(load_fn(platform, "clCreateCommandBufferKHR"))),
+                finalize: mem::transmute(try!(load_fn(platform, "clFinalizeCommandBufferKHR"))),
+                release: mem::transmute(try!(load_fn(platform, "clReleaseCommandBufferKHR"))),
+                enqueue: mem::transmute(try!(load_fn(platform, "clEnqueueCommandBufferKHR"))),
+                nd_range_kernel: mem::transmute(try!(load_fn(platform,
+                    "clCommandNDRangeKernelKHR"))),
+                copy_buffer: mem::transmute(try!(load_fn(platform, "clCommandCopyBufferKHR"))),
+                fill_buffer: mem::transmute(try!(load_fn(platform, "clCommandFillBufferKHR"))),
+            })
+        }
+    }
+}
+
+
+/// A native command buffer created using the `cl_khr_command_buffer`
+/// extension.
+///
+/// Commands are recorded using `::kernel`, `::copy`, and `::fill`. Once all
+/// commands have been recorded, call `::finalize`. A finalized command buffer
+/// can then be enqueued any number of times with `::enq`.
+///
+/// Commands within the command buffer execute in the order they were
+/// recorded.
+///
+/// See `CommandBuffer` for a software emulated equivalent which works on
+/// any device.
+///
+pub struct CommandBufferKhr {
+    ptr: cl_command_buffer_khr,
+    queue: Queue,
+    fns: CommandBufferFns,
+    last_sync_point: Option<cl_sync_point_khr>,
+    is_finalized: bool,
+}
+
+impl CommandBufferKhr {
+    /// Returns true if `device` supports the `cl_khr_command_buffer`
+    /// extension.
+    pub fn is_supported(device: &Device) -> bool {
+        match device.info(DeviceInfo::Extensions) {
+            DeviceInfoResult::Extensions(exts) => standard::clean_info_string(exts)
+                .split_whitespace()
+                .any(|ext| ext == EXTENSION_NAME),
+            _ => false,
+        }
+    }
+
+    /// Creates a new, empty command buffer associated with `queue`.
+    ///
+    /// ### Errors
+    ///
+    /// The device associated with `queue` must support the
+    /// `cl_khr_command_buffer` extension.
+    pub fn new(queue: &Queue) -> OclResult<CommandBufferKhr> {
+        let device = queue.device();
+
+        if !CommandBufferKhr::is_supported(&device) {
+            return OclError::err_string(format!("ocl::CommandBufferKhr::new: The device \
+                '{}' does not support the '{}' extension.", device.name(), EXTENSION_NAME));
+        }
+
+        let platform = match device.info(DeviceInfo::Platform) {
+            DeviceInfoResult::Platform(p) => p,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => panic!("CommandBufferKhr::new: Unexpected 'DeviceInfoResult' variant."),
+        };
+
+        let fns = try!(CommandBufferFns::load(platform.as_ptr()));
+        let queue_ptr = queue.core().as_ptr();
+        let mut errcode: cl_int = 0;
+
+        let ptr = (fns.create)(1, &queue_ptr, ptr::null(), &mut errcode);
+        try!(eval_errcode(errcode, "clCreateCommandBufferKHR"));
+
+        Ok(CommandBufferKhr {
+            ptr: ptr,
+            queue: queue.clone(),
+            fns: fns,
+            last_sync_point: None,
+            is_finalized: false,
+        })
+    }
+
+    /// Returns the sync point wait list (the previous command, if any) and
+    /// a pointer to store the new sync point.
+    fn sync_wait_list(&self) -> (cl_uint, *const cl_sync_point_khr) {
+        match self.last_sync_point {
+            Some(ref sp) => (1, sp as *const cl_sync_point_khr),
+            None => (0, ptr::null()),
+        }
+    }
+
+    fn assert_unfinalized(&self, fn_name: &'static str) {
+        assert!(!self.is_finalized, "ocl::CommandBufferKhr::{}: Commands cannot be recorded \
+            after the command buffer has been finalized.", fn_name);
+    }
+
+    /// Records a kernel command using the kernel's current global work
+    /// offset, global work size, and local work size.
+    ///
+    /// ## Panics
+    ///
+    /// The command buffer must not have been finalized.
+    pub fn kernel(&mut self, kernel: &Kernel) -> OclResult<&mut CommandBufferKhr> {
+        self.assert_unfinalized("kernel");
+
+        let gws = match kernel.get_gws().to_work_size() {
+            Some(gws) => gws,
+            None => return OclError::err_string("ocl::CommandBufferKhr::kernel: Global Work \
+                Size ('gws') cannot be left unspecified."),
+        };
+        let gwo = kernel.get_gwo().to_work_offset();
+        let lws = kernel.get_lws().to_work_size();
+
+        let (wait_count, wait_ptr) = self.sync_wait_list();
+        let mut sync_point: cl_sync_point_khr = 0;
+
+        let errcode = (self.fns.nd_range_kernel)(self.ptr, ptr::null_mut(), ptr::null(),
+            kernel.core().as_ptr(), kernel.get_gws().dim_count(),
+            gwo.as_ref().map(|o| o.as_ptr()).unwrap_or(ptr::null()), gws.as_ptr(),
+            lws.as_ref().map(|l| l.as_ptr()).unwrap_or(ptr::null()),
+            wait_count, wait_ptr, &mut sync_point, ptr::null_mut());
+        try!(eval_errcode(errcode, "clCommandNDRangeKernelKHR"));
+
+        self.last_sync_point = Some(sync_point);
+        Ok(self)
+    }
+
+    /// Records a copy of `len` elements (the remainder of the source buffer
+    /// after `src_offset` if `None`) from `src` to `dst`.
+    ///
+    /// ### Errors
+    ///
+    /// The source and destination ranges must lie within `src` and `dst`.
+    ///
+    /// ## Panics
+    ///
+    /// The command buffer must not have been finalized.
+    pub fn copy<T: OclPrm>(&mut self, src: &Buffer<T>, dst: &Buffer<T>, src_offset: usize,
+            dst_offset: usize, len: Option<usize>) -> OclResult<&mut CommandBufferKhr>
+    {
+        self.assert_unfinalized("copy");
+
+        if src_offset > src.len() {
+            return OclError::err_string(format!("ocl::CommandBufferKhr::copy: The source offset \
+                ({}) exceeds the length of the source buffer ({}).", src_offset, src.len()));
+        }
+
+        let len = len.unwrap_or(src.len() - src_offset);
+        try!(check_range(src_offset, len, src.len(), "copy", "source"));
+        try!(check_range(dst_offset, len, dst.len(), "copy", "destination"));
+
+        let ele_size = mem::size_of::<T>();
+        let (wait_count, wait_ptr) = self.sync_wait_list();
+        let mut sync_point: cl_sync_point_khr = 0;
+
+        let errcode = (self.fns.copy_buffer)(self.ptr, ptr::null_mut(), ptr::null(),
+            src.as_mem().as_ptr(), dst.as_mem().as_ptr(), src_offset * ele_size,
+            dst_offset * ele_size, len * ele_size, wait_count, wait_ptr, &mut sync_point,
+            ptr::null_mut());
+        try!(eval_errcode(errcode, "clCommandCopyBufferKHR"));
+
+        self.last_sync_point = Some(sync_point);
+        Ok(self)
+    }
+
+    /// Records a fill of `len` elements (the remainder of the buffer after
+    /// `offset` if `None`) of `buffer` with `pattern`, starting at `offset`.
+    ///
+    /// ### Errors
+    ///
+    /// The filled range must lie within `buffer`.
+    ///
+    /// ## Panics
+    ///
+    /// The command buffer must not have been finalized.
+    pub fn fill<T: OclPrm>(&mut self, buffer: &Buffer<T>, pattern: T, offset: usize,
+            len: Option<usize>) -> OclResult<&mut CommandBufferKhr>
+    {
+        self.assert_unfinalized("fill");
+
+        if offset > buffer.len() {
+            return OclError::err_string(format!("ocl::CommandBufferKhr::fill: The offset ({}) \
+                exceeds the length of the buffer ({}).", offset, buffer.len()));
+        }
+
+        let len = len.unwrap_or(buffer.len() - offset);
+        try!(check_range(offset, len, buffer.len(), "fill", "fill"));
+
+        let ele_size = mem::size_of::<T>();
+        let (wait_count, wait_ptr) = self.sync_wait_list();
+        let mut sync_point: cl_sync_point_khr = 0;
+
+        let errcode = (self.fns.fill_buffer)(self.ptr, ptr::null_mut(), ptr::null(),
+            buffer.as_mem().as_ptr(), &pattern as *const T as *const c_void, ele_size,
+            offset * ele_size, len * ele_size, wait_count, wait_ptr, &mut sync_point,
+            ptr::null_mut());
+        try!(eval_errcode(errcode, "clCommandFillBufferKHR"));
+
+        self.last_sync_point = Some(sync_point);
+        Ok(self)
+    }
+
+    /// Finalizes this command buffer, after which no more commands may be
+    /// recorded and it may be enqueued.
+    pub fn finalize(&mut self) -> OclResult<()> {
+        if self.is_finalized { return Ok(()); }
+        try!(eval_errcode((self.fns.finalize)(self.ptr), "clFinalizeCommandBufferKHR"));
+        self.is_finalized = true;
+        Ok(())
+    }
+
+    /// Enqueues this command buffer on the queue it was created with.
+    ///
+    /// ### Errors
+    ///
+    /// The command buffer must have been finalized.
+    pub fn enq<Ewl, En>(&self, ewait: Option<Ewl>, enew: Option<En>) -> OclResult<()>
+            where Ewl: ClWaitListPtr, En: ClNullEventPtr
+    {
+        if !self.is_finalized {
+            return OclError::err_string("ocl::CommandBufferKhr::enq: The command buffer must \
+                be finalized (using '::finalize') before being enqueued.");
+        }
+
+        let (wait_count, wait_ptr) = match ewait {
+            Some(ref el) => unsafe { (el.count(), el.as_ptr_ptr()) },
+            None => (0, ptr::null()),
+        };
+
+        let mut enew = enew;
+        let new_event_ptr = match enew {
+            Some(ref mut ne) => ne.alloc_new(),
+            None => ptr::null_mut(),
+        };
+
+        // Enqueue on the queue used during creation (passing no queues).
+        let errcode = (self.fns.enqueue)(0, ptr::null_mut(), self.ptr, wait_count, wait_ptr,
+            new_event_ptr);
+        eval_errcode(errcode, "clEnqueueCommandBufferKHR")
+    }
+
+    /// Returns the queue this command buffer was created with.
+    pub fn queue(&self) -> &Queue {
+        &self.queue
+    }
+
+    /// Returns true if this command buffer has been finalized.
+    pub fn is_finalized(&self) -> bool {
+        self.is_finalized
+    }
+}
+
+impl Drop for CommandBufferKhr {
+    fn drop(&mut self) {
+        // A failed release cannot be reported from here (it is traced when
+        // the `trace` feature is enabled):
+        let res = eval_errcode((self.fns.release)(self.ptr), "clReleaseCommandBufferKHR");
+        trace_cl!("clReleaseCommandBufferKHR", &res, "command_buffer: {:?}", self.ptr);
+        let _ = res;
+    }
+}
+
+impl std::fmt::Debug for CommandBufferKhr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("CommandBufferKhr")
+            .field("ptr", &self.ptr)
+            .field("queue", &self.queue)
+            .field("is_finalized", &self.is_finalized)
+            .finish()
+    }
+}
@@ -0,0 +1,95 @@
+//! A read-only buffer containing a single value, intended for `__constant`
+//! kernel arguments.
+
+use std;
+use std::mem;
+use std::ops::Deref;
+use core::{OclPrm, Mem as MemCore, AsMem, MemCmdAll, DeviceInfo, DeviceInfoResult};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Buffer};
+
+
+/// A `MEM_READ_ONLY` buffer holding a single value of type `T` (such as a
+/// block of parameters or a matrix) to be passed as a `__constant` kernel
+/// argument and shared between any number of kernels.
+///
+/// Pass to a kernel as any other buffer, i.e.: `.arg_buf(&constant_buffer)`.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct ConstantBuffer<T: OclPrm> {
+    buffer: Buffer<T>,
+}
+
+impl<T: OclPrm> ConstantBuffer<T> {
+    /// Returns a new constant buffer initialized with `value`.
+    ///
+    /// ### Errors
+    ///
+    /// The size of `T` must not exceed the maximum constant buffer size of
+    /// the device associated with `queue`
+    /// (`CL_DEVICE_MAX_CONSTANT_BUFFER_SIZE`).
+    ///
+    pub fn new(queue: &Queue, value: &T) -> OclResult<ConstantBuffer<T>> {
+        let device = queue.device();
+
+        let max_size = match device.info(DeviceInfo::MaxConstantBufferSize) {
+            DeviceInfoResult::MaxConstantBufferSize(s) => s,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => panic!("ocl::ConstantBuffer::new: Unexpected 'DeviceInfoResult' variant."),
+        };
+
+        if mem::size_of::<T>() as u64 > max_size {
+            return OclError::err_string(format!("ocl::ConstantBuffer::new: The size of the value \
+                ({} bytes) exceeds the maximum constant buffer size of the device '{}' \
+                ({} bytes).", mem::size_of::<T>(), device.name(), max_size));
+        }
+
+        let buffer = try!(Buffer::<T>::builder()
+            .queue(queue.clone())
+            .flags(::flags::MEM_READ_ONLY | ::flags::MEM_COPY_HOST_PTR)
+            .dims(1)
+            .host_data(&[*value])
+            .build());
+
+        Ok(ConstantBuffer { buffer: buffer })
+    }
+
+    /// Writes `value` to the buffer (blocking).
+    ///
+    /// Kernels enqueued before this call (on the same in-order queue) will
+    /// see the previous value.
+    pub fn update(&self, value: &T) -> OclResult<()> {
+        self.buffer.write(&[*value][..]).enq()
+    }
+
+    /// Reads the current value from the buffer (blocking).
+    pub fn get(&self) -> OclResult<T> {
+        let mut value = [T::default()];
+        try!(self.buffer.read(&mut value[..]).enq());
+        Ok(value[0])
+    }
+
+    /// Returns a reference to the underlying buffer.
+    pub fn buffer(&self) -> &Buffer<T> {
+        &self.buffer
+    }
+}
+
+impl<T: OclPrm> Deref for ConstantBuffer<T> {
+    type Target = Buffer<T>;
+
+    fn deref(&self) -> &Buffer<T> {
+        &self.buffer
+    }
+}
+
+impl<T: OclPrm> AsMem<T> for ConstantBuffer<T> {
+    fn as_mem(&self) -> &MemCore {
+        self.buffer.as_mem()
+    }
+}
+
+unsafe impl<T> MemCmdAll for ConstantBuffer<T> where T: OclPrm {}
+unsafe impl<'a, T> MemCmdAll for &'a ConstantBuffer<T> where T: OclPrm {}
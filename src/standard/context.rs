@@ -5,9 +5,14 @@ use std::ops::{Deref, DerefMut};
 use ffi::cl_context;
 use core::{self, Context as ContextCore, ContextProperties, ContextPropertyValue, ContextInfo,
     ContextInfoResult, DeviceInfo, DeviceInfoResult, PlatformId as PlatformIdCore, PlatformInfo,
-    PlatformInfoResult, CreateContextCallbackFn, UserDataPtr, OpenclVersion, ClContextPtr};
+    PlatformInfoResult, CreateContextCallbackFn, UserDataPtr, OpenclVersion, ClContextPtr,
+    ImageFormat, MemFlags, MemObjectType, ImageChannelOrder, ImageChannelDataType, DeviceType};
 use core::error::{Result as OclResult, Error as OclError};
-use standard::{Platform, Device, DeviceSpecifier};
+use standard::{Platform, Device, DeviceSpecifier, Queue};
+#[cfg(feature = "opencl_version_2_1")]
+use ffi;
+#[cfg(feature = "opencl_version_2_1")]
+use core::ClDeviceIdPtr;
 
 
 
@@ -80,6 +85,37 @@ impl Context {
         Ok(Context(obj_core))
     }
 
+    /// Returns a new context spanning every device of type `device_type`
+    /// (e.g. `flags::DEVICE_TYPE_GPU`) on `platform`.
+    ///
+    /// Use with `::queues_per_device` to fan work out across all devices.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if `platform` has no devices of the given type.
+    ///
+    pub fn all_devices_of_type(platform: Platform, device_type: DeviceType)
+            -> OclResult<Context>
+    {
+        let devices = try!(Device::list(platform, Some(device_type)));
+
+        if devices.is_empty() {
+            return OclError::err_string(format!("Context::all_devices_of_type: No devices of \
+                type '{:?}' found on platform '{}'.", device_type, platform.name()));
+        }
+
+        Context::builder()
+            .platform(platform)
+            .devices(DeviceSpecifier::List(devices))
+            .build()
+    }
+
+    /// Returns a new queue (with default properties) for each device
+    /// associated with this context, in the order returned by `::devices`.
+    pub fn queues_per_device(&self) -> OclResult<Vec<Queue>> {
+        self.devices().into_iter().map(|device| Queue::new(self, device, None)).collect()
+    }
+
     /// Resolves a list of zero-based device indices into a list of Devices.
     ///
     /// If any index is out of bounds it will wrap around zero (%) to the next
@@ -174,6 +210,65 @@ impl Context {
         self.0.platform().map(|opt| opt.map(Platform::from))
     }
 
+    /// Returns the list of image formats supported by this context for
+    /// images of type `image_type` created with `flags`.
+    ///
+    /// Wraps `clGetSupportedImageFormats`.
+    pub fn supported_image_formats(&self, flags: MemFlags, image_type: MemObjectType)
+            -> OclResult<Vec<ImageFormat>>
+    {
+        core::get_supported_image_formats(&self.0, flags, image_type)
+    }
+
+    /// Returns true if images of type `image_type`, created with `flags`,
+    /// support the format described by `channel_order` and
+    /// `channel_data_type`.
+    pub fn is_image_format_supported(&self, flags: MemFlags, image_type: MemObjectType,
+            channel_order: ImageChannelOrder, channel_data_type: ImageChannelDataType)
+            -> OclResult<bool>
+    {
+        let formats = try!(self.supported_image_formats(flags, image_type));
+
+        Ok(formats.iter().any(|fmt| fmt.channel_order == channel_order &&
+            fmt.channel_data_type == channel_data_type))
+    }
+
+    /// Sets `queue` as the default on-device queue for `device`
+    /// (`clSetDefaultDeviceCommandQueue`).
+    ///
+    /// Kernels enqueued from within other kernels using
+    /// `get_default_queue()` are enqueued on the default on-device queue.
+    /// The current default can be retrieved with `Queue::device_default`.
+    ///
+    /// ### Errors
+    ///
+    /// `queue` must have been created on `device` with the `QUEUE_ON_DEVICE`
+    /// property.
+    ///
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn set_default_device_queue(&self, device: &Device, queue: &Queue) -> OclResult<()> {
+        if !try!(queue.properties()).contains(core::QUEUE_ON_DEVICE) {
+            return Err("ocl::Context::set_default_device_queue: The queue must be created with \
+                the 'QUEUE_ON_DEVICE' property.".into());
+        }
+
+        if queue.device() != *device {
+            return OclError::err_string(format!("ocl::Context::set_default_device_queue: The \
+                queue is not associated with the device '{}'.", device.name()));
+        }
+
+        let errcode = unsafe {
+            ffi::clSetDefaultDeviceCommandQueue(self.0.as_ptr(), device.as_ptr(), queue.as_ptr())
+        };
+
+        if errcode == ffi::CL_SUCCESS as ffi::cl_int {
+            Ok(())
+        } else {
+            OclError::err_string(format!("ocl::Context::set_default_device_queue: \
+                'clSetDefaultDeviceCommandQueue' failed with error code: {}", errcode))
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Context")
             .field("ReferenceCount", &self.info(ContextInfo::ReferenceCount))
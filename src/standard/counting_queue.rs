@@ -0,0 +1,108 @@
+//! A queue wrapper which counts the commands enqueued through it.
+
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use core::OclPrm;
+use core::error::{Result as OclResult};
+use standard::{Queue, KernelCmd, BufferCmd, BufferReadCmd, BufferWriteCmd, ImageCmd};
+
+
+/// Command builders which can be enqueued on a specified queue.
+///
+/// Implemented by the kernel, buffer, and image command builders for use
+/// with `CountingQueue::enq`.
+pub trait EnqueueOn<'q> {
+    /// Enqueues this command on `queue`, overriding any queue previously
+    /// specified.
+    fn enq_on(self, queue: &'q Queue) -> OclResult<()>;
+}
+
+impl<'k> EnqueueOn<'k> for KernelCmd<'k> {
+    fn enq_on(self, queue: &'k Queue) -> OclResult<()> {
+        self.queue(queue).enq()
+    }
+}
+
+impl<'c, T: OclPrm> EnqueueOn<'c> for BufferCmd<'c, T> {
+    fn enq_on(self, queue: &'c Queue) -> OclResult<()> {
+        self.queue(queue).enq()
+    }
+}
+
+impl<'c, 'd, T: OclPrm> EnqueueOn<'c> for BufferReadCmd<'c, 'd, T> {
+    fn enq_on(self, queue: &'c Queue) -> OclResult<()> {
+        self.queue(queue).enq()
+    }
+}
+
+impl<'c, 'd, T: OclPrm> EnqueueOn<'c> for BufferWriteCmd<'c, 'd, T> {
+    fn enq_on(self, queue: &'c Queue) -> OclResult<()> {
+        self.queue(queue).enq()
+    }
+}
+
+impl<'c, T: OclPrm> EnqueueOn<'c> for ImageCmd<'c, T> {
+    fn enq_on(self, queue: &'c Queue) -> OclResult<()> {
+        self.queue(queue).enq()
+    }
+}
+
+
+/// A `Queue` which counts the number of commands enqueued through
+/// `::enq`.
+///
+/// Intended for debugging throughput problems, such as accidentally
+/// enqueuing one command per element. Commands enqueued directly on the
+/// underlying queue (including through `Deref`) are not counted. Clones
+/// share the same count.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct CountingQueue {
+    queue: Queue,
+    count: Arc<AtomicUsize>,
+}
+
+impl CountingQueue {
+    /// Returns a new counting wrapper around `queue` with a count of zero.
+    pub fn new(queue: Queue) -> CountingQueue {
+        CountingQueue {
+            queue: queue,
+            count: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Enqueues `cmd` on this queue and increments the command count.
+    ///
+    /// Commands which fail to enqueue are not counted.
+    pub fn enq<'q, C: EnqueueOn<'q>>(&'q self, cmd: C) -> OclResult<()> {
+        try!(cmd.enq_on(&self.queue));
+        self.count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the number of commands enqueued through `::enq`.
+    pub fn command_count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Resets the command count to zero, returning the previous count.
+    pub fn reset_count(&self) -> usize {
+        self.count.swap(0, Ordering::Relaxed)
+    }
+
+    /// Returns a reference to the underlying queue.
+    pub fn queue(&self) -> &Queue {
+        &self.queue
+    }
+}
+
+impl Deref for CountingQueue {
+    type Target = Queue;
+
+    fn deref(&self) -> &Queue {
+        &self.queue
+    }
+}
@@ -3,12 +3,107 @@
 use std;
 use std::ops::{Deref, DerefMut};
 use std::borrow::Borrow;
+use std::{mem, ptr};
+use std::collections::BTreeMap;
+use libc::c_void;
 use core::error::{Error as OclError, Result as OclResult};
-use standard::Platform;
-use ffi::cl_device_id;
-use core::{self, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr};
+use standard::{self, Platform};
+use standard::platform::enumeration_cache;
+use ffi::{self, cl_device_id};
+use core::{self, DeviceId as DeviceIdCore, DeviceType, DeviceInfo, DeviceInfoResult, ClDeviceIdPtr,
+    ClPlatformIdPtr};
 use core::util;
 
+const CL_DEVICE_MAX_NUM_SUB_GROUPS: ffi::cl_uint = 0x105C;
+const CL_DEVICE_UUID_KHR: ffi::cl_uint = 0x106A;
+const CL_DEVICE_PCI_BUS_INFO_KHR: ffi::cl_uint = 0x410F;
+const CL_DEVICE_PCI_BUS_ID_NV: ffi::cl_uint = 0x4008;
+const CL_DEVICE_PCI_SLOT_ID_NV: ffi::cl_uint = 0x4009;
+const CL_DEVICE_PCI_DOMAIN_ID_NV: ffi::cl_uint = 0x400A;
+const CL_DEVICE_TOPOLOGY_AMD: ffi::cl_uint = 0x4037;
+const CL_DEVICE_TOPOLOGY_TYPE_PCIE_AMD: u32 = 1;
+const CL_DEVICE_GLOBAL_FREE_MEMORY_AMD: ffi::cl_uint = 0x4039;
+
+/// Every device info kind, along with its name, queried by `Device::info_map`.
+static DEVICE_INFO_KINDS: &'static [(&'static str, DeviceInfo)] = &[
+    ("Type", DeviceInfo::Type),
+    ("VendorId", DeviceInfo::VendorId),
+    ("MaxComputeUnits", DeviceInfo::MaxComputeUnits),
+    ("MaxWorkItemDimensions", DeviceInfo::MaxWorkItemDimensions),
+    ("MaxWorkGroupSize", DeviceInfo::MaxWorkGroupSize),
+    ("MaxWorkItemSizes", DeviceInfo::MaxWorkItemSizes),
+    ("PreferredVectorWidthChar", DeviceInfo::PreferredVectorWidthChar),
+    ("PreferredVectorWidthShort", DeviceInfo::PreferredVectorWidthShort),
+    ("PreferredVectorWidthInt", DeviceInfo::PreferredVectorWidthInt),
+    ("PreferredVectorWidthLong", DeviceInfo::PreferredVectorWidthLong),
+    ("PreferredVectorWidthFloat", DeviceInfo::PreferredVectorWidthFloat),
+    ("PreferredVectorWidthDouble", DeviceInfo::PreferredVectorWidthDouble),
+    ("MaxClockFrequency", DeviceInfo::MaxClockFrequency),
+    ("AddressBits", DeviceInfo::AddressBits),
+    ("MaxReadImageArgs", DeviceInfo::MaxReadImageArgs),
+    ("MaxWriteImageArgs", DeviceInfo::MaxWriteImageArgs),
+    ("MaxMemAllocSize", DeviceInfo::MaxMemAllocSize),
+    ("Image2dMaxWidth", DeviceInfo::Image2dMaxWidth),
+    ("Image2dMaxHeight", DeviceInfo::Image2dMaxHeight),
+    ("Image3dMaxWidth", DeviceInfo::Image3dMaxWidth),
+    ("Image3dMaxHeight", DeviceInfo::Image3dMaxHeight),
+    ("Image3dMaxDepth", DeviceInfo::Image3dMaxDepth),
+    ("ImageSupport", DeviceInfo::ImageSupport),
+    ("MaxParameterSize", DeviceInfo::MaxParameterSize),
+    ("MaxSamplers", DeviceInfo::MaxSamplers),
+    ("MemBaseAddrAlign", DeviceInfo::MemBaseAddrAlign),
+    ("MinDataTypeAlignSize", DeviceInfo::MinDataTypeAlignSize),
+    ("SingleFpConfig", DeviceInfo::SingleFpConfig),
+    ("GlobalMemCacheType", DeviceInfo::GlobalMemCacheType),
+    ("GlobalMemCachelineSize", DeviceInfo::GlobalMemCachelineSize),
+    ("GlobalMemCacheSize", DeviceInfo::GlobalMemCacheSize),
+    ("GlobalMemSize", DeviceInfo::GlobalMemSize),
+    ("MaxConstantBufferSize", DeviceInfo::MaxConstantBufferSize),
+    ("MaxConstantArgs", DeviceInfo::MaxConstantArgs),
+    ("LocalMemType", DeviceInfo::LocalMemType),
+    ("LocalMemSize", DeviceInfo::LocalMemSize),
+    ("ErrorCorrectionSupport", DeviceInfo::ErrorCorrectionSupport),
+    ("ProfilingTimerResolution", DeviceInfo::ProfilingTimerResolution),
+    ("EndianLittle", DeviceInfo::EndianLittle),
+    ("Available", DeviceInfo::Available),
+    ("CompilerAvailable", DeviceInfo::CompilerAvailable),
+    ("ExecutionCapabilities", DeviceInfo::ExecutionCapabilities),
+    ("QueueProperties", DeviceInfo::QueueProperties),
+    ("Name", DeviceInfo::Name),
+    ("Vendor", DeviceInfo::Vendor),
+    ("DriverVersion", DeviceInfo::DriverVersion),
+    ("Profile", DeviceInfo::Profile),
+    ("Version", DeviceInfo::Version),
+    ("Extensions", DeviceInfo::Extensions),
+    ("Platform", DeviceInfo::Platform),
+    ("DoubleFpConfig", DeviceInfo::DoubleFpConfig),
+    ("HalfFpConfig", DeviceInfo::HalfFpConfig),
+    ("PreferredVectorWidthHalf", DeviceInfo::PreferredVectorWidthHalf),
+    ("HostUnifiedMemory", DeviceInfo::HostUnifiedMemory),
+    ("NativeVectorWidthChar", DeviceInfo::NativeVectorWidthChar),
+    ("NativeVectorWidthShort", DeviceInfo::NativeVectorWidthShort),
+    ("NativeVectorWidthInt", DeviceInfo::NativeVectorWidthInt),
+    ("NativeVectorWidthLong", DeviceInfo::NativeVectorWidthLong),
+    ("NativeVectorWidthFloat", DeviceInfo::NativeVectorWidthFloat),
+    ("NativeVectorWidthDouble", DeviceInfo::NativeVectorWidthDouble),
+    ("NativeVectorWidthHalf", DeviceInfo::NativeVectorWidthHalf),
+    ("OpenclCVersion", DeviceInfo::OpenclCVersion),
+    ("LinkerAvailable", DeviceInfo::LinkerAvailable),
+    ("BuiltInKernels", DeviceInfo::BuiltInKernels),
+    ("ImageMaxBufferSize", DeviceInfo::ImageMaxBufferSize),
+    ("ImageMaxArraySize", DeviceInfo::ImageMaxArraySize),
+    ("ParentDevice", DeviceInfo::ParentDevice),
+    ("PartitionMaxSubDevices", DeviceInfo::PartitionMaxSubDevices),
+    ("PartitionProperties", DeviceInfo::PartitionProperties),
+    ("PartitionAffinityDomain", DeviceInfo::PartitionAffinityDomain),
+    ("PartitionType", DeviceInfo::PartitionType),
+    ("ReferenceCount", DeviceInfo::ReferenceCount),
+    ("PreferredInteropUserSync", DeviceInfo::PreferredInteropUserSync),
+    ("PrintfBufferSize", DeviceInfo::PrintfBufferSize),
+    ("ImagePitchAlignment", DeviceInfo::ImagePitchAlignment),
+    ("ImageBaseAddressAlignment", DeviceInfo::ImageBaseAddressAlignment),
+];
+
 
 // Perhaps add something like this to the `DeviceSpecifier`.
 //
@@ -227,6 +322,46 @@ impl From<DeviceType> for DeviceSpecifier {
 }
 
 
+/// The PCI bus location of a device (see `Device::pci_bus_info`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PciBusInfo {
+    pub domain: u32,
+    pub bus: u32,
+    pub device: u32,
+    pub function: u32,
+}
+
+
+/// The maximum image dimensions supported by a device (see
+/// `Device::image_limits`), in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageLimits {
+    pub image2d_max_width: usize,
+    pub image2d_max_height: usize,
+    pub image3d_max_width: usize,
+    pub image3d_max_height: usize,
+    pub image3d_max_depth: usize,
+    pub image_max_buffer_size: usize,
+    pub image_max_array_size: usize,
+}
+
+
+/// The global memory usage of a device (see `Device::memory_usage`), in
+/// bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub total: u64,
+    pub free: u64,
+}
+
+impl MemoryUsage {
+    /// Returns the number of bytes in use.
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.free)
+    }
+}
+
+
 /// An individual device identifier (an OpenCL device_id).
 ///
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -328,6 +463,58 @@ impl Device {
         Self::list(platform, None)
     }
 
+    /// Returns a list of all devices avaliable for a given `platform`,
+    /// enumerating them only upon the first call for that platform.
+    ///
+    /// See [`Platform::list_cached`](struct.Platform.html#method.list_cached)
+    /// for caveats. The cache is cleared by `Platform::invalidate_cache`.
+    ///
+    pub fn list_all_cached<P: Borrow<Platform>>(platform: P) -> OclResult<Vec<Device>> {
+        let platform = platform.borrow();
+        let mut cache = try!(enumeration_cache().lock()
+            .map_err(|_| OclError::from("ocl::Device::list_all_cached: Enumeration cache \
+                lock poisoned.")));
+
+        let key = platform.as_ptr() as usize;
+
+        if let Some(devices) = cache.devices.get(&key) {
+            return Ok(devices.clone());
+        }
+
+        let devices = try!(Self::list_all(platform));
+        cache.devices.insert(key, devices.clone());
+        cache.enumerations += 1;
+        Ok(devices)
+    }
+
+    /// Returns a list of all devices on all platforms, sorted in an order
+    /// which is stable across runs, for reproducible (index-based) device
+    /// assignment.
+    ///
+    /// Devices are sorted by platform name, then by PCI bus location
+    /// (`cl_khr_pci_bus_info`), then by UUID (`cl_khr_device_uuid`), then by
+    /// device name. Devices not distinguished by any available key (such as
+    /// identical devices lacking both extensions) retain their enumeration
+    /// order.
+    ///
+    pub fn list_all_sorted() -> OclResult<Vec<Device>> {
+        let mut keyed = Vec::new();
+
+        for platform in Platform::list() {
+            let platform_name = platform.name();
+
+            for device in try!(Self::list_all(&platform)) {
+                let key = (platform_name.clone(), device.pci_bus_info().ok(),
+                    device.uuid().ok(), device.name());
+                keyed.push((key, device));
+            }
+        }
+
+        // A stable sort preserves enumeration order for equal keys:
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(keyed.into_iter().map(|(_, device)| device).collect())
+    }
+
     /// Returns a list of devices filtered by type then selected using a
     /// list of indices.
     ///
@@ -372,12 +559,14 @@ impl Device {
 
     /// Returns the device name.
     pub fn name(&self) -> String {
-        core::get_device_info(&self.0, DeviceInfo::Name).into()
+        let info: String = core::get_device_info(&self.0, DeviceInfo::Name).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns the device vendor as a string.
     pub fn vendor(&self) -> String {
-        core::get_device_info(&self.0, DeviceInfo::Vendor).into()
+        let info: String = core::get_device_info(&self.0, DeviceInfo::Vendor).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns the maximum workgroup size or an error.
@@ -398,6 +587,24 @@ impl Device {
         }
     }
 
+    /// Returns the size of the device address space in bits
+    /// (`CL_DEVICE_ADDRESS_BITS`).
+    pub fn address_bits(&self) -> OclResult<u32> {
+        match self.info(DeviceInfo::AddressBits) {
+            DeviceInfoResult::AddressBits(r) => Ok(r),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => panic!("ocl::Device::address_bits: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns an error if a memory object of `size_bytes` bytes cannot be
+    /// addressed by this device or by the host (see
+    /// `ocl::verify_address_bits`).
+    pub fn verify_address_bits(&self, size_bytes: u64) -> OclResult<()> {
+        standard::verify_address_bits(try!(self.address_bits()), size_bytes)
+            .map_err(|err| OclError::from(format!("Device '{}': {}", self.name(), err)))
+    }
+
     /// Returns whether or not the device is available for use.
     pub fn is_available(&self) -> OclResult<bool> {
         match self.info(DeviceInfo::Available) {
@@ -407,6 +614,316 @@ impl Device {
         }
     }
 
+    /// Returns true if the device and the host share a unified memory
+    /// subsystem (`CL_DEVICE_HOST_UNIFIED_MEMORY`), as is typical of
+    /// integrated GPUs.
+    pub fn is_unified_memory(&self) -> OclResult<bool> {
+        match self.info(DeviceInfo::HostUnifiedMemory) {
+            DeviceInfoResult::HostUnifiedMemory(r) => Ok(r),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => panic!("Device::is_unified_memory: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the names of the built-in kernels supported by the device.
+    ///
+    /// Returns an empty list if the device has no built-in kernels. See
+    /// `Program::from_built_in_kernels`.
+    pub fn built_in_kernels(&self) -> OclResult<Vec<String>> {
+        match self.info(DeviceInfo::BuiltInKernels) {
+            DeviceInfoResult::BuiltInKernels(names) => Ok(names.split(';')
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_owned())
+                .collect()),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => panic!("ocl::Device::built_in_kernels: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the maximum number of subgroups in a work group for any
+    /// kernel executed on this device.
+    ///
+    /// Requires an OpenCL 2.1 (or later) device. Returns an error otherwise.
+    pub fn max_num_subgroups(&self) -> OclResult<u32> {
+        let mut result: ffi::cl_uint = 0;
+
+        let errcode = unsafe {
+            ffi::clGetDeviceInfo(self.0.as_ptr(), CL_DEVICE_MAX_NUM_SUB_GROUPS,
+                mem::size_of::<ffi::cl_uint>(), &mut result as *mut ffi::cl_uint as *mut c_void,
+                ptr::null_mut())
+        };
+
+        if errcode == 0 {
+            Ok(result)
+        } else {
+            OclError::err_string(format!("Device::max_num_subgroups: Unable to query \
+                'CL_DEVICE_MAX_NUM_SUB_GROUPS' (errcode: {}). The device may not support \
+                OpenCL 2.1.", errcode))
+        }
+    }
+
+    /// Returns true if the device supports subgroups, either as a core
+    /// OpenCL 2.1 feature or through the `cl_khr_subgroups` extension.
+    pub fn supports_subgroups(&self) -> OclResult<bool> {
+        if try!(self.supports_extension("cl_khr_subgroups")) {
+            return Ok(true);
+        }
+
+        // Fails on devices prior to OpenCL 2.1:
+        Ok(self.max_num_subgroups().map(|n| n > 0).unwrap_or(false))
+    }
+
+    /// Returns every queryable device attribute, keyed by name (the name of
+    /// the corresponding `DeviceInfo` variant, i.e. "MaxComputeUnits"), with
+    /// each value formatted as a string.
+    ///
+    /// Attributes which cannot be queried (such as those unsupported by the
+    /// device's OpenCL version) are omitted.
+    ///
+    /// ### Errors
+    ///
+    /// Returns the first error encountered if no attribute can be queried
+    /// at all (such as for an invalid device).
+    ///
+    pub fn info_map(&self) -> OclResult<BTreeMap<String, String>> {
+        let mut map = BTreeMap::new();
+        let mut first_err = None;
+
+        for &(name, info_kind) in DEVICE_INFO_KINDS {
+            match self.info(info_kind) {
+                DeviceInfoResult::Error(err) => if first_err.is_none() { first_err = Some(*err) },
+                res => {
+                    map.insert(name.to_owned(), standard::clean_info_string(res.to_string()));
+                },
+            }
+        }
+
+        match first_err {
+            Some(err) if map.is_empty() => Err(err),
+            _ => Ok(map),
+        }
+    }
+
+    /// Returns a text table of key device limits (compute units, clock
+    /// frequency, memory sizes, work sizes, and extensions), with one row
+    /// per property and values aligned, intended for diagnostics.
+    ///
+    /// Each extension is listed on its own row.
+    ///
+    /// ### Errors
+    ///
+    /// Returns the first error encountered while querying any property.
+    ///
+    pub fn capability_table(&self) -> OclResult<String> {
+        let info_string = |info_kind: DeviceInfo| -> OclResult<String> {
+            match self.info(info_kind) {
+                DeviceInfoResult::Error(err) => Err(*err),
+                res => Ok(standard::clean_info_string(res.to_string())),
+            }
+        };
+
+        let mut rows = vec![
+            ("Name", self.name()),
+            ("Vendor", self.vendor()),
+            ("Type", try!(info_string(DeviceInfo::Type))),
+            ("Version", try!(info_string(DeviceInfo::Version))),
+            ("Max Compute Units", try!(info_string(DeviceInfo::MaxComputeUnits))),
+            ("Max Clock Frequency (MHz)", try!(info_string(DeviceInfo::MaxClockFrequency))),
+            ("Global Memory Size (bytes)", try!(info_string(DeviceInfo::GlobalMemSize))),
+            ("Local Memory Size (bytes)", try!(info_string(DeviceInfo::LocalMemSize))),
+            ("Max Allocation Size (bytes)", try!(info_string(DeviceInfo::MaxMemAllocSize))),
+            ("Max Constant Buffer Size (bytes)",
+                try!(info_string(DeviceInfo::MaxConstantBufferSize))),
+            ("Max Work Group Size", try!(self.max_wg_size()).to_string()),
+            ("Max Work Item Dimensions", try!(info_string(DeviceInfo::MaxWorkItemDimensions))),
+            ("Max Work Item Sizes", try!(info_string(DeviceInfo::MaxWorkItemSizes))),
+        ];
+
+        let extensions = try!(info_string(DeviceInfo::Extensions));
+        let mut extensions = extensions.split_whitespace();
+        rows.push(("Extensions", extensions.next().unwrap_or("").to_owned()));
+        rows.extend(extensions.map(|ext| ("", ext.to_owned())));
+
+        let label_width = rows.iter()
+            .map(|&(label, _)| label.len())
+            .chain(Some("Property".len()))
+            .max().unwrap_or(0);
+        let value_width = rows.iter()
+            .map(|&(_, ref value)| value.len())
+            .chain(Some("Value".len()))
+            .max().unwrap_or(0);
+
+        let mut table = format!("{:<lw$} | {}\n", "Property", "Value", lw = label_width);
+        table.push_str(&format!("{}-+-{}\n", "-".repeat(label_width), "-".repeat(value_width)));
+
+        for (label, value) in rows {
+            table.push_str(&format!("{:<lw$} | {}\n", label, value, lw = label_width));
+        }
+
+        Ok(table)
+    }
+
+    /// Returns the value of a fixed-size device info parameter not covered by
+    /// `DeviceInfo` (such as those defined by extensions) or `None` if the
+    /// query fails.
+    fn raw_info<V: Copy + Default>(&self, param_name: ffi::cl_uint) -> Option<V> {
+        let mut result = V::default();
+        let mut size: usize = 0;
+
+        let errcode = unsafe {
+            ffi::clGetDeviceInfo(self.0.as_ptr(), param_name, mem::size_of::<V>(),
+                &mut result as *mut V as *mut c_void, &mut size)
+        };
+
+        if errcode == 0 && size == mem::size_of::<V>() { Some(result) } else { None }
+    }
+
+    /// Returns the maximum image dimensions supported by this device.
+    ///
+    /// Every limit is zero for devices without image support.
+    pub fn image_limits(&self) -> OclResult<ImageLimits> {
+        let limit = |info_kind: DeviceInfo| -> OclResult<usize> {
+            match self.info(info_kind) {
+                DeviceInfoResult::Image2dMaxWidth(s) | DeviceInfoResult::Image2dMaxHeight(s) |
+                    DeviceInfoResult::Image3dMaxWidth(s) | DeviceInfoResult::Image3dMaxHeight(s) |
+                    DeviceInfoResult::Image3dMaxDepth(s) |
+                    DeviceInfoResult::ImageMaxBufferSize(s) |
+                    DeviceInfoResult::ImageMaxArraySize(s) => Ok(s),
+                DeviceInfoResult::Error(err) => Err(*err),
+                _ => panic!("ocl::Device::image_limits: Unexpected 'DeviceInfoResult' variant."),
+            }
+        };
+
+        Ok(ImageLimits {
+            image2d_max_width: try!(limit(DeviceInfo::Image2dMaxWidth)),
+            image2d_max_height: try!(limit(DeviceInfo::Image2dMaxHeight)),
+            image3d_max_width: try!(limit(DeviceInfo::Image3dMaxWidth)),
+            image3d_max_height: try!(limit(DeviceInfo::Image3dMaxHeight)),
+            image3d_max_depth: try!(limit(DeviceInfo::Image3dMaxDepth)),
+            image_max_buffer_size: try!(limit(DeviceInfo::ImageMaxBufferSize)),
+            image_max_array_size: try!(limit(DeviceInfo::ImageMaxArraySize)),
+        })
+    }
+
+    /// Returns true if the device supports the extension named `extension`
+    /// (i.e. `"cl_khr_fp64"`).
+    pub fn supports_extension(&self, extension: &str) -> OclResult<bool> {
+        match self.info(DeviceInfo::Extensions) {
+            DeviceInfoResult::Extensions(exts) => Ok(standard::clean_info_string(exts)
+                .split_whitespace()
+                .any(|ext| ext == extension)),
+            DeviceInfoResult::Error(err) => Err(*err),
+            _ => panic!("ocl::Device::supports_extension: Unexpected 'DeviceInfoResult' variant."),
+        }
+    }
+
+    /// Returns the universally unique identifier of the device
+    /// (`CL_DEVICE_UUID_KHR`), which identifies a physical device across
+    /// runs and processes.
+    ///
+    /// ### Errors
+    ///
+    /// The device must support the `cl_khr_device_uuid` extension.
+    ///
+    pub fn uuid(&self) -> OclResult<[u8; 16]> {
+        if !try!(self.supports_extension("cl_khr_device_uuid")) {
+            return OclError::err_string(format!("ocl::Device::uuid: The device '{}' does not \
+                support the 'cl_khr_device_uuid' extension.", self.name()));
+        }
+
+        self.raw_info::<[u8; 16]>(CL_DEVICE_UUID_KHR).ok_or_else(|| OclError::from(format!(
+            "ocl::Device::uuid: Unable to query 'CL_DEVICE_UUID_KHR' for the device '{}'.",
+            self.name())))
+    }
+
+    /// Returns the PCI bus location of the device, which identifies a
+    /// physical device across runs and processes.
+    ///
+    /// Uses `cl_khr_pci_bus_info` where supported, falling back to the
+    /// `cl_nv_device_attribute_query` or `cl_amd_device_attribute_query`
+    /// extensions.
+    ///
+    /// ### Errors
+    ///
+    /// The device must support one of the above extensions.
+    ///
+    pub fn pci_bus_info(&self) -> OclResult<PciBusInfo> {
+        let unavailable = || OclError::from(format!("ocl::Device::pci_bus_info: Unable to query \
+            the PCI bus location of the device '{}'.", self.name()));
+
+        if try!(self.supports_extension("cl_khr_pci_bus_info")) {
+            let info = try!(self.raw_info::<[u32; 4]>(CL_DEVICE_PCI_BUS_INFO_KHR)
+                .ok_or_else(&unavailable));
+            return Ok(PciBusInfo { domain: info[0], bus: info[1], device: info[2],
+                function: info[3] });
+        }
+
+        if try!(self.supports_extension("cl_nv_device_attribute_query")) {
+            let bus = try!(self.raw_info::<u32>(CL_DEVICE_PCI_BUS_ID_NV).ok_or_else(&unavailable));
+            let slot = try!(self.raw_info::<u32>(CL_DEVICE_PCI_SLOT_ID_NV)
+                .ok_or_else(&unavailable));
+            // The domain query is unavailable on older drivers:
+            let domain = self.raw_info::<u32>(CL_DEVICE_PCI_DOMAIN_ID_NV).unwrap_or(0);
+            return Ok(PciBusInfo { domain: domain, bus: bus, device: slot >> 3,
+                function: slot & 0x7 });
+        }
+
+        if try!(self.supports_extension("cl_amd_device_attribute_query")) {
+            // `cl_device_topology_amd`: a `cl_uint` type followed by 17
+            // unused bytes then the bus, device, and function numbers.
+            let topology = try!(self.raw_info::<[u8; 24]>(CL_DEVICE_TOPOLOGY_AMD)
+                .ok_or_else(&unavailable));
+            let topology_type = topology[0] as u32 | (topology[1] as u32) << 8 |
+                (topology[2] as u32) << 16 | (topology[3] as u32) << 24;
+
+            if topology_type == CL_DEVICE_TOPOLOGY_TYPE_PCIE_AMD {
+                return Ok(PciBusInfo { domain: 0, bus: topology[21] as u32,
+                    device: topology[22] as u32, function: topology[23] as u32 });
+            }
+        }
+
+        OclError::err_string(format!("ocl::Device::pci_bus_info: The device '{}' does not support \
+            any of the 'cl_khr_pci_bus_info', 'cl_nv_device_attribute_query', or \
+            'cl_amd_device_attribute_query' extensions.", self.name()))
+    }
+
+    /// Returns the current free and total global memory of the device.
+    ///
+    /// Uses the `cl_amd_device_attribute_query` extension. No equivalent
+    /// query is available through OpenCL on other vendors' devices
+    /// (including NVIDIA's).
+    ///
+    /// The free memory reported is that available to all processes and
+    /// changes as memory is allocated. It is reported by the driver in
+    /// kilobytes.
+    ///
+    /// ### Errors
+    ///
+    /// The device must support the above extension.
+    ///
+    pub fn memory_usage(&self) -> OclResult<MemoryUsage> {
+        if !try!(self.supports_extension("cl_amd_device_attribute_query")) {
+            return OclError::err_string(format!("ocl::Device::memory_usage: The device '{}' does \
+                not support querying free memory ('cl_amd_device_attribute_query').", self.name()));
+        }
+
+        let total = match self.info(DeviceInfo::GlobalMemSize) {
+            DeviceInfoResult::GlobalMemSize(s) => s,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => panic!("ocl::Device::memory_usage: Unexpected 'DeviceInfoResult' variant."),
+        };
+
+        // Total free memory followed by the largest free block, in KB:
+        let free_kb = match self.raw_info::<[usize; 2]>(CL_DEVICE_GLOBAL_FREE_MEMORY_AMD) {
+            Some(free) => free[0] as u64,
+            None => return OclError::err_string(format!("ocl::Device::memory_usage: Unable to \
+                query the free memory of the device '{}'.", self.name())),
+        };
+
+        Ok(MemoryUsage { total: total, free: ::std::cmp::min(free_kb * 1024, total) })
+    }
+
     /// Returns info about the device.
     pub fn info(&self, info_kind: DeviceInfo) -> DeviceInfoResult {
         core::get_device_info(&self.0, info_kind)
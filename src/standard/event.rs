@@ -15,7 +15,12 @@
 
 extern crate nodrop;
 
-use std::{mem, ptr, fmt};
+use std::{mem, ptr, fmt, cmp};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use libc::c_void;
+use num::FromPrimitive;
 use std::borrow::Borrow;
 use std::ops::{Deref, DerefMut};
 use std::cell::Ref;
@@ -25,15 +30,92 @@ use futures::task;
 use ffi::cl_event;
 use core::{self, Event as EventCore, EventInfo, EventInfoResult, ProfilingInfo,
     ProfilingInfoResult, ClNullEventPtr, ClWaitListPtr, ClEventPtrRef,
-    CommandQueue as CommandQueueCore, ClContextPtr};
+    CommandQueue as CommandQueueCore, ClContextPtr, Status};
 use core::error::{Error as OclError, Result as OclResult};
 use standard::{Queue, ClWaitListPtrEnum};
 #[cfg(not(feature = "async_block"))]
 use standard::{_unpark_task, box_raw_void};
+#[cfg(all(feature = "std_future", not(feature = "async_block")))]
+use standard::{_wake_task, pending_wakers, WakerSlot};
 use self::nodrop::NoDrop;
 
 const PRINT_DEBUG: bool = false;
 
+
+/// The execution status of the command associated with an event (see
+/// `Event::status`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventStatus {
+    Queued,
+    Submitted,
+    Running,
+    Complete,
+    /// The command was terminated abnormally. Contains the (negative) error
+    /// code.
+    Error(i32),
+}
+
+impl EventStatus {
+    /// Returns the status corresponding to a raw
+    /// `CL_EVENT_COMMAND_EXECUTION_STATUS` value.
+    ///
+    /// Any negative (or otherwise unknown) value is mapped to
+    /// `EventStatus::Error`.
+    pub fn from_raw(status: i32) -> EventStatus {
+        match status {
+            0 => EventStatus::Complete,
+            1 => EventStatus::Running,
+            2 => EventStatus::Submitted,
+            3 => EventStatus::Queued,
+            errcode => EventStatus::Error(errcode),
+        }
+    }
+
+    /// Returns true if the command has completed or was terminated
+    /// abnormally.
+    pub fn is_finished(&self) -> bool {
+        match *self {
+            EventStatus::Complete | EventStatus::Error(_) => true,
+            _ => false,
+        }
+    }
+}
+
+
+/// How the polling-based waits (`Event::wait_for_polling` and
+/// `EventList::wait_for_any`) pass the time between status checks.
+///
+/// Trades latency for CPU usage: `Spin` has the lowest latency but occupies
+/// an entire core while waiting, `Yield` gives up the remainder of each time
+/// slice, and `Sleep` uses almost no CPU but may oversleep completion by up
+/// to its duration (plus scheduler latency).
+///
+/// Defaults to a short (100 microsecond) sleep.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitStrategy {
+    Spin,
+    Yield,
+    Sleep(Duration),
+}
+
+impl WaitStrategy {
+    /// Pauses between two status checks.
+    fn pause(&self) {
+        match *self {
+            WaitStrategy::Spin => (),
+            WaitStrategy::Yield => ::std::thread::yield_now(),
+            WaitStrategy::Sleep(duration) => ::std::thread::sleep(duration),
+        }
+    }
+}
+
+impl Default for WaitStrategy {
+    fn default() -> WaitStrategy {
+        WaitStrategy::Sleep(Duration::new(0, 100_000))
+    }
+}
+
 /// An event representing a command or user created event.
 ///
 #[derive(Clone, Debug)]
@@ -48,12 +130,50 @@ impl Event {
         Event(EventCore::null())
     }
 
-    /// Creates a new, empty event which must be filled by a newly initiated
-    /// command, associating the event with it.
+    /// Creates a new user event (`clCreateUserEvent`) associated with
+    /// `context`.
+    ///
+    /// Commands waiting on a user event will not run until its status is set
+    /// from the host using `::set_status` or `::set_complete`.
     pub fn user<C: ClContextPtr>(context: C) -> OclResult<Event> {
         EventCore::user(context).map(Event)
     }
 
+    /// Sets the execution status of this user event
+    /// (`clSetUserEventStatus`).
+    ///
+    /// `status` must be either `CL_COMPLETE` (`0`) or a negative error code.
+    /// A negative value terminates all commands waiting on this event. The
+    /// status of a user event may only be set once.
+    ///
+    /// ### Errors
+    ///
+    /// This event must have been created with `::user`.
+    ///
+    pub fn set_status(&self, status: i32) -> OclResult<()> {
+        if self.is_empty() {
+            return OclError::err_string("ocl::Event::set_status: This event is empty.");
+        }
+
+        let errcode = unsafe { ::ffi::clSetUserEventStatus(*self.0.as_ptr_ref(), status) };
+
+        if errcode == 0 {
+            Ok(())
+        } else {
+            OclError::err_string(format!("ocl::Event::set_status: 'clSetUserEventStatus' failed \
+                with status: {:?} ({}). Only user events which have not already been set may \
+                have their status set.", Status::from_i32(errcode), errcode))
+        }
+    }
+
+    /// Marks this user event as complete, releasing any commands waiting on
+    /// it.
+    ///
+    /// Equivalent to `::set_status(CL_COMPLETE)`.
+    pub fn set_complete(&self) -> OclResult<()> {
+        self.set_status(::ffi::CL_COMPLETE as i32)
+    }
+
     /// Returns true if this event is 'empty' and has not yet been associated
     /// with a command.
     ///
@@ -78,6 +198,164 @@ impl Event {
         unsafe { self.set_callback(_unpark_task, task_ptr) }
     }
 
+    /// Sets a callback function to trigger upon completion (or abnormal
+    /// termination) of this event which will wake the task associated with
+    /// `waker`.
+    ///
+    /// Only one callback is registered per event. If one is already pending,
+    /// `waker` replaces the waker it will wake (so that repeatedly polling a
+    /// pending event does not accumulate callbacks).
+    #[cfg(all(feature = "std_future", not(feature = "async_block")))]
+    pub fn set_wake_callback(&self, waker: &::std::task::Waker) -> OclResult<()> {
+        let key = *self.0.as_ptr_ref() as usize;
+
+        {
+            let mut wakers = pending_wakers().lock().unwrap();
+
+            if let Some(slot) = wakers.get(&key) {
+                *slot.lock().unwrap() = Some(waker.clone());
+                return Ok(());
+            }
+
+            let slot: WakerSlot = Arc::new(Mutex::new(Some(waker.clone())));
+            wakers.insert(key, slot);
+        }
+
+        // The registry must be unlocked: the callback runs immediately if the
+        // event has completed in the meanwhile.
+        let res = unsafe { self.set_callback(_wake_task, ptr::null_mut()) };
+
+        if res.is_err() {
+            pending_wakers().lock().unwrap().remove(&key);
+        }
+        res
+    }
+
+    /// Blocks until this event completes or until `timeout` elapses,
+    /// whichever comes first. Returns `true` if the event completed in time.
+    ///
+    /// Polls the event status using the default `WaitStrategy` (see
+    /// `::wait_for_polling` to choose another). Returns immediately if the
+    /// event is already complete.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if this event is empty or if its command terminated
+    /// abnormally.
+    ///
+    pub fn wait_for_timeout(&self, timeout: Duration) -> OclResult<bool> {
+        self.wait_for_polling(WaitStrategy::default(), Some(timeout))
+    }
+
+    /// Returns a user event which completes once `f`, called upon the
+    /// completion of this event, has enqueued its command(s) on `queue` and
+    /// the event it returns has completed.
+    ///
+    /// `f` is called from within an event callback (on a thread owned by
+    /// the OpenCL implementation) and must therefore not block: commands
+    /// must be enqueued with blocking disabled. `queue` is flushed after
+    /// `f` returns. Calls may be chained:
+    ///
+    /// ```rust,ignore
+    /// let done = write_event
+    ///     .then(queue.clone(), move |q| { /* enqueue kernel */ })?
+    ///     .then(queue.clone(), move |q| { /* enqueue read */ })?;
+    /// done.wait_for()?;
+    /// ```
+    ///
+    /// If this event terminates abnormally, `f` is not called and the
+    /// returned event is terminated with the same status. If `f` returns an
+    /// error, the returned event is terminated with `CL_INVALID_OPERATION`.
+    ///
+    /// [UNSTABLE]: This method is new and may be changed or removed.
+    ///
+    pub fn then<F>(&self, queue: Queue, f: F) -> OclResult<Event>
+            where F: FnOnce(&Queue) -> OclResult<Event> + Send + 'static
+    {
+        if self.is_empty() {
+            return OclError::err_string("ocl::Event::then: This event is empty.");
+        }
+
+        let done = try!(Event::user(&queue));
+        let chained = ChainedCmd { queue: queue, f: Some(f), done: done.clone() };
+        let chained_ptr = Box::into_raw(Box::new(chained)) as *mut c_void;
+        unsafe { try!(self.set_callback(_enqueue_chained::<F>, chained_ptr)); }
+        Ok(done)
+    }
+
+    /// Returns the execution status of the command associated with this
+    /// event without blocking.
+    ///
+    /// Suitable for polling (e.g. from a UI loop). A command which was
+    /// terminated abnormally is reported as `EventStatus::Error`.
+    ///
+    pub fn status(&self) -> OclResult<EventStatus> {
+        if self.is_empty() {
+            return OclError::err_string("ocl::Event::status: This event is empty.");
+        }
+
+        self.execution_status().map(EventStatus::from_raw)
+    }
+
+    /// Returns the raw command execution status of this event.
+    ///
+    /// Negative values indicate that the associated command was terminated
+    /// abnormally (the value being the error code).
+    fn execution_status(&self) -> OclResult<i32> {
+        let mut status: i32 = 0;
+
+        let errcode = unsafe {
+            ::ffi::clGetEventInfo(*self.0.as_ptr_ref(), ::ffi::CL_EVENT_COMMAND_EXECUTION_STATUS,
+                mem::size_of::<i32>(), &mut status as *mut i32 as *mut c_void,
+                ptr::null_mut())
+        };
+
+        if errcode == 0 {
+            Ok(status)
+        } else {
+            OclError::err_string(format!("ocl::Event::execution_status: Unable to retrieve \
+                event status (errcode: {}).", errcode))
+        }
+    }
+
+    /// Blocks until this event completes, polling its status and pausing
+    /// between checks according to `strategy`, or until `timeout` (if any)
+    /// elapses. Returns `true` if the event completed in time.
+    ///
+    /// Prefer `::wait_for`, which blocks without polling, when no timeout is
+    /// needed.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if this event is empty or if its command terminated
+    /// abnormally.
+    ///
+    pub fn wait_for_polling(&self, strategy: WaitStrategy, timeout: Option<Duration>)
+            -> OclResult<bool>
+    {
+        if self.is_empty() {
+            return OclError::err_string("ocl::Event::wait_for_polling: This event is empty.");
+        }
+
+        let deadline = timeout.map(|t| Instant::now() + t);
+
+        loop {
+            match try!(self.status()) {
+                EventStatus::Complete => return Ok(true),
+                EventStatus::Error(errcode) => return OclError::err_string(format!(
+                    "ocl::Event::wait_for_polling: The command associated with this event \
+                    terminated abnormally (status: {:?}).", Status::from_i32(errcode))),
+                _ => (),
+            }
+
+            if let Some(deadline) = deadline {
+                if Instant::now() >= deadline { return Ok(false); }
+            }
+
+            strategy.pause();
+        }
+    }
+
     /// Returns info about the event.
     pub fn info(&self, info_kind: EventInfo) -> EventInfoResult {
         core::get_event_info(&self.0, info_kind)
@@ -107,9 +385,15 @@ impl Event {
 
     /// Consumes the `Event`, returning the wrapped `cl_event` pointer.
     ///
-    /// To avoid a memory leak the pointer must be converted back to an `Event` using
+    /// Ownership of the reference held by this `Event` is transferred to the
+    /// caller: the reference count is not changed and `clReleaseEvent` will
+    /// not be called. The pointer may be passed to C code which releases it
+    /// (with `clReleaseEvent`) when done. Otherwise, to avoid a leak, it
+    /// must be converted back to an `Event` using
     /// [`Event::from_raw`][from_raw].
     ///
+    /// Returns a null pointer if this event is empty.
+    ///
     /// [from_raw]: struct.Event.html#method.from_raw
     ///
     #[inline]
@@ -117,10 +401,20 @@ impl Event {
         self.0.into_raw()
     }
 
-    /// Constructs an `Event` from a raw `cl_event` pointer.
+    /// Constructs an `Event` from a raw `cl_event` pointer, taking ownership
+    /// of one reference.
+    ///
+    /// The reference count is not changed on construction and
+    /// `clReleaseEvent` is called once when the returned `Event` (and all of
+    /// its clones, each of which holds its own reference) is dropped.
     ///
-    /// The raw pointer must have been previously returned by a call to a
-    /// [`Event::into_raw`][into_raw].
+    /// ### Safety
+    ///
+    /// `ptr` must be a valid event (or null, for an empty event) holding a
+    /// reference owned by the caller, such as a pointer previously returned
+    /// by [`Event::into_raw`][into_raw] or one created by C code which will
+    /// not release it. To wrap an event whose reference remains owned
+    /// elsewhere, call `clRetainEvent` on it first.
     ///
     /// [into_raw]: struct.Event.html#method.into_raw
     #[inline]
@@ -128,6 +422,19 @@ impl Event {
         EventCore::from_raw(ptr).into()
     }
 
+    /// Returns the wrapped `cl_event` pointer without affecting ownership.
+    ///
+    /// The reference count is not changed. The pointer is valid only for as
+    /// long as this `Event` (or another reference to the same event) exists
+    /// and must not be released by the recipient. C code which needs to keep
+    /// the event must call `clRetainEvent` on it.
+    ///
+    /// Returns a null pointer if this event is empty.
+    #[inline]
+    pub fn as_raw(&self) -> cl_event {
+        unsafe { *self.0.as_ptr_ref() }
+    }
+
     fn fmt_info(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Event")
             .field("CommandQueue", &self.info(EventInfo::CommandQueue))
@@ -144,6 +451,55 @@ impl Event {
     }
 }
 
+/// A command to be enqueued by `_enqueue_chained` (see `Event::then`).
+struct ChainedCmd<F> {
+    queue: Queue,
+    f: Option<F>,
+    done: Event,
+}
+
+/// Calls the closure of a `ChainedCmd` (boxed and passed as `user_data`)
+/// then arranges for its user event to complete along with the event the
+/// closure returns.
+extern "C" fn _enqueue_chained<F>(event_ptr: cl_event, event_status: i32, user_data: *mut c_void)
+        where F: FnOnce(&Queue) -> OclResult<Event>
+{
+    let _ = event_ptr;
+    if user_data.is_null() { return; }
+
+    let mut chained = unsafe { Box::from_raw(user_data as *mut ChainedCmd<F>) };
+
+    if event_status < 0 {
+        let _ = chained.done.set_status(event_status);
+        return;
+    }
+
+    let f = chained.f.take().expect("ocl::Event::then: Chained closure already called.");
+
+    let result = f(&chained.queue).and_then(|event| {
+        try!(chained.queue.flush());
+        let done_ptr = Box::into_raw(Box::new(chained.done.clone())) as *mut c_void;
+        unsafe { event.set_callback(_complete_user_event, done_ptr) }
+    });
+
+    if result.is_err() {
+        let _ = chained.done.set_status(::ffi::CL_INVALID_OPERATION);
+    }
+}
+
+/// Sets the status of a user event (boxed and passed as `user_data`) to that
+/// of the completed (or terminated) event.
+extern "C" fn _complete_user_event(event_ptr: cl_event, event_status: i32,
+        user_data: *mut c_void)
+{
+    let _ = event_ptr;
+
+    if !user_data.is_null() {
+        let done = unsafe { Box::from_raw(user_data as *mut Event) };
+        let _ = done.set_status(cmp::min(event_status, 0));
+    }
+}
+
 impl From<EventCore> for Event {
     #[inline]
     fn from(ev: EventCore) -> Event {
@@ -259,6 +615,46 @@ impl Future for Event {
 }
 
 
+/// Allows an event to be `.await`ed (or otherwise used as a
+/// `std::future::Future`) on any executor.
+///
+/// Resolves to `Ok(())` once the associated command is complete or to an
+/// error if the command was terminated abnormally.
+#[cfg(feature = "std_future")]
+impl ::std::future::Future for Event {
+    type Output = OclResult<()>;
+
+    #[cfg(not(feature = "async_block"))]
+    fn poll(self: ::std::pin::Pin<&mut Self>, cx: &mut ::std::task::Context)
+            -> ::std::task::Poll<Self::Output>
+    {
+        debug_assert!(self.0.is_valid());
+
+        match self.execution_status() {
+            // `CL_COMPLETE`:
+            Ok(0) => ::std::task::Poll::Ready(Ok(())),
+            Ok(status) if status < 0 => ::std::task::Poll::Ready(OclError::err_string(
+                format!("ocl::Event::poll: The command associated with this event was terminated \
+                abnormally (status: {}).", status))),
+            Ok(_) => {
+                if let Err(err) = self.set_wake_callback(cx.waker()) {
+                    return ::std::task::Poll::Ready(Err(err));
+                }
+                ::std::task::Poll::Pending
+            },
+            Err(err) => ::std::task::Poll::Ready(Err(err)),
+        }
+    }
+
+    #[cfg(feature = "async_block")]
+    fn poll(self: ::std::pin::Pin<&mut Self>, _cx: &mut ::std::task::Context)
+            -> ::std::task::Poll<Self::Output>
+    {
+        debug_assert!(self.0.is_valid());
+        ::std::task::Poll::Ready(self.wait_for())
+    }
+}
+
 
 /// Returns an empty, initialized (zeroed) event array.
 fn empty_event_array() -> NoDrop<[Event; 8]> {
@@ -700,7 +1096,9 @@ impl EventList {
 
     /// Clears all events from the list whether or not they have completed.
     ///
-    /// Forwards any errors related to releasing events.
+    /// Each event is released (its reference count decremented) and the
+    /// length is reset to zero. Any allocated capacity is retained so that
+    /// the list can be reused (such as within a loop) without reallocating.
     ///
     #[inline]
     pub fn clear(&mut self) {
@@ -710,6 +1108,15 @@ impl EventList {
         }
     }
 
+    /// Returns the number of events the list can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match self.inner {
+            Inner::Array(ref a) => a.array.len(),
+            Inner::Vec(ref v) => v.capacity(),
+        }
+    }
+
     /// Clears events which have completed.
     pub fn clear_completed(&mut self) -> OclResult<()> {
         match self.inner {
@@ -742,6 +1149,38 @@ impl EventList {
         }
     }
 
+    /// Blocks until any event in this list completes, polling the status of
+    /// each and pausing between passes according to `strategy`. Returns the
+    /// index of the first complete event found.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if the list is empty, if any event is empty, or if
+    /// any command terminates abnormally before another completes.
+    ///
+    pub fn wait_for_any(&self, strategy: WaitStrategy) -> OclResult<usize> {
+        let events = self.as_slice();
+
+        if events.is_empty() {
+            return Err("ocl::EventList::wait_for_any: List empty.".into());
+        }
+
+        loop {
+            for (idx, event) in events.iter().enumerate() {
+                match try!(event.status()) {
+                    EventStatus::Complete => return Ok(idx),
+                    EventStatus::Error(errcode) => return OclError::err_string(format!(
+                        "ocl::EventList::wait_for_any: The command associated with the event at \
+                        index {} terminated abnormally (status: {:?}).", idx,
+                        Status::from_i32(errcode))),
+                    _ => (),
+                }
+            }
+
+            strategy.pause();
+        }
+    }
+
     /// Enqueue a marker event representing the completion of each and every
     /// event in this list.
     ///
@@ -763,6 +1202,54 @@ impl EventList {
         }
     }
 
+    /// Returns the total and combined (union) execution time of the commands
+    /// associated with the events in this list.
+    ///
+    /// Each event must be complete and must have been created by a queue with
+    /// profiling enabled.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if the list is empty or if profiling information is
+    /// unavailable for any event.
+    ///
+    pub fn profiling_summary(&self) -> OclResult<ProfilingSummary> {
+        let events = self.as_slice();
+
+        if events.is_empty() {
+            return Err("EventList::profiling_summary: List empty.".into());
+        }
+
+        let mut summary = ProfilingSummary {
+            event_count: events.len(),
+            total_ns: 0,
+            earliest_start_ns: u64::max_value(),
+            latest_end_ns: 0,
+        };
+
+        for event in events {
+            let start = match event.profiling_info(ProfilingInfo::Start) {
+                ProfilingInfoResult::Start(time) => time,
+                ProfilingInfoResult::Error(err) => return Err(*err),
+                _ => panic!("EventList::profiling_summary: Unexpected 'ProfilingInfoResult' \
+                    variant."),
+            };
+
+            let end = match event.profiling_info(ProfilingInfo::End) {
+                ProfilingInfoResult::End(time) => time,
+                ProfilingInfoResult::Error(err) => return Err(*err),
+                _ => panic!("EventList::profiling_summary: Unexpected 'ProfilingInfoResult' \
+                    variant."),
+            };
+
+            summary.total_ns += end.saturating_sub(start);
+            summary.earliest_start_ns = cmp::min(summary.earliest_start_ns, start);
+            summary.latest_end_ns = cmp::max(summary.latest_end_ns, end);
+        }
+
+        Ok(summary)
+    }
+
     /// Returns a slice of the contained events.
     #[inline]
     pub fn as_slice(&self) -> &[Event] {
@@ -822,6 +1309,89 @@ impl EventList {
     }
 }
 
+/// Aggregate profiling times for a list of events.
+///
+/// Returned by `EventList::profiling_summary`. All times are in nanoseconds,
+/// as reported by the device.
+///
+/// A `span` shorter than the `total` indicates that commands overlapped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProfilingSummary {
+    pub event_count: usize,
+    pub total_ns: u64,
+    pub earliest_start_ns: u64,
+    pub latest_end_ns: u64,
+}
+
+impl ProfilingSummary {
+    /// Returns the sum of the start to end durations of every command.
+    pub fn total(&self) -> Duration {
+        Duration::new(self.total_ns / 1_000_000_000, (self.total_ns % 1_000_000_000) as u32)
+    }
+
+    /// Returns the span from the earliest start to the latest end of all
+    /// commands, in nanoseconds.
+    pub fn span_ns(&self) -> u64 {
+        self.latest_end_ns - self.earliest_start_ns
+    }
+
+    /// Returns the span from the earliest start to the latest end of all
+    /// commands.
+    pub fn span(&self) -> Duration {
+        let span_ns = self.span_ns();
+        Duration::new(span_ns / 1_000_000_000, (span_ns % 1_000_000_000) as u32)
+    }
+}
+
+
+/// The profiling timestamps of a single command, in nanoseconds.
+///
+/// Returned by `Kernel::enq_timed` and `ProfilingNanos::from_event`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProfilingNanos {
+    pub queued: u64,
+    pub submit: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ProfilingNanos {
+    /// Returns the profiling timestamps of `event`.
+    ///
+    /// ### Errors
+    ///
+    /// The command associated with `event` must have been enqueued on a
+    /// profiling-enabled queue and must be complete.
+    ///
+    pub fn from_event(event: &Event) -> OclResult<ProfilingNanos> {
+        let mut times = [0u64; 4];
+        let kinds = [ProfilingInfo::Queued, ProfilingInfo::Submit, ProfilingInfo::Start,
+            ProfilingInfo::End];
+
+        for (time, kind) in times.iter_mut().zip(kinds.iter()) {
+            *time = match event.profiling_info(*kind) {
+                ProfilingInfoResult::Queued(t) | ProfilingInfoResult::Submit(t) |
+                    ProfilingInfoResult::Start(t) | ProfilingInfoResult::End(t) => t,
+                ProfilingInfoResult::Error(err) => return Err(*err),
+            };
+        }
+
+        Ok(ProfilingNanos { queued: times[0], submit: times[1], start: times[2], end: times[3] })
+    }
+
+    /// Returns the execution time (start to end), in nanoseconds.
+    pub fn duration_ns(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Returns the execution time (start to end).
+    pub fn duration(&self) -> Duration {
+        let duration_ns = self.duration_ns();
+        Duration::new(duration_ns / 1_000_000_000, (duration_ns % 1_000_000_000) as u32)
+    }
+}
+
+
 impl<'a, E> From<E> for EventList where E: Into<Event> {
     #[inline]
     fn from(event: E) -> EventList {
@@ -1178,6 +1748,157 @@ unsafe impl<'a> ClWaitListPtr for &'a EventList {
 }
 
 
+/// The completion state shared between a `JoinEvents` future and the
+/// callbacks set on each of its events.
+struct JoinState {
+    remaining: AtomicUsize,
+    error_status: Mutex<Option<i32>>,
+    #[cfg(not(feature = "async_block"))]
+    task: Mutex<Option<task::Task>>,
+}
+
+/// Decrements the count of remaining events of a `JoinState` (an `Arc`,
+/// boxed and passed as `user_data`), recording the status of the first event
+/// to terminate abnormally, then unparks the joining task once all events
+/// have completed or upon an error.
+#[cfg(not(feature = "async_block"))]
+extern "C" fn _join_countdown(event_ptr: cl_event, event_status: i32, user_data: *mut c_void) {
+    let _ = event_ptr;
+    if user_data.is_null() { return; }
+
+    let state = unsafe { Box::from_raw(user_data as *mut Arc<JoinState>) };
+
+    if event_status < 0 {
+        if let Ok(mut error_status) = state.error_status.lock() {
+            if error_status.is_none() { *error_status = Some(event_status); }
+        }
+    }
+
+    let remaining = state.remaining.fetch_sub(1, Ordering::SeqCst) - 1;
+
+    if remaining == 0 || event_status < 0 {
+        if let Ok(task) = state.task.lock() {
+            if let Some(ref task) = *task { task.unpark(); }
+        }
+    }
+}
+
+
+/// A future which resolves once every one of a list of events has completed.
+///
+/// Returned by `join_events`. The queues of the commands associated with the
+/// events must have been flushed (or finished), otherwise the future may
+/// never resolve (see `join_events`).
+///
+#[must_use = "futures do nothing unless polled"]
+pub struct JoinEvents {
+    events: Vec<Event>,
+    state: Arc<JoinState>,
+    callbacks_registered: usize,
+}
+
+impl JoinEvents {
+    /// Returns an error if any event has terminated abnormally.
+    fn check_error(&self) -> OclResult<()> {
+        match *self.state.error_status.lock().unwrap() {
+            Some(status) => OclError::err_string(format!("ocl::JoinEvents::poll: The command \
+                associated with an event terminated abnormally (status: {:?}).",
+                Status::from_i32(status))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for JoinEvents {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinEvents")
+            .field("events", &self.events)
+            .field("remaining", &self.state.remaining.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+impl Future for JoinEvents {
+    type Item = ();
+    type Error = OclError;
+
+    #[cfg(not(feature = "async_block"))]
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        try!(self.check_error());
+        if self.state.remaining.load(Ordering::SeqCst) == 0 { return Ok(Async::Ready(())); }
+
+        *self.state.task.lock().unwrap() = Some(task::park());
+
+        // Registration resumes with the first event lacking a callback if a
+        // previous poll failed partway through:
+        while self.callbacks_registered < self.events.len() {
+            let state_ptr = Box::into_raw(Box::new(self.state.clone()));
+            let res = unsafe {
+                self.events[self.callbacks_registered].set_callback(_join_countdown,
+                    state_ptr as *mut c_void)
+            };
+
+            if let Err(err) = res {
+                // The callback will never run to free its state:
+                unsafe { drop(Box::from_raw(state_ptr)); }
+                return Err(err);
+            }
+
+            self.callbacks_registered += 1;
+        }
+
+        // Callbacks may have run before the task was stored:
+        try!(self.check_error());
+        if self.state.remaining.load(Ordering::SeqCst) == 0 {
+            Ok(Async::Ready(()))
+        } else {
+            Ok(Async::NotReady)
+        }
+    }
+
+    #[cfg(feature = "async_block")]
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        for event in self.events.iter() {
+            try!(event.wait_for());
+        }
+        self.state.remaining.store(0, Ordering::SeqCst);
+        Ok(Async::Ready(()))
+    }
+}
+
+/// Returns a future which resolves once all of `events` have completed.
+///
+/// A callback is set on each event when the future is first polled and a
+/// shared count of remaining events is decremented as each completes. If
+/// registering a callback fails, the error is returned and registration
+/// resumes with that event when next polled. An
+/// empty list resolves immediately. If any command terminates abnormally,
+/// the future resolves to an error (reporting the first such status) without
+/// waiting for the remaining events.
+///
+/// Callbacks only run once their commands have been submitted to a device.
+/// Each queue with commands associated with `events` must therefore be
+/// flushed (`Queue::flush`) or finished before the future is polled,
+/// otherwise the future may never resolve.
+///
+/// [UNSTABLE]: This function is new and may be changed or removed.
+///
+pub fn join_events(events: Vec<Event>) -> JoinEvents {
+    let state = JoinState {
+        remaining: AtomicUsize::new(events.len()),
+        error_status: Mutex::new(None),
+        #[cfg(not(feature = "async_block"))]
+        task: Mutex::new(None),
+    };
+
+    JoinEvents {
+        events: events,
+        state: Arc::new(state),
+        callbacks_registered: 0,
+    }
+}
+
+
 
 /// A stack allocated array of `cl_event` pointers with a maximum length of 8.
 ///
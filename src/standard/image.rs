@@ -7,6 +7,7 @@
 
 use std;
 use std::mem;
+use std::cmp;
 // use std::borrow::Borrow;
 use std::ops::{Deref, DerefMut};
 use std::marker::PhantomData;
@@ -16,9 +17,11 @@ use core::error::{Error as OclError, Result as OclResult};
 use core::{self, OclPrm, Mem as MemCore, MemFlags, MemObjectType, ImageFormat, ImageDescriptor,
     ImageInfo, ImageInfoResult, MemInfo, MemInfoResult, ImageChannelOrder, ImageChannelDataType,
     GlTextureTarget, AsMem, MemCmdRw, MemCmdAll, MapFlags};
-use standard::{Context, Queue, SpatialDims, ClNullEventPtrEnum, ClWaitListPtrEnum,
+use standard::{self, Context, Queue, Device, SpatialDims, ClNullEventPtrEnum, ClWaitListPtrEnum,
     QueCtx};
 use ::MemMap;
+#[cfg(feature = "image")]
+use image::{DynamicImage, RgbaImage};
 
 
 /// The type of operation to be performed by a command.
@@ -81,6 +84,7 @@ pub struct ImageCmd<'c, T: 'c> {
     ewait: Option<ClWaitListPtrEnum<'c>>,
     enew: Option<ClNullEventPtrEnum<'c>>,
     mem_dims: [usize; 3],
+    mip_level: Option<usize>,
 }
 
 /// [UNSTABLE]: All methods still in a state of adjustifulsomeness.
@@ -103,6 +107,7 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
             ewait: None,
             enew: None,
             mem_dims: dims,
+            mip_level: None,
         }
     }
 
@@ -346,6 +351,19 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
         self
     }
 
+    /// Specifies the mip level of a mipmapped image (`cl_khr_mipmap_image`)
+    /// to be read or written.
+    ///
+    /// Unless a region is set with `::region`, the full extent of the level
+    /// (each dimension halved per level, to a minimum of one) is used. Only
+    /// 1D, 1D array, and 2D images are supported. The mip level is passed to
+    /// OpenCL in the otherwise unused coordinate of the origin.
+    ///
+    pub fn mip_level(mut self, mip_level: usize) -> ImageCmd<'c, T> {
+        self.mip_level = Some(mip_level);
+        self
+    }
+
     /// Sets the row and slice pitch for a read or write operation in bytes.
     ///
     /// `row_pitch_bytes`: Must be greater than or equal to the region width
@@ -402,12 +420,20 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
     ///
     /// * TODO: FOR COPY, FILL, AND COPYTOBUFFER -- ENSURE PITCHES ARE BOTH
     ///   UNSET.
-    pub fn enq(self) -> OclResult<()> {
+    pub fn enq(mut self) -> OclResult<()> {
         let queue = match self.queue {
             Some(q) => q,
             None => return Err("ImageCmd::enq: No queue set.".into()),
         };
 
+        if let Some(ref ewait) = self.ewait {
+            try!(ewait.check_order("ImageCmd::enq", queue));
+        }
+
+        if let Some(mip_level) = self.mip_level {
+            try!(self.apply_mip_level(mip_level));
+        }
+
         match self.kind {
             ImageCmdKind::Read { data } => {
                 // try!(check_len(self.to_len, data.len(), offset));
@@ -435,6 +461,32 @@ impl<'c, T: 'c + OclPrm> ImageCmd<'c, T> {
             _ => unimplemented!(),
         }
     }
+
+    /// Stores `mip_level` in the origin and, if the region is unchanged,
+    /// scales it to the extent of the level.
+    fn apply_mip_level(&mut self, mip_level: usize) -> OclResult<()> {
+        let image_type = match core::get_mem_object_info(self.obj_core, MemInfo::Type) {
+            MemInfoResult::Type(t) => t,
+            MemInfoResult::Error(err) => return Err(*err),
+            _ => panic!("ocl::ImageCmd::enq: Unexpected 'MemInfoResult' variant."),
+        };
+
+        let level_len = |len: usize| cmp::max(len >> mip_level, 1);
+        let region_is_default = self.region == self.mem_dims;
+
+        let (mip_idx, level_region) = match image_type {
+            MemObjectType::Image1d => (1, [level_len(self.mem_dims[0]), 1, 1]),
+            MemObjectType::Image1dArray => (2, [level_len(self.mem_dims[0]), self.mem_dims[1], 1]),
+            MemObjectType::Image2d => (2, [level_len(self.mem_dims[0]),
+                level_len(self.mem_dims[1]), 1]),
+            _ => return OclError::err_string(format!("ocl::ImageCmd::enq: Mip levels are only \
+                supported for 1D, 1D array, and 2D images (image type: {:?}).", image_type)),
+        };
+
+        self.origin[mip_idx] = mip_level;
+        if region_is_default { self.region = level_region; }
+        Ok(())
+    }
 }
 
 
@@ -540,6 +592,10 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
             None => return Err("ImageCmd::enq: No queue set.".into()),
         };
 
+        if let Some(ref ewait) = self.cmd.ewait {
+            try!(ewait.check_order("ImageMapCmd::enq", queue));
+        }
+
         let flags = self.flags.unwrap_or(MapFlags::empty());
 
         match self.cmd.kind {
@@ -589,6 +645,42 @@ impl<'c, T> ImageMapCmd<'c, T> where T: OclPrm {
 
 
 
+/// A host-side pixel component type which can be checked against an
+/// image's channel data type.
+///
+/// Used by `Image::write_checked` to ensure that host data matches the
+/// format of the image it is written to (preventing, for example, `u8` data
+/// being written to a `Float` image).
+pub trait ImageComponent: OclPrm {
+    /// Returns true if host data of this type is a valid representation of a
+    /// pixel component with the channel data type, `data_type`.
+    fn matches_channel_data_type(data_type: ImageChannelDataType) -> bool;
+}
+
+macro_rules! impl_image_component {
+    ($($ty:ty => [$($data_type:ident),+]),+) => ($(
+        impl ImageComponent for $ty {
+            fn matches_channel_data_type(data_type: ImageChannelDataType) -> bool {
+                match data_type {
+                    $(ImageChannelDataType::$data_type)|+ => true,
+                    _ => false,
+                }
+            }
+        }
+    )+)
+}
+
+impl_image_component!(
+    i8 => [SnormInt8, SignedInt8],
+    u8 => [UnormInt8, UnsignedInt8],
+    i16 => [SnormInt16, SignedInt16],
+    u16 => [UnormInt16, UnsignedInt16, UnormShort565, UnormShort555, HalfFloat],
+    i32 => [SignedInt32],
+    u32 => [UnsignedInt32, UnormInt101010],
+    f32 => [Float]
+);
+
+
 /// A section of device memory which represents one or many images.
 ///
 /// Use `::builder` for an easy way to create. [UNIMPLEMENTED]
@@ -774,6 +866,116 @@ impl<T: OclPrm> Image<T> {
         self.cmd().write(data)
     }
 
+    /// Returns an image command builder set to write after verifying that
+    /// the host component type, `T`, matches the channel data type of this
+    /// image.
+    ///
+    /// Call `.enq()` to enqueue the command.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if `T` does not correspond to the image's channel
+    /// data type (see `ImageComponent`).
+    ///
+    pub fn write_checked<'c, 'd>(&'c self, data: &'d [T]) -> OclResult<ImageCmd<'c, T>>
+        where 'd: 'c, T: ImageComponent
+    {
+        let data_type = match self.info(ImageInfo::Format) {
+            ImageInfoResult::Format(format) => format.channel_data_type,
+            ImageInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err_string("ocl::Image::write_checked(): \
+                Unexpected 'ImageInfoResult' variant."),
+        };
+
+        if !T::matches_channel_data_type(data_type) {
+            return OclError::err_string(format!("ocl::Image::write_checked(): The host data \
+                type does not match the channel data type of this image ('{:?}').", data_type));
+        }
+
+        Ok(self.cmd().write(data))
+    }
+
+    /// Returns an image command builder set to read a single layer of a 2D
+    /// image array into `data`.
+    ///
+    /// The origin of the read is `[0, 0, layer]` and the region is one full
+    /// layer (`[width, height, 1]`). `data` must hold exactly one layer
+    /// (`width * height * ::pixel_element_len` elements).
+    ///
+    /// Call `.enq()` to enqueue the command.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if this is not a 2D image array, if `layer` is not
+    /// less than its array size, or if the length of `data` is incorrect.
+    ///
+    pub fn read_layer<'c, 'd>(&'c self, layer: usize, data: &'d mut [T])
+            -> OclResult<ImageCmd<'c, T>>
+        where 'd: 'c
+    {
+        match self.mem_info(MemInfo::Type) {
+            MemInfoResult::Type(MemObjectType::Image2dArray) => (),
+            MemInfoResult::Type(mem_obj_type) => return OclError::err_string(format!(
+                "ocl::Image::read_layer(): Layers may only be read from 2D image arrays \
+                (image type: '{:?}').", mem_obj_type)),
+            MemInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err_string("ocl::Image::read_layer(): \
+                Unexpected 'MemInfoResult' variant."),
+        }
+
+        let array_size = match self.info(ImageInfo::ArraySize) {
+            ImageInfoResult::ArraySize(s) => s,
+            ImageInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err_string("ocl::Image::read_layer(): \
+                Unexpected 'ImageInfoResult' variant."),
+        };
+
+        if layer >= array_size {
+            return OclError::err_string(format!("ocl::Image::read_layer(): Layer index ({}) \
+                out of range (array size: {}).", layer, array_size));
+        }
+
+        let width = match self.info(ImageInfo::Width) {
+            ImageInfoResult::Width(w) => w,
+            ImageInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err_string("ocl::Image::read_layer(): \
+                Unexpected 'ImageInfoResult' variant."),
+        };
+
+        let height = match self.info(ImageInfo::Height) {
+            ImageInfoResult::Height(h) => h,
+            ImageInfoResult::Error(err) => return Err(*err),
+            _ => return OclError::err_string("ocl::Image::read_layer(): \
+                Unexpected 'ImageInfoResult' variant."),
+        };
+
+        let layer_len = width * height * self.pixel_element_len;
+
+        if data.len() != layer_len {
+            return OclError::err_string(format!("ocl::Image::read_layer(): The length of the \
+                destination ({}) must equal the length of one layer ({}).", data.len(),
+                layer_len));
+        }
+
+        Ok(self.cmd().read(data).origin([0, 0, layer]).region([width, height, 1]))
+    }
+
+    /// Reads the entire image into a new, tightly packed vector (blocking).
+    ///
+    /// Pixels are stored in row-major order (row by row, then slice by
+    /// slice) with each pixel's `::pixel_element_len` components stored
+    /// contiguously. The length of the vector is `::element_count`.
+    ///
+    /// ### Errors
+    ///
+    /// A default queue must be set for this image.
+    ///
+    pub fn read_flat(&self) -> OclResult<Vec<T>> {
+        let mut vec = vec![T::default(); self.element_count()];
+        try!(self.cmd().read(&mut vec).enq());
+        Ok(vec)
+    }
+
     /// Returns a command builder used to map data for reading or writing.
     ///
     /// Call `.enq()` to enqueue the command.
@@ -911,6 +1113,62 @@ impl<T: OclPrm> Image<T> {
     }
 }
 
+#[cfg(feature = "image")]
+impl Image<u8> {
+    /// Returns a new 2D `Rgba`/`UnormInt8` image containing the pixels of
+    /// `img` and using `queue` as its default queue.
+    ///
+    /// RGBA8 images are copied directly. Images of any other format are
+    /// first converted to RGBA8.
+    pub fn from_dynamic_image(queue: &Queue, img: &DynamicImage) -> OclResult<Image<u8>> {
+        let converted;
+        let rgba = match *img {
+            DynamicImage::ImageRgba8(ref rgba) => rgba,
+            _ => {
+                converted = img.to_rgba();
+                &converted
+            },
+        };
+
+        let (width, height) = (rgba.width(), rgba.height());
+
+        Image::<u8>::builder()
+            .channel_order(ImageChannelOrder::Rgba)
+            .channel_data_type(ImageChannelDataType::UnormInt8)
+            .image_type(MemObjectType::Image2d)
+            .dims((width as usize, height as usize))
+            .flags(::flags::MEM_READ_WRITE)
+            .copy_host_slice(&**rgba)
+            .queue(queue.clone())
+            .build()
+    }
+
+    /// Reads this image into a new `RgbaImage` (blocking).
+    ///
+    /// ### Errors
+    ///
+    /// The image must be a 2D image with four components per pixel and a
+    /// default queue must be set.
+    ///
+    pub fn to_rgba_image(&self) -> OclResult<RgbaImage> {
+        let lens = try!(self.dims.to_lens());
+
+        if self.pixel_element_len != 4 || lens[2] != 1 {
+            return OclError::err_string(format!("ocl::Image::to_rgba_image: The image must be \
+                two dimensional with four components per pixel (dims: {:?}, components: {}).",
+                self.dims, self.pixel_element_len));
+        }
+
+        let vec = try!(self.read_flat());
+
+        match RgbaImage::from_raw(lens[0] as u32, lens[1] as u32, vec) {
+            Some(rgba) => Ok(rgba),
+            None => OclError::err_string("ocl::Image::to_rgba_image: The image data is \
+                insufficient for its dimensions."),
+        }
+    }
+}
+
 impl<T: OclPrm> std::fmt::Display for Image<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         try!(self.fmt_info(f));
@@ -954,6 +1212,44 @@ unsafe impl<'a, T> MemCmdAll for &'a mut Image<T> where T: OclPrm {}
 
 
 
+/// Returns the number of host elements (of the image's component type) per
+/// pixel for `image_format`.
+///
+/// Packed formats (such as `UnormShort565`) are a single element per pixel.
+pub fn channel_count(image_format: &ImageFormat) -> usize {
+    match image_format.channel_data_type {
+        ImageChannelDataType::UnormShort565 | ImageChannelDataType::UnormShort555 |
+            ImageChannelDataType::UnormInt101010 => 1,
+        _ => match image_format.channel_order {
+            ImageChannelOrder::R | ImageChannelOrder::A | ImageChannelOrder::Intensity |
+                ImageChannelOrder::Luminance | ImageChannelOrder::Rx |
+                ImageChannelOrder::Depth => 1,
+            ImageChannelOrder::Rg | ImageChannelOrder::Ra | ImageChannelOrder::Rgx |
+                ImageChannelOrder::DepthStencil => 2,
+            ImageChannelOrder::Rgb | ImageChannelOrder::Rgbx | ImageChannelOrder::Srgb |
+                ImageChannelOrder::Srgbx => 3,
+            _ => 4,
+        },
+    }
+}
+
+/// Returns the size, in bytes, of a single pixel of `image_format`.
+fn pixel_size(image_format: &ImageFormat) -> usize {
+    let channels = channel_count(image_format);
+
+    match image_format.channel_data_type {
+        ImageChannelDataType::UnormShort565 | ImageChannelDataType::UnormShort555 => 2,
+        ImageChannelDataType::UnormInt101010 => 4,
+        ImageChannelDataType::SnormInt8 | ImageChannelDataType::UnormInt8 |
+            ImageChannelDataType::SignedInt8 | ImageChannelDataType::UnsignedInt8 => channels,
+        ImageChannelDataType::SnormInt16 | ImageChannelDataType::UnormInt16 |
+            ImageChannelDataType::SignedInt16 | ImageChannelDataType::UnsignedInt16 |
+            ImageChannelDataType::HalfFloat => channels * 2,
+        _ => channels * 4,
+    }
+}
+
+
 /// A builder for `Image`.
 #[must_use = "builders do nothing unless '::build' is called"]
 pub struct ImageBuilder<'a, T> where T: 'a {
@@ -962,6 +1258,7 @@ pub struct ImageBuilder<'a, T> where T: 'a {
     host_data: Option<&'a [T]>,
     image_format: ImageFormat,
     image_desc: ImageDescriptor,
+    check_host_data_len: bool,
     _pixel: PhantomData<T>,
     // host_data: Option<&'a [S]>,
 }
@@ -1007,6 +1304,7 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
             host_data: None,
             image_format: ImageFormat::new_rgba(),
             image_desc: ImageDescriptor::new(MemObjectType::Image1d, 0, 0, 0, 0, 0, 0, None),
+            check_host_data_len: false,
             _pixel: PhantomData,
             // host_data: None,
         }
@@ -1049,6 +1347,31 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Sets the device access flag to `MEM_READ_ONLY`, replacing any other
+    /// access flag (`MEM_READ_WRITE` or `MEM_WRITE_ONLY`) previously set.
+    ///
+    /// Other flags are preserved. `::read_only`, `::write_only`, and
+    /// `::read_write` are mutually exclusive; the last call wins. A
+    /// subsequent call to `::flags` overwrites all flags.
+    pub fn read_only(mut self) -> ImageBuilder<'a, T> {
+        self.flags = standard::set_access_flags(self.flags, ::flags::MEM_READ_ONLY);
+        self
+    }
+
+    /// Sets the device access flag to `MEM_WRITE_ONLY`, replacing any other
+    /// access flag previously set (see `::read_only`).
+    pub fn write_only(mut self) -> ImageBuilder<'a, T> {
+        self.flags = standard::set_access_flags(self.flags, ::flags::MEM_WRITE_ONLY);
+        self
+    }
+
+    /// Sets the device access flag to `MEM_READ_WRITE` (the default),
+    /// replacing any other access flag previously set (see `::read_only`).
+    pub fn read_write(mut self) -> ImageBuilder<'a, T> {
+        self.flags = standard::set_access_flags(self.flags, ::flags::MEM_READ_WRITE);
+        self
+    }
+
     /// A slice use to designate a region of memory for use in combination of
     /// one of the two following flags:
     ///
@@ -1092,6 +1415,45 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Copies `data` into the new image upon creation (adds the
+    /// `MEM_COPY_HOST_PTR` flag).
+    ///
+    /// The length of `data` is checked when building and must equal the
+    /// size of the image (width * height * depth * array size * pixel size)
+    /// divided by the size of `T`. `T` may be either a scalar matching the
+    /// channel data type (one element per channel) or a vector type holding
+    /// entire pixels (such as `Uchar4` for an RGBA, `UnormInt8` image).
+    pub fn copy_host_slice<'d>(mut self, data: &'d [T]) -> ImageBuilder<'a, T>
+            where 'd: 'a
+    {
+        self.host_data = Some(data);
+        self.flags = self.flags | ::flags::MEM_COPY_HOST_PTR;
+        self.check_host_data_len = true;
+        self
+    }
+
+    /// Returns the number of elements of type `T` needed to fill an image
+    /// with the current format and dimensions.
+    ///
+    /// `T` may be a scalar channel component (such as `u8`) or a vector
+    /// holding one or more whole pixels (such as `Uchar4`). An error is
+    /// returned if the size of `T` does not evenly divide the size of the
+    /// image.
+    fn host_data_len(&self) -> OclResult<usize> {
+        let desc = &self.image_desc;
+        let pixel_count = desc.image_width * cmp::max(desc.image_height, 1) *
+            cmp::max(desc.image_depth, 1) * cmp::max(desc.image_array_size, 1);
+        let image_size = pixel_count * pixel_size(&self.image_format);
+
+        if image_size % mem::size_of::<T>() != 0 {
+            return OclError::err_string(format!("ocl::ImageBuilder::build: The size of the host \
+                data type ({} bytes) does not evenly divide the size of the image ({} bytes).",
+                mem::size_of::<T>(), image_size));
+        }
+
+        Ok(image_size / mem::size_of::<T>())
+    }
+
     pub fn channel_order(mut self, order: ImageChannelOrder) -> ImageBuilder<'a, T> {
         self.image_format.channel_order = order;
         self
@@ -1219,6 +1581,19 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
         self
     }
 
+    /// Number of mip levels (`cl_khr_mipmap_image`).
+    ///
+    /// Levels are read and written using `ImageCmd::mip_level`. Mipmapped
+    /// images may not be created with host data. When `num_mip_levels` is
+    /// greater than one, the device of the default queue (or every device in
+    /// the context if only a context is set) must support the
+    /// `cl_khr_mipmap_image` extension.
+    ///
+    pub fn num_mip_levels(mut self, num_mip_levels: u32) -> ImageBuilder<'a, T> {
+        self.image_desc.num_mip_levels = num_mip_levels;
+        self
+    }
+
     /// Specifies the image pixel format.
     ///
     /// If unspecified, defaults to:
@@ -1280,9 +1655,87 @@ impl<'a, T> ImageBuilder<'a, T> where T: 'a + OclPrm {
             Some(host_data))
     }
 
+    /// Returns the devices the image is being created for: the device of
+    /// the default queue if one is set, every device in the context if only
+    /// a context is set, or none if neither is set.
+    fn target_devices(&self) -> Vec<Device> {
+        match self.queue_option {
+            Some(QueCtx::Queue(ref queue)) => vec![queue.device()],
+            Some(QueCtx::Context(context)) => context.devices(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns an error if the dimensions of the image exceed the limits of
+    /// `device` (rather than the less descriptive `CL_INVALID_IMAGE_SIZE`).
+    fn check_image_limits(&self, device: &Device) -> OclResult<()> {
+        let limits = try!(device.image_limits());
+        let desc = &self.image_desc;
+
+        let checks: Vec<(&str, usize, usize)> = match desc.image_type {
+            MemObjectType::Image1dBuffer => vec![
+                ("width", desc.image_width, limits.image_max_buffer_size)],
+            MemObjectType::Image1d => vec![("width", desc.image_width, limits.image2d_max_width)],
+            MemObjectType::Image1dArray => vec![
+                ("width", desc.image_width, limits.image2d_max_width),
+                ("array size", desc.image_array_size, limits.image_max_array_size)],
+            MemObjectType::Image2d => vec![
+                ("width", desc.image_width, limits.image2d_max_width),
+                ("height", desc.image_height, limits.image2d_max_height)],
+            MemObjectType::Image2dArray => vec![
+                ("width", desc.image_width, limits.image2d_max_width),
+                ("height", desc.image_height, limits.image2d_max_height),
+                ("array size", desc.image_array_size, limits.image_max_array_size)],
+            MemObjectType::Image3d => vec![
+                ("width", desc.image_width, limits.image3d_max_width),
+                ("height", desc.image_height, limits.image3d_max_height),
+                ("depth", desc.image_depth, limits.image3d_max_depth)],
+            _ => Vec::new(),
+        };
+
+        for (dim_name, size, max) in checks {
+            if size > max {
+                return OclError::err_string(format!("ocl::ImageBuilder::build: The image {} ({}) \
+                    exceeds the maximum supported by the device '{}' ({}) for images of type \
+                    '{:?}'.", dim_name, size, device.name(), max, desc.image_type));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Builds with no host side image data memory specified and returns a
     /// new `Image`.
+    ///
+    /// The image dimensions are checked against the limits of the device of
+    /// the default queue (or of every device in the context if only a
+    /// context is set).
     pub fn build(self) -> OclResult<Image<T>> {
+        if self.check_host_data_len {
+            let data_len = self.host_data.map(|d| d.len()).unwrap_or(0);
+            let image_len = try!(self.host_data_len());
+
+            if data_len != image_len {
+                return OclError::err_string(format!("ocl::ImageBuilder::build: The length of \
+                    the host slice ({}) does not match the size of the image ({} elements).",
+                    data_len, image_len));
+            }
+        }
+
+        for device in self.target_devices() {
+            try!(self.check_image_limits(&device));
+        }
+
+        if self.image_desc.num_mip_levels > 1 {
+            for device in self.target_devices() {
+                if !try!(device.supports_extension("cl_khr_mipmap_image")) {
+                    return OclError::err_string(format!("ocl::ImageBuilder::build: The device \
+                        '{}' does not support mipmapped images ('cl_khr_mipmap_image').",
+                        device.name()));
+                }
+            }
+        }
+
         match self.queue_option {
             Some(qo) => {
                 Image::new(qo, self.flags, self.image_format.clone(),
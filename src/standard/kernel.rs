@@ -3,16 +3,27 @@
 use std;
 use std::ops::{Deref, DerefMut};
 use std::any::Any;
+use std::mem;
+use std::slice;
+use std::ffi::CString;
+use libc::{c_void, size_t};
+use num::FromPrimitive;
+use ffi;
 // use std::rc::{Rc};
 // use std::cell::{RefCell};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::HashMap;
 use core::{self, OclPrm, Kernel as KernelCore, CommandQueue as CommandQueueCore, Mem as MemCore,
     KernelArg, KernelInfo, KernelInfoResult, KernelArgInfo, KernelArgInfoResult,
-    KernelWorkGroupInfo, KernelWorkGroupInfoResult, AsMem, MemCmdAll, ClVersions};
+    KernelWorkGroupInfo, KernelWorkGroupInfoResult, AsMem, MemCmdAll, ClVersions, DeviceInfo,
+    DeviceInfoResult, Status, ClDeviceIdPtr, CommandQueueInfo, CommandQueueInfoResult,
+    QUEUE_PROFILING_ENABLE};
 use core::error::{Result as OclResult, Error as OclError};
-use standard::{SpatialDims, Program, Queue, WorkDims, Sampler, Device, ClNullEventPtrEnum,
-    ClWaitListPtrEnum};
+#[cfg(feature = "opencl_version_2_1")]
+use core::OpenclVersion;
+use standard::{SpatialDims, Dimensioned, Program, Queue, WorkDims, Sampler, Device, Buffer,
+    Image, ClNullEventPtrEnum, ClWaitListPtrEnum, Event, ProfilingNanos};
 pub use self::arg_type::{BaseType, Cardinality, ArgType};
 
 const PRINT_DEBUG: bool = false;
@@ -22,10 +33,16 @@ const PRINT_DEBUG: bool = false;
 #[must_use = "commands do nothing unless enqueued"]
 pub struct KernelCmd<'k> {
     queue: Option<&'k CommandQueueCore>,
+    default_queue: Option<&'k CommandQueueCore>,
+    profiling_queue: Option<&'k CommandQueueCore>,
+    profiled: bool,
     kernel: &'k KernelCore,
     gwo: SpatialDims,
     gws: SpatialDims,
     lws: SpatialDims,
+    work_dims: Option<u32>,
+    reqd_wg_size: [usize; 3],
+    default_args: Option<&'k Mutex<HashMap<u32, DefaultArg>>>,
     wait_list: Option<ClWaitListPtrEnum<'k>>,
     new_event: Option<ClNullEventPtrEnum<'k>>,
 }
@@ -42,6 +59,26 @@ impl<'k> KernelCmd<'k> {
         self
     }
 
+    /// Specifies that this command should be enqueued on a
+    /// profiling-enabled queue.
+    ///
+    /// If no queue has been specified with `::queue` (or the kernel's
+    /// default queue has), the profiling-enabled queue associated with the
+    /// default queue is used (see `Queue::enable_profiling_sibling`).
+    /// Otherwise the specified queue is used and must itself have profiling
+    /// enabled (pass `Queue::profiled` to `::queue` to use its sibling).
+    ///
+    /// ### Errors
+    ///
+    /// Enqueuing returns an error unless the kernel's default queue either
+    /// has profiling enabled or has a profiling sibling or, when another
+    /// queue has been specified, unless that queue has profiling enabled.
+    ///
+    pub fn profiled(mut self) -> KernelCmd<'k> {
+        self.profiled = true;
+        self
+    }
+
     /// Specifies a global work offset for this call only.
      pub fn gwo<D: Into<SpatialDims>>(mut self, gwo: D) -> KernelCmd<'k> {
         self.gwo = gwo.into();
@@ -97,13 +134,84 @@ impl<'k> KernelCmd<'k> {
         self
     }
 
-    /// Enqueues this kernel command.
-    pub fn enq(self) -> OclResult<()> {
+    /// Returns an error if the kernel's required work dimension count (see
+    /// `Kernel::work_dims`) does not match the global work size, or a
+    /// specified global work offset or local work size.
+    fn check_work_dims(&self) -> OclResult<()> {
+        let work_dims = match self.work_dims {
+            Some(d) => d,
+            None => return Ok(()),
+        };
+
+        let sizes = [("global work size ('gws')", self.gws, true),
+            ("global work offset ('gwo')", self.gwo, false),
+            ("local work size ('lws')", self.lws, false)];
+
+        for &(name, dims, required) in sizes.iter() {
+            if (required || dims.dim_count() != 0) && dims.dim_count() != work_dims {
+                return OclError::err_string(format!("ocl::KernelCmd::enq: The {} has {} \
+                    dimension(s) ({:?}) but the kernel requires {}.", name, dims.dim_count(),
+                    dims, work_dims));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the queue to enqueue on, resolving the profiling queue if
+    /// this command is profiled (see `::profiled`).
+    fn resolve_queue(&self) -> OclResult<&'k CommandQueueCore> {
         let queue = match self.queue {
             Some(q) => q,
-            None => return Err("KernelCmd::enq: No queue specified.".into()),
+            None => return Err("ocl::KernelCmd::enq: No queue specified.".into()),
         };
 
+        if !self.profiled { return Ok(queue); }
+
+        if self.default_queue.map(|dq| dq.as_ptr()) == Some(queue.as_ptr()) {
+            return match self.profiling_queue {
+                Some(q) => Ok(q),
+                None => OclError::err_string("ocl::KernelCmd::enq: The default queue for this \
+                    kernel does not have profiling enabled or a profiling sibling. Use \
+                    'Queue::enable_profiling_sibling' to create one."),
+            };
+        }
+
+        match core::get_command_queue_info(queue, CommandQueueInfo::Properties) {
+            CommandQueueInfoResult::Properties(props) => {
+                if props.contains(QUEUE_PROFILING_ENABLE) {
+                    Ok(queue)
+                } else {
+                    OclError::err_string("ocl::KernelCmd::enq: The specified queue does not \
+                        have profiling enabled. Pass its profiling sibling instead (see \
+                        'Queue::profiled').")
+                }
+            },
+            CommandQueueInfoResult::Error(err) => Err(*err),
+            _ => panic!("ocl::KernelCmd::enq: Unexpected 'CommandQueueInfoResult' variant."),
+        }
+    }
+
+    /// Enqueues this kernel command.
+    ///
+    /// If the kernel declares a required work group size
+    /// (`reqd_work_group_size`) and no local work size has been set, the
+    /// required size is used. A conflicting local work size causes an error.
+    ///
+    /// If a work dimension count has been set (see `Kernel::work_dims`),
+    /// work sizes with a different number of dimensions cause an error.
+    ///
+    /// Any default arguments overridden for the previous launch (see
+    /// `Kernel::set_default_arg`) are re-applied beforehand.
+    pub fn enq(self) -> OclResult<()> {
+        let queue = try!(self.resolve_queue());
+
+        if let Some(ref wait_list) = self.wait_list {
+            try!(wait_list.check_order("KernelCmd::enq", queue));
+        }
+
+        try!(self.check_work_dims());
+
         let dim_count = self.gws.dim_count();
 
         let gws = match self.gws.to_work_size() {
@@ -117,12 +225,477 @@ impl<'k> KernelCmd<'k> {
                 core::get_kernel_info(self.kernel, KernelInfo::FunctionName));
         }
 
-        core::enqueue_kernel(queue, self.kernel, dim_count, self.gwo.to_work_offset(),
-            &gws, self.lws.to_work_size(), self.wait_list, self.new_event)
+        let lws = try!(resolve_required_lws(self.kernel, self.reqd_wg_size, dim_count,
+            self.lws));
+
+        if let Some(default_args) = self.default_args {
+            try!(apply_default_args(self.kernel, default_args));
+        }
+
+        let res = core::enqueue_kernel(queue, self.kernel, dim_count, self.gwo.to_work_offset(),
+            &gws, lws.to_work_size(), self.wait_list, self.new_event);
+        trace_cl!("clEnqueueNDRangeKernel", &res, "queue: {:?}, kernel: {:?}, work_dim: {}, \
+            gwo: {:?}, gws: {:?}, lws: {:?}", queue.as_ptr(), self.kernel.as_ptr(), dim_count,
+            self.gwo, self.gws, lws);
+        res
     }
 }
 
 
+/// A record of the value a kernel argument was last set to.
+///
+/// Recorded when argument tracking is enabled (see `Kernel::track_args`) and
+/// retrieved using `Kernel::debug_args`. Intended purely as a debugging aid.
+#[derive(Clone, Debug)]
+pub enum DebugArg {
+    /// The argument has not been set (or was set while tracking was off).
+    Unset,
+    /// A scalar argument along with its type and raw bytes.
+    Scalar { arg_type: ArgType, bytes: Vec<u8> },
+    /// A vector argument along with its type and raw bytes.
+    Vector { arg_type: ArgType, bytes: Vec<u8> },
+    /// A buffer or image argument and its `cl_mem` handle.
+    Mem { handle: usize },
+    /// A null buffer or image argument.
+    MemNull,
+    /// A sampler argument and its `cl_sampler` handle.
+    Sampler { handle: usize },
+    /// A null sampler argument.
+    SamplerNull,
+    /// A local memory allocation argument and its length in bytes.
+    Local { len: usize },
+    /// Any other kind of argument.
+    Other,
+}
+
+impl DebugArg {
+    /// Returns a new `DebugArg` representing the argument `arg`.
+    fn new<T: OclPrm>(arg: &KernelArg<T>, arg_type: &ArgType) -> DebugArg {
+        fn bytes_of<T>(val: &T) -> Vec<u8> {
+            unsafe {
+                slice::from_raw_parts(val as *const T as *const u8, mem::size_of::<T>()).to_vec()
+            }
+        }
+
+        match *arg {
+            KernelArg::Scalar(ref val) => DebugArg::Scalar { arg_type: arg_type.clone(),
+                bytes: bytes_of(val) },
+            KernelArg::Vector(ref val) => DebugArg::Vector { arg_type: arg_type.clone(),
+                bytes: bytes_of(val) },
+            KernelArg::Mem(mem) => DebugArg::Mem { handle: mem.as_ptr() as usize },
+            KernelArg::MemNull => DebugArg::MemNull,
+            KernelArg::Sampler(smp) => DebugArg::Sampler { handle: smp.as_ptr() as usize },
+            KernelArg::SamplerNull => DebugArg::SamplerNull,
+            KernelArg::Local(len) => DebugArg::Local { len: *len * mem::size_of::<T>() },
+            #[allow(unreachable_patterns)]
+            _ => DebugArg::Other,
+        }
+    }
+}
+
+
+/// Returns the largest value no greater than `max` which evenly divides
+/// `len` and is a multiple of `multiple`.
+fn largest_divisor(len: usize, max: usize, multiple: usize) -> Option<usize> {
+    if multiple == 0 { return None; }
+    let mut size = max.min(len) / multiple * multiple;
+
+    while size > 0 {
+        if len % size == 0 { return Some(size); }
+        size -= multiple;
+    }
+
+    None
+}
+
+/// Chooses a local work size for `gws` (see `Kernel::auto_lws`).
+fn auto_lws(gws: SpatialDims, max_size: usize, multiple: usize) -> SpatialDims {
+    let lens = match gws.to_lens() {
+        Ok(lens) => lens,
+        Err(_) => return SpatialDims::Unspecified,
+    };
+
+    // A size of one is never worth specifying over letting the driver
+    // choose (it is the only divisor found for a prime size when the
+    // preferred multiple is one):
+    let l0 = match largest_divisor(lens[0], max_size, multiple) {
+        Some(l) if l > 1 => l,
+        _ => return SpatialDims::Unspecified,
+    };
+
+    let l1 = largest_divisor(lens[1], max_size / l0, 1).unwrap_or(1);
+    let l2 = largest_divisor(lens[2], max_size / (l0 * l1), 1).unwrap_or(1);
+
+    match gws {
+        SpatialDims::One(..) => SpatialDims::One(l0),
+        SpatialDims::Two(..) => SpatialDims::Two(l0, l1),
+        SpatialDims::Three(..) => SpatialDims::Three(l0, l1, l2),
+        SpatialDims::Unspecified => SpatialDims::Unspecified,
+    }
+}
+
+/// Returns the required work group size declared by `kernel`
+/// (`__attribute__((reqd_work_group_size(X, Y, Z)))`), or `[0, 0, 0]` if none
+/// is declared.
+///
+/// The size is declared in the kernel source and is therefore the same for
+/// every device associated with the kernel's program. It is queried using
+/// the first.
+fn query_required_wg_size(kernel: &KernelCore) -> OclResult<[usize; 3]> {
+    let program = match core::get_kernel_info(kernel, KernelInfo::Program) {
+        KernelInfoResult::Program(p) => p,
+        KernelInfoResult::Error(err) => return Err(*err),
+        _ => panic!("ocl::Kernel::new: Unexpected 'KernelInfoResult' variant."),
+    };
+
+    let devices = match core::get_program_info(&program, core::ProgramInfo::Devices) {
+        core::ProgramInfoResult::Devices(d) => d,
+        core::ProgramInfoResult::Error(err) => return Err(*err),
+        _ => panic!("ocl::Kernel::new: Unexpected 'ProgramInfoResult' variant."),
+    };
+
+    let device = match devices.first() {
+        Some(d) => d,
+        None => return Ok([0, 0, 0]),
+    };
+
+    match core::get_kernel_work_group_info(kernel, device,
+            KernelWorkGroupInfo::CompileWorkGroupSize) {
+        KernelWorkGroupInfoResult::CompileWorkGroupSize(s) => Ok(s),
+        KernelWorkGroupInfoResult::Error(err) => Err(*err),
+        _ => panic!("ocl::Kernel::new: Unexpected 'KernelWorkGroupInfoResult' variant."),
+    }
+}
+
+/// Returns the local work size to use when enqueuing `kernel` with
+/// `dim_count` dimensions, accounting for the required work group size,
+/// `reqd`, declared by the kernel (see `query_required_wg_size`).
+///
+/// If a required size is declared, it is used when `lws` is unspecified and
+/// an error is returned if `lws` conflicts with it. Returns `lws` unchanged if
+/// no required size is declared.
+fn resolve_required_lws(kernel: &KernelCore, reqd: [usize; 3], dim_count: u32,
+        lws: SpatialDims) -> OclResult<SpatialDims>
+{
+    if reqd == [0, 0, 0] { return Ok(lws); }
+
+    match lws.to_lens() {
+        Ok(lens) => {
+            if lens == reqd {
+                Ok(lws)
+            } else {
+                OclError::err_string(format!("ocl::KernelCmd::enq: The local work size ('lws') \
+                    specified for the kernel '{}', {:?}, conflicts with the required work group \
+                    size declared by the kernel, {:?}.",
+                    core::get_kernel_info(kernel, KernelInfo::FunctionName), lens, reqd))
+            }
+        },
+        Err(_) => Ok(match dim_count {
+            1 => SpatialDims::One(reqd[0]),
+            2 => SpatialDims::Two(reqd[0], reqd[1]),
+            _ => SpatialDims::Three(reqd[0], reqd[1], reqd[2]),
+        }),
+    }
+}
+
+
+const CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE: ffi::cl_uint = 0x2033;
+const CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE: ffi::cl_uint = 0x2034;
+
+type GetKernelSubGroupInfoFn = unsafe extern "system" fn(ffi::cl_kernel, ffi::cl_device_id,
+    ffi::cl_uint, size_t, *const c_void, size_t, *mut c_void, *mut size_t) -> ffi::cl_int;
+
+/// Returns the OpenCL 2.1 core `clGetKernelSubGroupInfo` if `device`
+/// supports subgroups as a core feature.
+///
+/// Core functions are not available through the extension function lookup
+/// and must be linked directly.
+#[cfg(feature = "opencl_version_2_1")]
+fn core_get_kernel_sub_group_info(device: &Device) -> OclResult<Option<GetKernelSubGroupInfoFn>> {
+    if try!(device.version()) >= OpenclVersion::new(2, 1) &&
+            device.max_num_subgroups().map(|n| n > 0).unwrap_or(false)
+    {
+        Ok(Some(ffi::clGetKernelSubGroupInfo))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns `None`: the OpenCL 2.1 core `clGetKernelSubGroupInfo` is only
+/// linked with the `opencl_version_2_1` feature.
+#[cfg(not(feature = "opencl_version_2_1"))]
+fn core_get_kernel_sub_group_info(_: &Device) -> OclResult<Option<GetKernelSubGroupInfoFn>> {
+    Ok(None)
+}
+
+/// Returns the OpenCL 2.1 core `clGetKernelSubGroupInfo` (with the
+/// `opencl_version_2_1` feature) or loads the `cl_khr_subgroups` extension
+/// function, `clGetKernelSubGroupInfoKHR`, for the platform associated with
+/// `device`.
+fn load_get_kernel_sub_group_info(device: &Device) -> OclResult<GetKernelSubGroupInfoFn> {
+    if let Some(f) = try!(core_get_kernel_sub_group_info(device)) {
+        return Ok(f);
+    }
+
+    if try!(device.supports_extension("cl_khr_subgroups")) {
+        let platform = match device.info(DeviceInfo::Platform) {
+            DeviceInfoResult::Platform(p) => p,
+            DeviceInfoResult::Error(err) => return Err(*err),
+            _ => panic!("ocl::Kernel::subgroup_info: Unexpected 'DeviceInfoResult' variant."),
+        };
+
+        let c_name = CString::new("clGetKernelSubGroupInfoKHR")
+            .expect("ocl::Kernel::subgroup_info: Invalid function name.");
+        let fn_ptr = unsafe {
+            ffi::clGetExtensionFunctionAddressForPlatform(platform.as_ptr(), c_name.as_ptr())
+        };
+
+        if !fn_ptr.is_null() {
+            return Ok(unsafe { mem::transmute(fn_ptr) });
+        }
+    }
+
+    OclError::err_string(format!("ocl::Kernel::subgroup_info: Subgroup queries are not \
+        supported by the device '{}' (requires 'cl_khr_subgroups', or OpenCL 2.1 and the \
+        'opencl_version_2_1' feature).", device.name()))
+}
+
+/// Returns a new kernel object with a copy of the argument values of
+/// `kernel` using `clCloneKernel`.
+#[cfg(feature = "opencl_version_2_1")]
+fn clone_kernel_core(kernel: &KernelCore) -> OclResult<(KernelCore, bool)> {
+    let mut errcode: ffi::cl_int = 0;
+    let ptr = unsafe { ffi::clCloneKernel(kernel.as_ptr(), &mut errcode) };
+
+    if errcode != ffi::CL_SUCCESS as ffi::cl_int {
+        return OclError::err_string(format!("Kernel::duplicate: 'clCloneKernel' failed with \
+            status: {:?} ({})", Status::from_i32(errcode), errcode));
+    }
+
+    Ok((unsafe { KernelCore::from_raw_create_ptr(ptr) }, true))
+}
+
+/// Returns a new kernel object created from the same program and function
+/// name as `kernel`. No arguments are set.
+#[cfg(not(feature = "opencl_version_2_1"))]
+fn clone_kernel_core(kernel: &KernelCore) -> OclResult<(KernelCore, bool)> {
+    let program = match core::get_kernel_info(kernel, KernelInfo::Program) {
+        KernelInfoResult::Program(p) => p,
+        KernelInfoResult::Error(err) => return Err(*err),
+        _ => panic!("Kernel::duplicate: Unexpected 'KernelInfoResult' variant."),
+    };
+
+    let name = match core::get_kernel_info(kernel, KernelInfo::FunctionName) {
+        KernelInfoResult::FunctionName(n) => n,
+        KernelInfoResult::Error(err) => return Err(*err),
+        _ => panic!("Kernel::duplicate: Unexpected 'KernelInfoResult' variant."),
+    };
+
+    core::create_kernel(&program, &name).map(|core| (core, false))
+}
+
+/// Sets the argument at `arg_idx` to the raw value `size` bytes long at
+/// `value` (which may be null for local memory arguments).
+unsafe fn set_kernel_arg_raw(kernel: &KernelCore, arg_idx: u32, size: usize,
+        value: *const c_void) -> OclResult<()>
+{
+    let errcode = ffi::clSetKernelArg(kernel.as_ptr(), arg_idx, size, value);
+
+    if errcode == ffi::CL_SUCCESS as ffi::cl_int {
+        Ok(())
+    } else {
+        OclError::err_string(format!("Kernel::duplicate: 'clSetKernelArg' failed for argument \
+            {} with status: {:?} ({})", arg_idx, Status::from_i32(errcode), errcode))
+    }
+}
+
+
+#[cfg(feature = "opencl_version_2_0")]
+const CL_DEVICE_SVM_CAPABILITIES: ffi::cl_uint = 0x1053;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_DEVICE_SVM_FINE_GRAIN_SYSTEM: ffi::cl_bitfield = 1 << 2;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_KERNEL_EXEC_INFO_SVM_PTRS: ffi::cl_uint = 0x11B6;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_KERNEL_EXEC_INFO_SVM_FINE_GRAIN_SYSTEM: ffi::cl_uint = 0x11B7;
+
+/// Returns an error unless every device associated with the program of
+/// `kernel` supports SVM (shared virtual memory) with at least the
+/// capabilities in `required` (`CL_DEVICE_SVM_CAPABILITIES`).
+#[cfg(feature = "opencl_version_2_0")]
+fn check_svm_support(kernel: &KernelCore, required: ffi::cl_bitfield, fn_name: &'static str)
+        -> OclResult<()>
+{
+    let program = match core::get_kernel_info(kernel, KernelInfo::Program) {
+        KernelInfoResult::Program(p) => p,
+        KernelInfoResult::Error(err) => return Err(*err),
+        _ => panic!("{}: Unexpected 'KernelInfoResult' variant.", fn_name),
+    };
+
+    let devices = match core::get_program_info(&program, core::ProgramInfo::Devices) {
+        core::ProgramInfoResult::Devices(d) => d,
+        core::ProgramInfoResult::Error(err) => return Err(*err),
+        _ => panic!("{}: Unexpected 'ProgramInfoResult' variant.", fn_name),
+    };
+
+    for device in devices.into_iter().map(Device::from) {
+        let mut caps: ffi::cl_bitfield = 0;
+
+        // Devices predating OpenCL 2.0 do not recognize the query:
+        let errcode = unsafe {
+            ffi::clGetDeviceInfo(device.as_ptr(), CL_DEVICE_SVM_CAPABILITIES,
+                mem::size_of::<ffi::cl_bitfield>(), &mut caps as *mut _ as *mut c_void,
+                ::std::ptr::null_mut())
+        };
+
+        if errcode != ffi::CL_SUCCESS as ffi::cl_int || caps == 0 ||
+                (caps & required) != required
+        {
+            return OclError::err_string(format!("{}: The device '{}' does not support the \
+                required shared virtual memory capabilities (CL_DEVICE_SVM_CAPABILITIES: {:#x}, \
+                required: {:#x}).", fn_name, device.name(), caps, required));
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls `clSetKernelExecInfo` for `kernel`.
+#[cfg(feature = "opencl_version_2_0")]
+unsafe fn set_kernel_exec_info_raw(kernel: &KernelCore, param_name: ffi::cl_uint, size: usize,
+        value: *const c_void, fn_name: &'static str) -> OclResult<()>
+{
+    let errcode = ffi::clSetKernelExecInfo(kernel.as_ptr(), param_name, size, value);
+
+    if errcode == ffi::CL_SUCCESS as ffi::cl_int {
+        Ok(())
+    } else {
+        OclError::err_string(format!("{}: 'clSetKernelExecInfo' failed with status: {:?} ({})",
+            fn_name, Status::from_i32(errcode), errcode))
+    }
+}
+
+
+/// A value which can be passed positionally as a kernel argument.
+///
+/// Implemented for buffer and image references and for scalar primitives
+/// (`i8` through `f64`, by value or by reference). Used by `Kernel::arg`,
+/// `Kernel::set_arg`, and the `launch!` macro. Vector argument types must be
+/// added using `Kernel::arg_vec`.
+pub trait LaunchArg {
+    /// Adds this value as the next argument of `kernel` (builder-style).
+    fn add_to(self, kernel: Kernel) -> Kernel;
+
+    /// Sets the argument of `kernel` at `arg_idx` to this value.
+    fn set_at(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()>;
+}
+
+impl<'a, T: OclPrm + 'static> LaunchArg for &'a Buffer<T> {
+    fn add_to(self, kernel: Kernel) -> Kernel {
+        kernel.arg_buf(self)
+    }
+
+    fn set_at(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel._set_arg::<T>(arg_idx, KernelArg::Mem(self.as_mem()))
+    }
+}
+
+impl<'a, T: OclPrm + 'static> LaunchArg for &'a Image<T> {
+    fn add_to(self, kernel: Kernel) -> Kernel {
+        kernel.arg_img(self)
+    }
+
+    fn set_at(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+        kernel._set_arg::<T>(arg_idx, KernelArg::Mem(self.as_mem()))
+    }
+}
+
+/// Sets a default argument value (see `Kernel::set_default_arg`).
+type ApplyDefaultArg = Arc<Fn(&KernelCore) -> OclResult<()> + Send + Sync>;
+
+/// Whether a default argument is currently set, overridden for the next
+/// launch, or was overridden for the previous launch and must be re-applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DefaultArgState {
+    Applied,
+    Overridden,
+    Stale,
+}
+
+/// A default argument value (see `Kernel::set_default_arg`).
+#[derive(Clone)]
+struct DefaultArg {
+    apply: ApplyDefaultArg,
+    state: DefaultArgState,
+}
+
+impl std::fmt::Debug for DefaultArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DefaultArg")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Re-applies each default argument overridden for the previous launch.
+fn apply_default_args(kernel: &KernelCore, default_args: &Mutex<HashMap<u32, DefaultArg>>)
+        -> OclResult<()>
+{
+    for default_arg in default_args.lock().unwrap().values_mut() {
+        match default_arg.state {
+            DefaultArgState::Overridden => default_arg.state = DefaultArgState::Stale,
+            DefaultArgState::Stale => {
+                try!((default_arg.apply)(kernel));
+                default_arg.state = DefaultArgState::Applied;
+            },
+            DefaultArgState::Applied => (),
+        }
+    }
+
+    Ok(())
+}
+
+
+macro_rules! impl_launch_arg_scl {
+    ($($ty:ty),+) => ($(
+        impl LaunchArg for $ty {
+            fn add_to(self, kernel: Kernel) -> Kernel {
+                kernel.arg_scl(self)
+            }
+
+            fn set_at(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+                kernel._set_arg::<$ty>(arg_idx, KernelArg::Scalar(self))
+            }
+        }
+
+        impl<'a> LaunchArg for &'a $ty {
+            fn add_to(self, kernel: Kernel) -> Kernel {
+                kernel.arg_scl(*self)
+            }
+
+            fn set_at(self, kernel: &mut Kernel, arg_idx: u32) -> OclResult<()> {
+                kernel._set_arg::<$ty>(arg_idx, KernelArg::Scalar(*self))
+            }
+        }
+    )+)
+}
+
+impl_launch_arg_scl!(i8, u8, i16, u16, i32, u32, i64, u64, f32, f64);
+
+
+/// A set of values (usually the fields of a parameter struct) which are
+/// bound to a sequence of kernel arguments.
+///
+/// Implement using `#[derive(KernelArgs)]` (from the `ocl-derive` crate),
+/// which binds each field, in declaration order, using `LaunchArg`. Fields
+/// marked with `#[kernel_arg(skip)]` are ignored.
+pub trait KernelArgs {
+    /// Sets consecutive arguments of `kernel`, starting at `start_idx`, and
+    /// returns the index following the last argument set.
+    fn bind(&self, kernel: &mut Kernel, start_idx: u32) -> OclResult<u32>;
+}
+
+
 /// A kernel which represents a 'procedure'.
 ///
 /// Corresponds to code which must have already been compiled into a program.
@@ -181,8 +754,13 @@ pub struct Kernel {
     gwo: SpatialDims,
     gws: SpatialDims,
     lws: SpatialDims,
+    work_dims: Option<u32>,
+    reqd_wg_size: [usize; 3],
     num_args: u32,
     arg_types: Vec<ArgType>,
+    debug_args: Option<Vec<DebugArg>>,
+    default_args: Arc<Mutex<HashMap<u32, DefaultArg>>>,
+    has_default_args: Arc<AtomicBool>,
 }
 
 
@@ -205,6 +783,7 @@ impl Kernel {
         }
 
         let mem_args = vec![None; num_args as usize];
+        let reqd_wg_size = try!(query_required_wg_size(&obj_core));
 
         Ok(Kernel {
             obj_core: obj_core,
@@ -216,11 +795,38 @@ impl Kernel {
             gwo: SpatialDims::Unspecified,
             gws: SpatialDims::Unspecified,
             lws: SpatialDims::Unspecified,
+            work_dims: None,
+            reqd_wg_size: reqd_wg_size,
             num_args: num_args,
             arg_types: arg_types,
+            debug_args: if cfg!(debug_assertions) {
+                Some(vec![DebugArg::Unset; num_args as usize])
+            } else {
+                None
+            },
+            default_args: Arc::new(Mutex::new(HashMap::new())),
+            has_default_args: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Enables or disables the recording of argument values as they are set
+    /// (builder-style).
+    ///
+    /// Tracking is enabled by default in debug builds. Recorded values can be
+    /// retrieved with `::debug_args`. Disabling tracking clears any
+    /// previously recorded values.
+    ///
+    /// Only arguments set after tracking is enabled will be recorded. Call
+    /// this before adding arguments to record everything.
+    pub fn track_args(mut self, enable: bool) -> Kernel {
+        self.debug_args = if enable {
+            self.debug_args.take().or_else(|| Some(vec![DebugArg::Unset; self.num_args as usize]))
+        } else {
+            None
+        };
+        self
+    }
+
     /// Sets the default queue to be used by all subsequent enqueue commands
     /// unless otherwise changed (with `::set_default_queue`) or overridden
     /// (by `::cmd().queue(...)...`).
@@ -259,6 +865,26 @@ impl Kernel {
         self
     }
 
+    /// Sets the number of work dimensions (1, 2, or 3) this kernel requires
+    /// (builder-style).
+    ///
+    /// When set, enqueuing with a global work size (or a specified global
+    /// work offset or local work size) having a different number of
+    /// dimensions causes an error rather than silently producing unexpected
+    /// `get_global_id` indexing, e.g. a 2D kernel launched with a 1D global
+    /// work size.
+    ///
+    /// ### Panics
+    ///
+    /// `work_dims` must be 1, 2, or 3.
+    ///
+    pub fn work_dims(mut self, work_dims: u32) -> Kernel {
+        assert!(work_dims >= 1 && work_dims <= 3, "ocl::Kernel::work_dims: Invalid work \
+            dimension count ({}). Must be 1, 2, or 3.", work_dims);
+        self.work_dims = Some(work_dims);
+        self
+    }
+
     /// Adds a new argument to the kernel specifying the buffer object represented
     /// by 'buffer' (builder-style). Argument is added to the bottom of the argument
     /// order.
@@ -306,6 +932,104 @@ impl Kernel {
         self
     }
 
+    /// Adds a new argument (a buffer, image, or scalar) to the bottom of the
+    /// argument order (builder-style).
+    ///
+    /// See `LaunchArg` for the list of supported types.
+    pub fn arg<A: LaunchArg>(self, arg: A) -> Kernel {
+        arg.add_to(self)
+    }
+
+    /// Sets the argument at `arg_idx` to `arg` (a buffer, image, or scalar).
+    ///
+    /// Unlike the `::arg...` functions, arguments may be set in any order
+    /// and set again at any time.
+    pub fn set_arg<'a, A: LaunchArg>(&'a mut self, arg_idx: u32, arg: A)
+            -> OclResult<&'a mut Kernel>
+    {
+        arg.set_at(self, arg_idx).and(Ok(self))
+    }
+
+    /// Sets the scalar argument at `arg_idx` to `value` and records `value`
+    /// as its default.
+    ///
+    /// Setting the argument again (with `::set_arg` or any of the other
+    /// `::set_arg...` methods) overrides the default for the next launch
+    /// only: the default is re-applied automatically when the launch after
+    /// that is enqueued (by `KernelCmd::enq`) unless it is overridden again.
+    /// Setting a new default replaces the previous one. An error while
+    /// re-applying a default is returned by that launch's `::enq`.
+    ///
+    /// [UNSTABLE]: This method is new and may be changed or removed.
+    pub fn set_default_arg<'a, T>(&'a mut self, arg_idx: u32, value: T)
+            -> OclResult<&'a mut Kernel>
+            where T: OclPrm + 'static
+    {
+        try!(self._set_arg::<T>(arg_idx, KernelArg::Scalar(value)));
+
+        let apply: ApplyDefaultArg = Arc::new(move |obj_core: &KernelCore| {
+            core::set_kernel_arg::<T>(obj_core, arg_idx, KernelArg::Scalar(value))
+        });
+
+        self.default_args.lock().unwrap().insert(arg_idx, DefaultArg {
+            apply: apply,
+            state: DefaultArgState::Applied,
+        });
+        self.has_default_args.store(true, Ordering::SeqCst);
+        Ok(self)
+    }
+
+    /// Sets the `int` argument at `arg_idx` to `1` or `0` according to
+    /// `value`.
+    ///
+    /// OpenCL does not allow `bool` kernel arguments. By convention a flag is
+    /// passed as an `int` (a `cl_int`, four bytes), whereas a Rust `bool` is a
+    /// single byte.
+    pub fn set_arg_bool<'a>(&'a mut self, arg_idx: u32, value: bool)
+            -> OclResult<&'a mut Kernel>
+    {
+        self.set_arg(arg_idx, value as i32)
+    }
+
+    /// Sets the `int` argument at `arg_idx` to the integer value of `value`
+    /// (such as a C-like enum mirroring one declared in the kernel source).
+    pub fn set_arg_enum<'a, E: Into<i32>>(&'a mut self, arg_idx: u32, value: E)
+            -> OclResult<&'a mut Kernel>
+    {
+        self.set_arg(arg_idx, value.into())
+    }
+
+    /// Sets each buffer in `buffers` to consecutive arguments, beginning at
+    /// `start_idx`.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error, without setting any argument, if any of the
+    /// resulting indices exceeds this kernel's argument count or if any of
+    /// the arguments is not a buffer of type `T`.
+    ///
+    pub fn set_arg_buffers<'a, 'b, T>(&'a mut self, start_idx: u32, buffers: &[&'b Buffer<T>])
+            -> OclResult<&'a mut Kernel>
+            where T: OclPrm + 'static
+    {
+        if start_idx as usize + buffers.len() > self.num_args as usize {
+            return OclError::err_string(format!("ocl::Kernel::set_arg_buffers: Setting {} \
+                buffers beginning at index {} exceeds the argument count ({}) of kernel '{}'.",
+                buffers.len(), start_idx, self.num_args, self.name()));
+        }
+
+        // Check every argument type before setting any argument:
+        for idx in start_idx..(start_idx + buffers.len() as u32) {
+            try!(self.verify_arg_type::<T>(idx));
+        }
+
+        for (idx, buffer) in (start_idx..).zip(buffers.iter()) {
+            try!(self._set_arg::<T>(idx, KernelArg::Mem(buffer.as_mem())));
+        }
+
+        Ok(self)
+    }
+
     /// Adds a new argument specifying the allocation of a local variable of size
     /// `length * sizeof(T)` bytes (builder_style).
     ///
@@ -455,11 +1179,78 @@ impl Kernel {
         unimplemented!();
     }
 
+    /// Declares the SVM (shared virtual memory) pointers which this kernel
+    /// may access indirectly (`CL_KERNEL_EXEC_INFO_SVM_PTRS`), such as
+    /// pointers stored within other SVM allocations rather than passed as
+    /// arguments.
+    ///
+    /// Replaces any previously declared pointers. Must be called before the
+    /// kernel is enqueued.
+    ///
+    /// ### Errors
+    ///
+    /// Every device associated with the kernel's program must support SVM.
+    ///
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn set_exec_info_svm_ptrs(&mut self, ptrs: &[*const c_void])
+            -> OclResult<&mut Kernel>
+    {
+        try!(check_svm_support(&self.obj_core, 0, "Kernel::set_exec_info_svm_ptrs"));
+
+        unsafe {
+            try!(set_kernel_exec_info_raw(&self.obj_core, CL_KERNEL_EXEC_INFO_SVM_PTRS,
+                ptrs.len() * mem::size_of::<*const c_void>(), ptrs.as_ptr() as *const c_void,
+                "Kernel::set_exec_info_svm_ptrs"));
+        }
+
+        Ok(self)
+    }
+
+    /// Specifies whether this kernel may access host memory allocated by
+    /// the system allocator (such as `malloc`) through fine-grained system
+    /// SVM (`CL_KERNEL_EXEC_INFO_SVM_FINE_GRAIN_SYSTEM`).
+    ///
+    /// ### Errors
+    ///
+    /// Enabling requires that every device associated with the kernel's
+    /// program supports fine-grained system SVM
+    /// (`CL_DEVICE_SVM_FINE_GRAIN_SYSTEM`).
+    ///
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn set_exec_info_fine_grain_system(&mut self, enable: bool)
+            -> OclResult<&mut Kernel>
+    {
+        let required = if enable { CL_DEVICE_SVM_FINE_GRAIN_SYSTEM } else { 0 };
+        try!(check_svm_support(&self.obj_core, required,
+            "Kernel::set_exec_info_fine_grain_system"));
+
+        let value = (if enable { ffi::CL_TRUE } else { ffi::CL_FALSE }) as ffi::cl_bool;
+
+        unsafe {
+            try!(set_kernel_exec_info_raw(&self.obj_core,
+                CL_KERNEL_EXEC_INFO_SVM_FINE_GRAIN_SYSTEM, mem::size_of::<ffi::cl_bool>(),
+                &value as *const _ as *const c_void, "Kernel::set_exec_info_fine_grain_system"));
+        }
+
+        Ok(self)
+    }
+
     /// Returns a command builder which is used to chain parameters of an
     /// 'enqueue' command together.
     pub fn cmd(&self) -> KernelCmd {
-        KernelCmd { queue: self.queue.as_ref().map(|q| q.as_ref()), kernel: &self.obj_core,
-            gwo: self.gwo, gws: self.gws, lws: self.lws,
+        let default_args = if self.has_default_args.load(Ordering::SeqCst) {
+            Some(&*self.default_args)
+        } else {
+            None
+        };
+
+        KernelCmd { queue: self.queue.as_ref().map(|q| q.as_ref()),
+            default_queue: self.queue.as_ref().map(|q| q.as_ref()),
+            profiling_queue: self.queue.as_ref().and_then(|q| q.profiled()).map(|q| q.core()),
+            profiled: false,
+            kernel: &self.obj_core,
+            gwo: self.gwo, gws: self.gws, lws: self.lws, work_dims: self.work_dims,
+            reqd_wg_size: self.reqd_wg_size, default_args: default_args,
             wait_list: None, new_event: None }
     }
 
@@ -480,6 +1271,57 @@ impl Kernel {
         self.cmd().enq()
     }
 
+    /// Enqueues this kernel on the default queue with a global work size
+    /// derived from `data` (see `Dimensioned`), i.e. one work item per
+    /// element of a `Vec`.
+    ///
+    /// Shorthand for `.cmd().gws(data.dims()).enq()`. Any default global
+    /// work size is ignored for this call only.
+    ///
+    /// See `::enq` regarding safety.
+    ///
+    pub fn enq_for<D: Dimensioned + ?Sized>(&self, data: &D) -> OclResult<()> {
+        self.cmd().gws(data.dims()).enq()
+    }
+
+    /// Enqueues this kernel on the profiling-enabled queue associated with
+    /// the default queue (see `KernelCmd::profiled`), blocks until it
+    /// completes, and returns its event along with its profiling times.
+    ///
+    /// Intended for micro-benchmarks.
+    ///
+    /// ### Errors
+    ///
+    /// The default queue must be associated with `device` and must either
+    /// have profiling enabled or have a profiling sibling (see
+    /// `Queue::enable_profiling_sibling`).
+    ///
+    pub fn enq_timed(&self, device: &Device) -> OclResult<(Event, ProfilingNanos)> {
+        let queue = match self.queue {
+            Some(ref q) => q,
+            None => return Err("Kernel::enq_timed: No default queue set.".into()),
+        };
+
+        if queue.device() != *device {
+            return OclError::err_string(format!("Kernel::enq_timed: The default queue is not \
+                associated with the device '{}'.", device.name()));
+        }
+
+        let profiling_queue = match queue.profiled() {
+            Some(q) => q,
+            None => return Err("Kernel::enq_timed: The default queue does not have profiling \
+                enabled or a profiling sibling. Use 'Queue::enable_profiling_sibling' to \
+                create one.".into()),
+        };
+
+        let mut event = Event::empty();
+        try!(self.cmd().queue(profiling_queue).enew(&mut event).enq());
+        try!(event.wait_for());
+
+        let nanos = try!(ProfilingNanos::from_event(&event));
+        Ok((event, nanos))
+    }
+
     /// Changes the default queue.
     ///
     /// Returns a ref for chaining i.e.:
@@ -521,6 +1363,12 @@ impl Kernel {
         self.lws
     }
 
+    /// Returns the required number of work dimensions, if set (see
+    /// `Kernel::work_dims`).
+    pub fn get_work_dims(&self) -> Option<u32> {
+        self.work_dims
+    }
+
     /// Returns the number of arguments specified for this kernel.
     #[inline]
     pub fn new_arg_count(&self) -> u32 {
@@ -558,6 +1406,91 @@ impl Kernel {
         core::get_kernel_work_group_info(&self.obj_core, device, info_kind)
     }
 
+    /// Returns a reasonable local work size for a launch of this kernel (on
+    /// its default queue's device) with a global work size of `gws`.
+    ///
+    /// The first dimension is set to the largest multiple of the kernel's
+    /// preferred work group size multiple which evenly divides the global
+    /// size of that dimension without exceeding the maximum work group size.
+    /// Any remaining dimensions are set to the largest divisor of their
+    /// global size which fits within what remains of the maximum.
+    ///
+    /// Returns `SpatialDims::Unspecified` (letting the driver choose) if no
+    /// such size greater than one exists (such as when the first dimension
+    /// of `gws` is prime, regardless of the preferred multiple), if this
+    /// kernel has no default queue, or if the work group info queries fail.
+    pub fn auto_lws<D: Into<SpatialDims>>(&self, gws: D) -> SpatialDims {
+        let gws = gws.into();
+
+        let device = match self.queue {
+            Some(ref q) => q.device(),
+            None => return SpatialDims::Unspecified,
+        };
+
+        let max_size = match self.wg_info(device, KernelWorkGroupInfo::WorkGroupSize) {
+            KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+            _ => return SpatialDims::Unspecified,
+        };
+
+        let multiple = match self.wg_info(device,
+                KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple) {
+            KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(s) => s,
+            _ => return SpatialDims::Unspecified,
+        };
+
+        auto_lws(gws, max_size, multiple)
+    }
+
+    /// Returns the maximum subgroup size for this kernel when enqueued with
+    /// a local work size of `local_size` on `device`.
+    ///
+    /// Requires the `cl_khr_subgroups` extension (or an OpenCL 2.1 device
+    /// supporting subgroups and the `opencl_version_2_1` feature).
+    pub fn subgroup_size_for_ndrange<D>(&self, device: &Device, local_size: D)
+            -> OclResult<usize>
+            where D: Into<SpatialDims>
+    {
+        self.subgroup_info(device, CL_KERNEL_MAX_SUB_GROUP_SIZE_FOR_NDRANGE, local_size.into())
+    }
+
+    /// Returns the number of subgroups into which each work group of this
+    /// kernel will be partitioned when enqueued with a local work size of
+    /// `local_size` on `device`.
+    ///
+    /// Requires the `cl_khr_subgroups` extension (or an OpenCL 2.1 device
+    /// supporting subgroups and the `opencl_version_2_1` feature).
+    pub fn subgroup_count_for_ndrange<D>(&self, device: &Device, local_size: D)
+            -> OclResult<usize>
+            where D: Into<SpatialDims>
+    {
+        self.subgroup_info(device, CL_KERNEL_SUB_GROUP_COUNT_FOR_NDRANGE, local_size.into())
+    }
+
+    /// Queries subgroup information using `clGetKernelSubGroupInfo[KHR]`
+    /// (loaded at runtime).
+    fn subgroup_info(&self, device: &Device, param_name: ffi::cl_uint, local_size: SpatialDims)
+            -> OclResult<usize>
+    {
+        let get_sub_group_info = try!(load_get_kernel_sub_group_info(device));
+        let dim_count = local_size.dim_count() as usize;
+        let lens = try!(local_size.to_lens());
+        let mut result: usize = 0;
+
+        let errcode = unsafe {
+            get_sub_group_info(self.obj_core.as_ptr(), device.as_ptr(), param_name,
+                dim_count * mem::size_of::<usize>(), lens.as_ptr() as *const c_void,
+                mem::size_of::<usize>(), &mut result as *mut usize as *mut c_void,
+                std::ptr::null_mut())
+        };
+
+        if errcode == 0 {
+            Ok(result)
+        } else {
+            OclError::err_string(format!("ocl::Kernel::subgroup_info: 'clGetKernelSubGroupInfo' \
+                failed with status: {:?} ({}).", Status::from_i32(errcode), errcode))
+        }
+    }
+
     /// Returns the name of this kernel.
     pub fn name(&self) -> String {
         core::get_kernel_info(&self.obj_core, KernelInfo::FunctionName).into()
@@ -572,6 +1505,84 @@ impl Kernel {
         }
     }
 
+    /// Returns a record of the value each argument was last set to, in
+    /// argument order.
+    ///
+    /// Returns an empty list if argument tracking is disabled (see
+    /// `::track_args`).
+    pub fn debug_args(&self) -> Vec<DebugArg> {
+        self.debug_args.clone().unwrap_or_else(Vec::new)
+    }
+
+    /// Returns a new, independent, kernel with its own argument state and a
+    /// copy of this kernel's arguments, queue, and work sizes.
+    ///
+    /// Unlike `::clone`, which shares the underlying `cl_kernel` (and
+    /// therefore its argument state), setting an argument on the duplicate
+    /// does not affect the original. Use this to create per-thread copies of
+    /// a shared template kernel.
+    ///
+    /// When the `opencl_version_2_1` feature is enabled, `clCloneKernel` is
+    /// used and every argument is copied. Otherwise the kernel is recreated
+    /// from its program and function name: buffer and image arguments are
+    /// copied along with any scalar, vector, and local arguments recorded
+    /// while argument tracking was enabled (see `::track_args`). Any other
+    /// arguments must be set again before the duplicate is enqueued.
+    ///
+    pub fn duplicate(&self) -> OclResult<Kernel> {
+        let (obj_core, args_copied) = try!(clone_kernel_core(&self.obj_core));
+        let mem_args = self.mem_args.lock().unwrap().clone();
+
+        if !args_copied {
+            for (arg_idx, mem) in mem_args.iter().enumerate() {
+                if let Some(ref mem) = *mem {
+                    try!(core::set_kernel_arg::<u8>(&obj_core, arg_idx as u32,
+                        KernelArg::Mem(mem)));
+                }
+            }
+
+            if let Some(ref debug_args) = self.debug_args {
+                for (arg_idx, debug_arg) in debug_args.iter().enumerate() {
+                    let arg_idx = arg_idx as u32;
+
+                    unsafe {
+                        match *debug_arg {
+                            DebugArg::Scalar { ref bytes, .. } |
+                                    DebugArg::Vector { ref bytes, .. } => {
+                                try!(set_kernel_arg_raw(&obj_core, arg_idx, bytes.len(),
+                                    bytes.as_ptr() as *const c_void));
+                            },
+                            DebugArg::Local { len } => {
+                                try!(set_kernel_arg_raw(&obj_core, arg_idx, len,
+                                    std::ptr::null()));
+                            },
+                            _ => (),
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Kernel {
+            obj_core: obj_core,
+            named_args: self.named_args.clone(),
+            new_arg_count: self.new_arg_count,
+            mem_args: Arc::new(Mutex::new(mem_args)),
+            queue: self.queue.clone(),
+            gwo: self.gwo,
+            gws: self.gws,
+            lws: self.lws,
+            work_dims: self.work_dims,
+            reqd_wg_size: self.reqd_wg_size,
+            num_args: self.num_args,
+            arg_types: self.arg_types.clone(),
+            debug_args: self.debug_args.clone(),
+            default_args: Arc::new(Mutex::new(self.default_args.lock().unwrap().clone())),
+            has_default_args: Arc::new(AtomicBool::new(
+                self.has_default_args.load(Ordering::SeqCst))),
+        })
+    }
+
     /// Returns the argument index of a named argument if it exists.
     pub fn named_arg_idx(&self, name: &'static str) -> Option<u32> {
         self.resolve_named_arg_idx(name).ok()
@@ -611,6 +1622,10 @@ impl Kernel {
     fn _set_arg<T: OclPrm + 'static>(&mut self, arg_idx: u32, arg: KernelArg<T>) -> OclResult<()> {
         self.verify_arg_type::<T>(arg_idx)?;
 
+        if let Some(ref mut debug_args) = self.debug_args {
+            debug_args[arg_idx as usize] = DebugArg::new(&arg, &self.arg_types[arg_idx as usize]);
+        }
+
         // If the `KernelArg` is a `Mem` variant, clone the `MemCore` it
         // refers to, store it in `self.mem_args`, and create a new
         // `KernelArg::Mem` referring to the locally stored copy. This prevents
@@ -627,7 +1642,14 @@ impl Kernel {
             arg => arg,
         };
 
-        core::set_kernel_arg::<T>(&self.obj_core, arg_idx, arg)
+        try!(core::set_kernel_arg::<T>(&self.obj_core, arg_idx, arg));
+
+        if self.has_default_args.load(Ordering::SeqCst) {
+            if let Some(default_arg) = self.default_args.lock().unwrap().get_mut(&arg_idx) {
+                default_arg.state = DefaultArgState::Overridden;
+            }
+        }
+        Ok(())
     }
 
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -809,8 +1831,13 @@ impl Clone for Kernel {
             gwo: self.gwo.clone(),
             gws: self.gws.clone(),
             lws: self.lws.clone(),
+            work_dims: self.work_dims,
+            reqd_wg_size: self.reqd_wg_size,
             num_args: self.num_args.clone(),
             arg_types: self.arg_types.clone(),
+            debug_args: self.debug_args.clone(),
+            default_args: self.default_args.clone(),
+            has_default_args: self.has_default_args.clone(),
         }
     }
 }
@@ -14,25 +14,61 @@ mod sampler;
 mod pro_que;
 mod event;
 mod spatial_dims;
+mod command_buffer;
+mod command_buffer_khr;
+mod stream_pipeline;
+mod constant_buffer;
+mod system_info;
+mod ping_pong;
+mod pinned_buffer;
+mod reductions;
+mod sync_kernel;
+mod pipeline;
+mod counting_queue;
+mod texture;
+#[cfg(feature = "opencl_version_2_0")]
+mod svm;
 
 pub use self::platform::Platform;
-pub use self::device::{Device, DeviceSpecifier};
+pub use self::device::{Device, DeviceSpecifier, PciBusInfo, ImageLimits, MemoryUsage};
 pub use self::context::{Context, ContextBuilder};
-pub use self::program::{Program, ProgramBuilder, BuildOpt};
-pub use self::queue::Queue;
-pub use self::kernel::{Kernel, KernelCmd};
+pub use self::program::{Program, ProgramBuilder, BuildOpt, DefineValue, ProgramBuildError,
+    BuildDiagnostic, BuildDiagnosticSeverity};
+pub use self::queue::{Queue, QueueBuilder, Priority, Throttle};
+pub use self::kernel::{Kernel, KernelCmd, DebugArg, LaunchArg, KernelArgs};
 pub use self::buffer::{BufferCmdKind, BufferCmdDataShape, BufferCmd, Buffer, QueCtx,
     BufferBuilder, BufferReadCmd, BufferWriteCmd, BufferMapCmd,};
-pub use self::image::{Image, ImageCmd, ImageCmdKind, ImageBuilder};
+pub use self::image::{Image, ImageCmd, ImageCmdKind, ImageBuilder, ImageComponent};
 pub use self::sampler::Sampler;
 pub use self::pro_que::{ProQue, ProQueBuilder};
-pub use self::event::{Event, EventArray, EventList, IntoMarker, RawEventArray, IntoRawEventArray};
+pub use self::event::{Event, EventArray, EventList, IntoMarker, RawEventArray, IntoRawEventArray,
+    ProfilingSummary, ProfilingNanos, JoinEvents, join_events, EventStatus,
+    WaitStrategy};
 pub use self::spatial_dims::SpatialDims;
+pub use self::command_buffer::CommandBuffer;
+pub use self::command_buffer_khr::CommandBufferKhr;
+pub use self::stream_pipeline::{StreamPipeline, Tile};
+pub use self::constant_buffer::ConstantBuffer;
+pub use self::ping_pong::PingPong;
+pub use self::pinned_buffer::PinnedBuffer;
+pub use self::reductions::ReducePrm;
+pub use self::sync_kernel::SyncKernel;
+pub use self::pipeline::Pipeline;
+pub use self::counting_queue::{CountingQueue, EnqueueOn};
+pub use self::texture::Texture;
+#[cfg(feature = "opencl_version_2_0")]
+pub use self::svm::{SvmBuffer, SvmCmd, SvmCmdKind};
+pub use self::system_info::{describe_system, SystemInfo, PlatformDesc, DeviceDesc};
 #[cfg(not(feature = "async_block"))]
 pub use self::cb::{_unpark_task, box_raw_void};
-pub use self::traits::{MemLen, WorkDims};
+#[cfg(all(feature = "std_future", not(feature = "async_block")))]
+pub use self::cb::{_wake_task, pending_wakers, WakerSlot};
+pub use self::traits::{MemLen, WorkDims, Dimensioned};
 pub use self::types::{ClNullEventPtrEnum, ClWaitListPtrEnum};
 
+use core::{util, OclPrm, MemFlags};
+use core::error::{Error as OclError, Result as OclResult};
+
 //=============================================================================
 //================================ CONSTANTS ==================================
 //=============================================================================
@@ -43,6 +79,140 @@ pub use self::types::{ClNullEventPtrEnum, ClWaitListPtrEnum};
 //================================ FUNCTIONS ==================================
 //=============================================================================
 
+/// Returns `info` with any trailing null bytes and whitespace removed.
+///
+/// Some drivers include the null terminator (and occasionally padding) in
+/// the strings returned by info queries. Anything following the first null
+/// byte is discarded.
+pub fn clean_info_string<S: Into<String>>(info: S) -> String {
+    let mut info = info.into();
+    if let Some(nul_idx) = info.find('\0') {
+        info.truncate(nul_idx);
+    }
+    let trimmed_len = info.trim_right().len();
+    info.truncate(trimmed_len);
+    info
+}
+
+/// Returns `flags` with any device access flag (`MEM_READ_WRITE`,
+/// `MEM_READ_ONLY`, or `MEM_WRITE_ONLY`) replaced by `access`.
+pub fn set_access_flags(flags: MemFlags, access: MemFlags) -> MemFlags {
+    let mut flags = flags;
+    flags.remove(::flags::MEM_READ_WRITE | ::flags::MEM_READ_ONLY | ::flags::MEM_WRITE_ONLY);
+    flags | access
+}
+
+/// Returns a global work size, rounded up in each dimension to a multiple of
+/// `local`, along with `local` itself: `(global, local)`.
+///
+/// Pass both to a kernel command (`.gws(global).lws(local)`) and guard the
+/// surplus work items within the kernel, i.e.:
+/// `if (get_global_id(0) >= n) return;`.
+///
+/// ### Errors
+///
+/// `work_items` and `local` must have the same (non-zero) number of
+/// dimensions and no local size may be zero.
+///
+pub fn nd_range<W, L>(work_items: W, local: L) -> OclResult<(SpatialDims, SpatialDims)>
+        where W: Into<SpatialDims>, L: Into<SpatialDims>
+{
+    let work_items = work_items.into();
+    let local = local.into();
+
+    if work_items.dim_count() != local.dim_count() {
+        return OclError::err_string(format!("ocl::nd_range: The number of work item \
+            dimensions ({}) does not match the number of local dimensions ({}).",
+            work_items.dim_count(), local.dim_count()));
+    }
+
+    let items = try!(work_items.to_lens());
+    let lens = try!(local.to_lens());
+
+    if lens.iter().any(|&l| l == 0) {
+        return OclError::err_string(format!("ocl::nd_range: Local sizes must be non-zero: {:?}.",
+            local));
+    }
+
+    let global = match work_items {
+        SpatialDims::One(..) => SpatialDims::One(util::padded_len(items[0], lens[0])),
+        SpatialDims::Two(..) => SpatialDims::Two(util::padded_len(items[0], lens[0]),
+            util::padded_len(items[1], lens[1])),
+        _ => SpatialDims::Three(util::padded_len(items[0], lens[0]),
+            util::padded_len(items[1], lens[1]), util::padded_len(items[2], lens[2])),
+    };
+
+    Ok((global, local))
+}
+
+/// Returns an error if a memory object of `size_bytes` bytes exceeds the
+/// address space of either a device with `address_bits` bits
+/// (`CL_DEVICE_ADDRESS_BITS`) or the host.
+///
+/// Sizes are otherwise silently truncated when host and device pointer
+/// widths differ (for example a 32-bit driver used with a 64-bit host).
+pub fn verify_address_bits(address_bits: u32, size_bytes: u64) -> OclResult<()> {
+    if address_bits < 64 && size_bytes > (1u64 << address_bits) - 1 {
+        return OclError::err_string(format!("ocl::verify_address_bits: The size of the memory \
+            object ({} bytes) exceeds the address space of the device ({} address bits).",
+            size_bytes, address_bits));
+    }
+
+    if size_bytes > usize::max_value() as u64 {
+        return OclError::err_string(format!("ocl::verify_address_bits: The size of the memory \
+            object ({} bytes) exceeds the address space of the host ({} bit pointers).", size_bytes,
+            ::std::mem::size_of::<usize>() * 8));
+    }
+
+    Ok(())
+}
+
+/// Reads the contents of each buffer in `reads` into its paired `Vec`,
+/// distributing the reads across `queues` in turn, and blocks until all
+/// have completed.
+///
+/// Using more than one queue allows transfers to overlap on devices with
+/// multiple DMA engines. All queues must belong to the context of every
+/// buffer.
+///
+/// ### Errors
+///
+/// At least one queue must be provided and the length of each `Vec` must
+/// equal the length of its buffer. Nothing is read if either check fails.
+///
+pub fn read_all<T: OclPrm>(reads: &mut [(&Buffer<T>, &mut Vec<T>)], queues: &[Queue])
+        -> OclResult<()>
+{
+    if queues.is_empty() {
+        return Err("ocl::read_all: At least one queue must be provided.".into());
+    }
+
+    for (idx, read) in reads.iter().enumerate() {
+        if read.1.len() != read.0.len() {
+            return OclError::err_string(format!("ocl::read_all: The length of destination \
+                [{}] ({}) does not match the length of its buffer ({}).", idx, read.1.len(),
+                read.0.len()));
+        }
+    }
+
+    let mut result = Ok(());
+
+    for (idx, read) in reads.iter_mut().enumerate() {
+        let queue = &queues[idx % queues.len()];
+        let buffer = read.0;
+        let dst: &mut [T] = &mut read.1[..];
+
+        result = unsafe { buffer.read(dst).queue(queue).block(false).enq() }
+            .and_then(|_| queue.flush());
+
+        if result.is_err() { break; }
+    }
+
+    // Every destination must outlive its (non-blocking) read:
+    let finish_result = queues.iter().fold(Ok(()), |res, queue| res.and(queue.finish()));
+    result.and(finish_result)
+}
+
 #[cfg(not(feature = "async_block"))]
 mod cb {
     use libc::c_void;
@@ -50,6 +220,36 @@ mod cb {
     use futures::task::Task;
     use ffi::cl_event;
     use core::{CommandExecutionStatus, Status};
+    #[cfg(feature = "std_future")]
+    use std::collections::HashMap;
+    #[cfg(feature = "std_future")]
+    use std::sync::{Arc, Mutex, Once, ONCE_INIT};
+    #[cfg(feature = "std_future")]
+    use std::task::Waker;
+
+    /// The waker of a task awaiting a pending event, shared between polls of
+    /// the event and its completion callback (see `_wake_task`).
+    #[cfg(feature = "std_future")]
+    pub type WakerSlot = Arc<Mutex<Option<Waker>>>;
+
+    /// Returns the waker slots of every event with a pending `_wake_task`
+    /// callback, keyed by event pointer.
+    ///
+    /// Entries are removed by the callback. An event cannot be released (and
+    /// its pointer reused) before its callbacks have run.
+    #[cfg(feature = "std_future")]
+    pub fn pending_wakers() -> &'static Mutex<HashMap<usize, WakerSlot>> {
+        static INIT: Once = ONCE_INIT;
+        static mut WAKERS: *const Mutex<HashMap<usize, WakerSlot>> =
+            0 as *const Mutex<HashMap<usize, WakerSlot>>;
+
+        unsafe {
+            INIT.call_once(|| {
+                WAKERS = Box::into_raw(Box::new(Mutex::new(HashMap::new())));
+            });
+            &*WAKERS
+        }
+    }
 
     pub fn box_raw_void<T>(item: T) -> *mut c_void {
         let item_box = Box::new(item);
@@ -79,6 +279,27 @@ mod cb {
                 drivers instead (will work with Intel CPUs).\n\n", status, user_data);
         }
     }
+
+    /// Wakes the most recently stored waker for an event (see
+    /// `pending_wakers`) upon the completion or abnormal termination of the
+    /// event. `user_data` is unused.
+    ///
+    /// Unlike `_unpark_task`, an error status does not cause a panic. The
+    /// woken task is expected to check the event status when next polled.
+    #[cfg(feature = "std_future")]
+    pub extern "C" fn _wake_task(event_ptr: cl_event, event_status: i32, user_data: *mut c_void) {
+        let _ = (event_status, user_data);
+
+        let slot = pending_wakers().lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&(event_ptr as usize));
+
+        let waker = slot.and_then(|slot| slot.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take());
+
+        if let Some(waker) = waker { waker.wake(); }
+    }
 }
 
 
@@ -89,11 +310,14 @@ mod cb {
 //=============================================================================
 
 mod types {
-    use std::ptr;
+    use std::{mem, ptr, slice};
     use std::cell::Ref;
+    use libc::c_void;
     use standard::{Event, EventList, RawEventArray, Queue};
-    use core::ffi::cl_event;
-    use core::{Result as OclResult, Event as EventCore, ClNullEventPtr, ClWaitListPtr};
+    use core::ffi::{self, cl_event, cl_command_queue};
+    use core::{Result as OclResult, Event as EventCore, ClNullEventPtr, ClWaitListPtr,
+        CommandQueue as CommandQueueCore};
+    use core::error::Error as OclError;
 
     /// An enum which can represent several different ways of representing a
     /// event wait list.
@@ -182,6 +406,84 @@ mod types {
         }
     }
 
+    impl<'a> ClWaitListPtrEnum<'a> {
+        /// Checks that every event in this wait list belongs to a command
+        /// submitted before the command (to be enqueued on `queue`) waiting on
+        /// it. Does nothing unless the `event_order_debug` feature is enabled.
+        ///
+        /// ### Errors
+        ///
+        /// Returns an error if an event has not yet been enqueued (is empty),
+        /// as it likely belongs to a command enqueued after the waiting one,
+        /// or if an event belongs to a command on another queue which has not
+        /// yet been submitted (its queue has not been flushed).
+        ///
+        pub fn check_order(&self, fn_name: &'static str, queue: &CommandQueueCore)
+                -> OclResult<()>
+        {
+            if !cfg!(feature = "event_order_debug") { return Ok(()); }
+
+            // A single empty event is otherwise silently ignored (counted as
+            // zero events):
+            let unenqueued = match *self {
+                ClWaitListPtrEnum::EventOwned(ref e) => e.is_empty(),
+                ClWaitListPtrEnum::Event(e) => e.is_empty(),
+                ClWaitListPtrEnum::EventCoreOwned(ref e) => e.is_null(),
+                ClWaitListPtrEnum::EventCore(e) => e.is_null(),
+                _ => false,
+            };
+
+            let count = self.count() as usize;
+            let events: &[cl_event] = if unenqueued || count == 0 {
+                &[]
+            } else {
+                unsafe { slice::from_raw_parts(self.as_ptr_ptr(), count) }
+            };
+
+            if unenqueued || events.iter().any(|ev| ev.is_null()) {
+                return OclError::err_string(format!("ocl::{}: A wait list references an event \
+                    which has not yet been enqueued (an empty event). The event is probably \
+                    associated with a command enqueued later than the command waiting on it, \
+                    which would form a dependency cycle.", fn_name));
+            }
+
+            for &event in events {
+                let mut event_queue: cl_command_queue = ptr::null_mut();
+                let mut status: i32 = 0;
+
+                let errcode = unsafe {
+                    ffi::clGetEventInfo(event, ffi::CL_EVENT_COMMAND_QUEUE,
+                        mem::size_of::<cl_command_queue>(),
+                        &mut event_queue as *mut cl_command_queue as *mut c_void,
+                        ptr::null_mut())
+                };
+
+                let errcode = if errcode != 0 { errcode } else { unsafe {
+                    ffi::clGetEventInfo(event, ffi::CL_EVENT_COMMAND_EXECUTION_STATUS,
+                        mem::size_of::<i32>(), &mut status as *mut i32 as *mut c_void,
+                        ptr::null_mut())
+                } };
+
+                if errcode != 0 {
+                    return OclError::err_string(format!("ocl::{}: Unable to retrieve the \
+                        queue or status of a wait list event (errcode: {}).", fn_name, errcode));
+                }
+
+                // User events have no queue:
+                if !event_queue.is_null() && event_queue != queue.as_ptr()
+                        && status == ffi::CL_QUEUED as i32
+                {
+                    return OclError::err_string(format!("ocl::{}: A wait list references an \
+                        event whose command, on another queue, has not yet been submitted to \
+                        its device. Flush that queue before enqueuing commands which wait on \
+                        its events.", fn_name));
+                }
+            }
+
+            Ok(())
+        }
+    }
+
     impl<'a> From<&'a RawEventArray> for ClWaitListPtrEnum<'a> {
         fn from(e: &'a RawEventArray) -> ClWaitListPtrEnum<'a> {
             ClWaitListPtrEnum::RawEventArray(e)
@@ -340,6 +642,8 @@ mod traits {
     use num::{Num, ToPrimitive};
     use ::SpatialDims;
     use super::spatial_dims::to_usize;
+    #[cfg(feature = "ndarray")]
+    use ndarray::Array2;
 
     /// Types which have properties describing the amount of work to be done
     /// in multiple dimensions.
@@ -363,6 +667,40 @@ mod traits {
     }
 
 
+    /// Types from which the dimensions of a kernel launch (the global work
+    /// size) can be derived, such as the data being processed.
+    ///
+    /// Used by `Kernel::enq_for`. Slices and vectors are one dimensional
+    /// (their length). Two dimensional arrays (`ndarray::Array2`, with the
+    /// `ndarray` feature) are `(columns, rows)`: the first (fastest varying)
+    /// dimension indexes within a row.
+    ///
+    pub trait Dimensioned {
+        /// Returns the dimensions of this value.
+        fn dims(&self) -> SpatialDims;
+    }
+
+    impl<'a, D> Dimensioned for &'a D where D: Dimensioned + ?Sized {
+        fn dims(&self) -> SpatialDims { (*self).dims() }
+    }
+
+    impl<T> Dimensioned for [T] {
+        fn dims(&self) -> SpatialDims { SpatialDims::One(self.len()) }
+    }
+
+    impl<T> Dimensioned for Vec<T> {
+        fn dims(&self) -> SpatialDims { SpatialDims::One(self.len()) }
+    }
+
+    #[cfg(feature = "ndarray")]
+    impl<T> Dimensioned for Array2<T> {
+        fn dims(&self) -> SpatialDims {
+            let (rows, cols) = self.dim();
+            SpatialDims::Two(cols, rows)
+        }
+    }
+
+
     /// Types which have properties allowing them to be used to define the size
     /// of a volume of memory.
     ///
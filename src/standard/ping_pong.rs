@@ -0,0 +1,63 @@
+//! A pair of buffers used in alternation.
+
+use core::OclPrm;
+use standard::Buffer;
+
+
+/// A pair of equally sized buffers used in alternation by iterative
+/// (ping-pong) algorithms such as stencils.
+///
+/// Each iteration, kernels read from `::previous` and write to `::current`.
+/// Call `::swap` between iterations so that the most recently written buffer
+/// becomes the previous one.
+///
+/// Kernel arguments are not updated automatically. Use named arguments
+/// (`Kernel::set_arg_buf_named`) to rebind the buffers after each swap.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct PingPong<T: OclPrm> {
+    a: Buffer<T>,
+    b: Buffer<T>,
+    a_is_current: bool,
+}
+
+impl<T: OclPrm> PingPong<T> {
+    /// Returns a new `PingPong` with `current` initially being the buffer to
+    /// be written and `previous` the buffer to be read.
+    ///
+    /// ## Panics
+    ///
+    /// The lengths of `current` and `previous` must be equal.
+    pub fn new(current: Buffer<T>, previous: Buffer<T>) -> PingPong<T> {
+        assert_eq!(current.len(), previous.len(), "ocl::PingPong::new: Buffer lengths differ.");
+
+        PingPong {
+            a: current,
+            b: previous,
+            a_is_current: true,
+        }
+    }
+
+    /// Exchanges the current and previous buffers.
+    pub fn swap(&mut self) {
+        self.a_is_current = !self.a_is_current;
+    }
+
+    /// Returns the current buffer (the one to be written this iteration).
+    pub fn current(&self) -> &Buffer<T> {
+        if self.a_is_current { &self.a } else { &self.b }
+    }
+
+    /// Returns the previous buffer (the one to be read this iteration).
+    pub fn previous(&self) -> &Buffer<T> {
+        if self.a_is_current { &self.b } else { &self.a }
+    }
+
+    /// Consumes this `PingPong` returning the buffers as
+    /// `(current, previous)`.
+    pub fn into_buffers(self) -> (Buffer<T>, Buffer<T>) {
+        if self.a_is_current { (self.a, self.b) } else { (self.b, self.a) }
+    }
+}
@@ -0,0 +1,121 @@
+//! A persistently mapped, pinned host buffer used for fast transfers.
+
+use std::ops::{Deref, DerefMut};
+use core::{OclPrm, Mem as MemCore, AsMem};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Buffer};
+use ::MemMap;
+
+
+/// A host-accessible buffer allocated with `MEM_ALLOC_HOST_PTR` (usually
+/// page-locked, 'pinned', memory) which remains mapped for its entire
+/// lifetime.
+///
+/// Dereferences to a `[T]` on the host. Transfers to and from device buffers
+/// (`::upload_to` and `::download_from`) read from and write to the pinned
+/// memory directly, allowing the driver to avoid an intermediate staging
+/// copy. Reuse a `PinnedBuffer` across transfers rather than creating one
+/// per transfer, as allocating (and mapping) pinned memory is expensive.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Debug)]
+pub struct PinnedBuffer<T: OclPrm> {
+    // Must be dropped (unmapped) before `buffer`:
+    map: MemMap<T>,
+    buffer: Buffer<T>,
+    queue: Queue,
+}
+
+impl<T: OclPrm> PinnedBuffer<T> {
+    /// Returns a new pinned buffer of `len` elements, initialized to
+    /// `T::default()` and mapped using `queue`.
+    pub fn new(queue: &Queue, len: usize) -> OclResult<PinnedBuffer<T>> {
+        if len == 0 {
+            return OclError::err_string("ocl::PinnedBuffer::new: Length must be non-zero.");
+        }
+
+        let buffer = try!(Buffer::<T>::builder()
+            .queue(queue.clone())
+            .flags(::flags::MEM_READ_WRITE | ::flags::MEM_ALLOC_HOST_PTR)
+            .dims(len)
+            .build());
+
+        let mut map = try!(buffer.map()
+            .flags(::flags::MAP_READ | ::flags::MAP_WRITE)
+            .enq());
+
+        for val in map.iter_mut() {
+            *val = T::default();
+        }
+
+        Ok(PinnedBuffer {
+            map: map,
+            buffer: buffer,
+            queue: queue.clone(),
+        })
+    }
+
+    /// Copies the entire contents of this pinned buffer to `dst_buffer`
+    /// (blocking).
+    ///
+    /// ### Errors
+    ///
+    /// The length of `dst_buffer` must equal the length of this buffer.
+    ///
+    pub fn upload_to(&self, dst_buffer: &Buffer<T>) -> OclResult<()> {
+        try!(self.check_len(dst_buffer, "upload_to"));
+        dst_buffer.write(&self.map[..]).queue(&self.queue).enq()
+    }
+
+    /// Copies the entire contents of `src_buffer` into this pinned buffer
+    /// (blocking).
+    ///
+    /// ### Errors
+    ///
+    /// The length of `src_buffer` must equal the length of this buffer.
+    ///
+    pub fn download_from(&mut self, src_buffer: &Buffer<T>) -> OclResult<()> {
+        try!(self.check_len(src_buffer, "download_from"));
+        src_buffer.read(&mut self.map[..]).queue(&self.queue).enq()
+    }
+
+    /// Returns the length of this buffer (in elements).
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns a reference to the queue used for transfers.
+    pub fn queue(&self) -> &Queue {
+        &self.queue
+    }
+
+    fn check_len(&self, other: &Buffer<T>, fn_name: &str) -> OclResult<()> {
+        if other.len() != self.len() {
+            OclError::err_string(format!("ocl::PinnedBuffer::{}: Buffer length mismatch \
+                (pinned: {}, device: {}).", fn_name, self.len(), other.len()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T: OclPrm> Deref for PinnedBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.map
+    }
+}
+
+impl<T: OclPrm> DerefMut for PinnedBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.map
+    }
+}
+
+impl<T: OclPrm> AsMem<T> for PinnedBuffer<T> {
+    fn as_mem(&self) -> &MemCore {
+        self.buffer.as_mem()
+    }
+}
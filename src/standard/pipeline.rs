@@ -0,0 +1,102 @@
+//! A linear pipeline of kernel stages.
+
+use std;
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Kernel, Event, SpatialDims};
+
+
+/// A linear sequence of kernels, each enqueued with its own global work size
+/// and waiting on the completion of the stage before it.
+///
+/// ```rust,ignore
+/// let event = Pipeline::new(&queue)
+///     .stage(&scale_kernel, 1024)
+///     .stage(&offset_kernel, [32, 32])
+///     .run()?;
+/// ```
+///
+/// The global work offset and local work size of each kernel's defaults (if
+/// any) are used.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+pub struct Pipeline<'k> {
+    queue: Queue,
+    stages: Vec<(&'k Kernel, SpatialDims)>,
+}
+
+impl<'k> Pipeline<'k> {
+    /// Returns a new, empty pipeline which will enqueue its stages on
+    /// `queue`.
+    pub fn new(queue: &Queue) -> Pipeline<'k> {
+        Pipeline {
+            queue: queue.clone(),
+            stages: Vec::new(),
+        }
+    }
+
+    /// Appends a stage which enqueues `kernel` with the global work size
+    /// `gws`.
+    ///
+    /// All of the kernel's arguments must be specified before `::run` is
+    /// called.
+    pub fn stage<D: Into<SpatialDims>>(mut self, kernel: &'k Kernel, gws: D) -> Pipeline<'k> {
+        self.stages.push((kernel, gws.into()));
+        self
+    }
+
+    /// Enqueues every stage, in order, and returns the event associated with
+    /// the final stage.
+    ///
+    /// Does not block.
+    ///
+    /// ### Errors
+    ///
+    /// The pipeline must contain at least one stage.
+    ///
+    pub fn run(&self) -> OclResult<Event> {
+        if self.stages.is_empty() {
+            return OclError::err_string("ocl::Pipeline::run: The pipeline contains no stages.");
+        }
+
+        let mut prev_event: Option<Event> = None;
+
+        for &(kernel, gws) in self.stages.iter() {
+            let mut event = Event::empty();
+
+            try!(kernel.cmd()
+                .queue(&self.queue)
+                .gws(gws)
+                .ewait_opt(prev_event.as_ref())
+                .enew(&mut event)
+                .enq());
+
+            prev_event = Some(event);
+        }
+
+        Ok(prev_event.expect("ocl::Pipeline::run: No final event."))
+    }
+
+    /// Returns the number of stages.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns true if the pipeline contains no stages.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Returns the queue.
+    pub fn queue(&self) -> &Queue {
+        &self.queue
+    }
+}
+
+impl<'k> std::fmt::Debug for Pipeline<'k> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stage_count", &self.stages.len())
+            .finish()
+    }
+}
@@ -7,10 +7,41 @@
 // use std::fmt::{std::fmt::Display, std::fmt::Formatter, Result as std::fmt::Result};
 use std;
 use std::ops::{Deref, DerefMut};
+use std::collections::HashMap;
+use std::sync::{Mutex, Once, ONCE_INIT};
 // use std::convert::Into;
 use ffi::cl_platform_id;
+use standard::{self, Device};
 use core::{self, PlatformId as PlatformIdCore, PlatformInfo, PlatformInfoResult, ClPlatformIdPtr};
 
+/// Cached platform and device lists (see `Platform::list_cached`).
+pub struct EnumerationCache {
+    pub platforms: Option<Vec<Platform>>,
+    /// Device lists keyed by platform pointer.
+    pub devices: HashMap<usize, Vec<Device>>,
+    /// The number of enumerations (of platforms or of a platform's devices)
+    /// performed to fill the cache.
+    pub enumerations: usize,
+}
+
+/// Returns the process-wide enumeration cache.
+pub fn enumeration_cache() -> &'static Mutex<EnumerationCache> {
+    static INIT: Once = ONCE_INIT;
+    static mut CACHE: *const Mutex<EnumerationCache> = 0 as *const Mutex<EnumerationCache>;
+
+    unsafe {
+        INIT.call_once(|| {
+            CACHE = Box::into_raw(Box::new(Mutex::new(EnumerationCache {
+                platforms: None,
+                devices: HashMap::new(),
+                enumerations: 0,
+            })));
+        });
+        &*CACHE
+    }
+}
+
+
 /// A platform identifier.
 ///
 #[repr(C)]
@@ -26,6 +57,46 @@ impl Platform {
         list_core.into_iter().map(Platform::new).collect()
     }
 
+    /// Returns a list of all platforms avaliable on the host machine,
+    /// enumerating them only upon the first call.
+    ///
+    /// Subsequent calls return the cached list (as does
+    /// `Device::list_all_cached` for each platform's devices) until
+    /// `::invalidate_cache` is called. Caching assumes that the set of
+    /// platforms and devices does not change while the process is running.
+    /// Call `::invalidate_cache` after devices are added or removed
+    /// (hotplugged) or use `::list` instead.
+    pub fn list_cached() -> Vec<Platform> {
+        let mut cache = enumeration_cache().lock()
+            .expect("ocl::Platform::list_cached: Enumeration cache lock poisoned.");
+
+        if cache.platforms.is_none() {
+            cache.platforms = Some(Platform::list());
+            cache.enumerations += 1;
+        }
+
+        cache.platforms.clone().unwrap()
+    }
+
+    /// Clears the cached platform and device lists, causing the next call to
+    /// `::list_cached` or `Device::list_all_cached` to enumerate them again.
+    pub fn invalidate_cache() {
+        let mut cache = enumeration_cache().lock()
+            .expect("ocl::Platform::invalidate_cache: Enumeration cache lock poisoned.");
+
+        cache.platforms = None;
+        cache.devices.clear();
+    }
+
+    /// Returns the number of enumerations performed to fill the cache so
+    /// far (see `EnumerationCache::enumerations`).
+    #[cfg(test)]
+    pub(crate) fn cache_enumerations() -> usize {
+        enumeration_cache().lock()
+            .expect("ocl::Platform::cache_enumerations: Enumeration cache lock poisoned.")
+            .enumerations
+    }
+
     // DEPRICATED:
     // /// Returns the first available platform on the host machine.
     // pub fn first() -> Platform {
@@ -88,7 +159,8 @@ impl Platform {
         //     Ok(pi) => pi.into(),
         //     Err(err) => err.into(),
         // }
-        core::get_platform_info(&self.0, PlatformInfo::Profile).into()
+        let info: String = core::get_platform_info(&self.0, PlatformInfo::Profile).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns the platform driver version as a string.
@@ -107,7 +179,8 @@ impl Platform {
         //     Ok(pi) => pi.into(),
         //     Err(err) => err.into(),
         // }
-        core::get_platform_info(&self.0, PlatformInfo::Version).into()
+        let info: String = core::get_platform_info(&self.0, PlatformInfo::Version).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns the platform name as a string.
@@ -116,7 +189,8 @@ impl Platform {
         //     Ok(pi) => pi.into(),
         //     Err(err) => err.into(),
         // }
-        core::get_platform_info(&self.0, PlatformInfo::Name).into()
+        let info: String = core::get_platform_info(&self.0, PlatformInfo::Name).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns the platform vendor as a string.
@@ -125,7 +199,8 @@ impl Platform {
         //     Ok(pi) => pi.into(),
         //     Err(err) => err.into(),
         // }
-        core::get_platform_info(&self.0, PlatformInfo::Vendor).into()
+        let info: String = core::get_platform_info(&self.0, PlatformInfo::Vendor).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns the list of platform extensions as a string.
@@ -139,7 +214,8 @@ impl Platform {
         //     Ok(pi) => pi.into(),
         //     Err(err) => err.into(),
         // }
-        core::get_platform_info(&self.0, PlatformInfo::Extensions).into()
+        let info: String = core::get_platform_info(&self.0, PlatformInfo::Extensions).into();
+        standard::clean_info_string(info)
     }
 
     /// Returns a reference to the underlying `PlatformIdCore`.
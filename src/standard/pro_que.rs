@@ -202,6 +202,7 @@ pub struct ProQueBuilder {
     program_builder: Option<ProgramBuilder>,
     dims: Option<SpatialDims>,
     queue_properties: Option<CommandQueueProperties>,
+    profiling_sibling: bool,
 }
 
 impl ProQueBuilder {
@@ -224,6 +225,7 @@ impl ProQueBuilder {
             program_builder: None,
             dims: None,
             queue_properties: None,
+            profiling_sibling: false,
         }
     }
 
@@ -320,13 +322,30 @@ impl ProQueBuilder {
 
     /// Sets the command queue properties.
     ///
-    /// Optional.
+    /// Optional. Use `QUEUE_PROFILING_ENABLE` to enable profiling (i.e. for
+    /// benchmarking) and/or `QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE` for an
+    /// out-of-order queue. Defaults to an in-order queue with profiling
+    /// disabled.
     ///
     pub fn queue_properties(&mut self, props: CommandQueueProperties) -> &mut ProQueBuilder {
         self.queue_properties = Some(props);
         self
     }
 
+    /// Specifies whether or not to also create a profiling-enabled sibling
+    /// queue on the same device.
+    ///
+    /// Individual commands can then be routed to the profiling queue by
+    /// calling `::profiled` on their command builder. See
+    /// `Queue::enable_profiling_sibling` for more.
+    ///
+    /// Optional. Defaults to `false`.
+    ///
+    pub fn profiling_sibling(&mut self, enable: bool) -> &mut ProQueBuilder {
+        self.profiling_sibling = enable;
+        self
+    }
+
 
     /// Returns a new `ProQue`.
     ///
@@ -404,7 +423,9 @@ impl ProQueBuilder {
 
         if DEBUG_PRINT { println!("ProQue::build(): context.devices(): {:?}", context.devices()); }
 
-        let queue = try!(Queue::new(&context, device, self.queue_properties));
+        let mut queue = try!(Queue::new(&context, device, self.queue_properties));
+
+        if self.profiling_sibling { try!(queue.enable_profiling_sibling()); }
 
         // println!("PROQUEBUILDER: About to load SRC_STRINGS.");
         let src_strings = try!(program_builder.get_src_strings().map_err(|e| e.to_string()));
@@ -10,9 +10,10 @@ use std::convert::Into;
 
 use core::error::{Result as OclResult, Error as OclError};
 use core::{self, Program as ProgramCore, Context as ContextCore,
-    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult};
+    ProgramInfo, ProgramInfoResult, ProgramBuildInfo, ProgramBuildInfoResult, ClDeviceIdPtr};
 #[cfg(feature = "opencl_version_2_1")]
 use core::ClVersions;
+use ffi;
 use standard::{Context, Device, DeviceSpecifier};
 
 
@@ -42,8 +43,12 @@ impl Program {
     pub fn new(context_obj_core: &ContextCore, src_strings: Vec<CString>,
             device_ids: Option<&[Device]>, cmplr_opts: CString) -> OclResult<Program>
     {
-        let obj_core = try!(core::create_build_program(context_obj_core, &src_strings, device_ids,
-            &cmplr_opts));
+        let obj_core = core::create_build_program(context_obj_core, &src_strings, device_ids,
+            &cmplr_opts);
+        trace_cl!("clCreateProgramWithSource/clBuildProgram", &obj_core, "context: {:?}, \
+            src_strings: {}, devices: {:?}, options: {:?}", context_obj_core.as_ptr(),
+            src_strings.len(), device_ids, cmplr_opts);
+        let obj_core = try!(obj_core);
 
         Ok(Program(obj_core))
     }
@@ -65,6 +70,51 @@ impl Program {
         Ok(Program(obj_core))
     }
 
+    /// Returns a new program containing the built-in kernels named in
+    /// `kernel_names` (see `Device::built_in_kernels`) for each of `devices`.
+    ///
+    /// No build step is necessary. Kernels can be created from the program
+    /// as usual.
+    ///
+    /// ### Errors
+    ///
+    /// `kernel_names` and `devices` must not be empty and each name must be
+    /// a built-in kernel supported by every device.
+    ///
+    pub fn from_built_in_kernels(context: &Context, devices: &[Device], kernel_names: &[&str])
+            -> OclResult<Program>
+    {
+        if kernel_names.is_empty() {
+            return OclError::err_string("ocl::Program::from_built_in_kernels: No kernel \
+                names specified.");
+        }
+
+        if devices.is_empty() {
+            return OclError::err_string("ocl::Program::from_built_in_kernels: No devices \
+                specified.");
+        }
+
+        let names = try!(CString::new(kernel_names.join(";")));
+        let device_ptrs: Vec<_> = devices.iter().map(|d| d.as_ptr()).collect();
+        let mut errcode: i32 = 0;
+
+        let program_ptr = unsafe {
+            ffi::clCreateProgramWithBuiltInKernels(context.as_ptr(), device_ptrs.len() as u32,
+                device_ptrs.as_ptr(), names.as_ptr(), &mut errcode)
+        };
+        let result: Result<(), i32> = if errcode == 0 { Ok(()) } else { Err(errcode) };
+        trace_cl!("clCreateProgramWithBuiltInKernels", &result, "context: {:?}, devices: {:?}, \
+            kernel_names: {:?}", context.as_ptr(), devices, names);
+
+        if let Err(errcode) = result {
+            return OclError::err_string(format!("ocl::Program::from_built_in_kernels: \
+                Unable to create program (errcode: {}). Ensure that each kernel name is \
+                supported by every device.", errcode));
+        }
+
+        unsafe { Ok(Program(ProgramCore::from_raw_create_ptr(program_ptr))) }
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[deprecated(since="0.13.0", note="Use `::core` instead.")]
@@ -90,6 +140,20 @@ impl Program {
         core::get_program_info(&self.0, info_kind)
     }
 
+    /// Returns the source code this program was created from
+    /// (`CL_PROGRAM_SOURCE`).
+    ///
+    /// The source is returned as passed to the implementation, including any
+    /// defines and includes added by the builder. Programs created from
+    /// binaries or IL return an empty string.
+    pub fn source(&self) -> OclResult<String> {
+        match self.info(ProgramInfo::Source) {
+            ProgramInfoResult::Source(src) => Ok(src),
+            ProgramInfoResult::Error(err) => Err(*err),
+            _ => panic!("Program::source: Unexpected 'ProgramInfoResult' variant."),
+        }
+    }
+
     /// Returns info about this program's build.
     ///
     /// * TODO: Check that device is valid.
@@ -97,6 +161,16 @@ impl Program {
         core::get_program_build_info(&self.0, &device, info_kind)
     }
 
+    /// Returns the options this program was built with for `device`
+    /// (`CL_PROGRAM_BUILD_OPTIONS`).
+    pub fn build_options(&self, device: &Device) -> OclResult<String> {
+        match core::get_program_build_info(&self.0, device, ProgramBuildInfo::BuildOptions) {
+            ProgramBuildInfoResult::BuildOptions(opts) => Ok(opts),
+            ProgramBuildInfoResult::Error(err) => Err(*err),
+            _ => panic!("Program::build_options: Unexpected 'ProgramBuildInfoResult' variant."),
+        }
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Program")
             .field("ReferenceCount", &self.info(ProgramInfo::ReferenceCount))
@@ -134,6 +208,70 @@ impl DerefMut for Program {
 }
 
 
+/// A numeric value which can be formatted as an OpenCL C literal for use in
+/// a build definition (see `ProgramBuilder::define_typed`).
+///
+/// Literals are suffixed according to their type (`f` for `f32`, `U` for
+/// `u32`, `L` for `i64`, `UL` for `u64`) so that the constant has the
+/// intended type when used within a kernel. Floating point values always
+/// contain a decimal point or exponent. Negative values are parenthesized.
+pub trait DefineValue {
+    /// Returns this value formatted as an OpenCL C literal.
+    fn to_define_string(&self) -> String;
+}
+
+macro_rules! impl_define_value_signed {
+    ($($ty:ty => $sfx:expr),+) => ($(
+        impl DefineValue for $ty {
+            fn to_define_string(&self) -> String {
+                if *self == <$ty>::min_value() {
+                    // The magnitude of the minimum value is not representable.
+                    format!("({}{} - 1)", *self + 1, $sfx)
+                } else if *self < 0 {
+                    format!("({}{})", self, $sfx)
+                } else {
+                    format!("{}{}", self, $sfx)
+                }
+            }
+        }
+    )+)
+}
+
+macro_rules! impl_define_value_unsigned {
+    ($($ty:ty => $sfx:expr),+) => ($(
+        impl DefineValue for $ty {
+            fn to_define_string(&self) -> String {
+                format!("{}{}", self, $sfx)
+            }
+        }
+    )+)
+}
+
+impl_define_value_signed!(i8 => "", i16 => "", i32 => "", i64 => "L");
+impl_define_value_unsigned!(u8 => "", u16 => "", u32 => "U", u64 => "UL");
+
+macro_rules! impl_define_value_float {
+    ($($ty:ty => $sfx:expr),+) => ($(
+        impl DefineValue for $ty {
+            fn to_define_string(&self) -> String {
+                if self.is_nan() {
+                    String::from("NAN")
+                } else if self.is_infinite() {
+                    if *self > 0.0 { String::from("INFINITY") } else { String::from("(-INFINITY)") }
+                } else if *self < 0.0 {
+                    // `Debug` always includes a decimal point or exponent.
+                    format!("({:?}{})", self, $sfx)
+                } else {
+                    format!("{:?}{}", self, $sfx)
+                }
+            }
+        }
+    )+)
+}
+
+impl_define_value_float!(f32 => "f", f64 => "");
+
+
 /// A build option used by ProgramBuilder.
 ///
 /// Strings intended for use either by the compiler as a command line switch
@@ -173,6 +311,13 @@ impl BuildOpt {
 }
 
 
+/// Returns a `#line` directive resetting the line number and file name
+/// reported for the text which follows.
+fn line_marker<D: std::fmt::Display>(name: &D) -> String {
+    format!("\n#line 1 \"{}\"\n", name)
+}
+
+
 /// A builder for `Program`.
 ///
 // * [SOMEDAY TODO]: Keep track of line number range for each string and print
@@ -185,6 +330,8 @@ pub struct ProgramBuilder {
     src_files: Vec<PathBuf>,
     il: Option<Vec<u8>>,
     device_spec: Option<DeviceSpecifier>,
+    line_marker_name: Option<String>,
+    extensions: Vec<&'static str>,
 }
 
 impl ProgramBuilder {
@@ -195,6 +342,8 @@ impl ProgramBuilder {
             src_files: Vec::with_capacity(16),
             il: None,
             device_spec: None,
+            line_marker_name: None,
+            extensions: Vec::new(),
         }
     }
 
@@ -215,6 +364,8 @@ impl ProgramBuilder {
             None => context.devices(),
         };
 
+        try!(self.check_extensions(&device_list, "ProgramBuilder::build"));
+
         match self.il {
             Some(_) => {
                 return Err("ocl::ProgramBuilder::build: Unreachable section (IL).".into());
@@ -244,6 +395,8 @@ impl ProgramBuilder {
             None => context.devices().to_owned(),
         };
 
+        try!(self.check_extensions(&device_list, "ProgramBuilder::build"));
+
         match self.il.take() {
             Some(il) => {
                 if cfg!(feature = "opencl_version_2_1") {
@@ -271,6 +424,119 @@ impl ProgramBuilder {
         }
     }
 
+    /// Returns a new program built only for `devices`, overriding any device
+    /// specifier set with `::devices`.
+    ///
+    /// Each device must be associated with `context`. Other devices
+    /// associated with the context are not built for and may not be used
+    /// with the resulting program.
+    ///
+    /// ### Errors
+    ///
+    /// If the build fails, the returned error lists the build status and
+    /// build log of each device in `devices`.
+    ///
+    pub fn build_for(self, context: &Context, devices: &[Device]) -> OclResult<Program> {
+        if self.il.is_some() {
+            return Err("ocl::ProgramBuilder::build_for: Programs using intermediate language \
+                must be built with '::build'.".into());
+        }
+
+        if devices.is_empty() {
+            return Err("ocl::ProgramBuilder::build_for: No devices specified.".into());
+        }
+
+        let context_devices = context.devices();
+        for device in devices {
+            if !context_devices.contains(device) {
+                return OclError::err_string(format!("ocl::ProgramBuilder::build_for: The device \
+                    '{}' is not associated with the context.", device.name()));
+            }
+        }
+
+        try!(self.check_extensions(devices, "ProgramBuilder::build_for"));
+
+        let src_strings = try!(self.get_src_strings().map_err(|e| e.to_string()));
+        let cmplr_opts = try!(self.get_compiler_options().map_err(|e| e.to_string()));
+        let obj_core = try!(core::create_program_with_source(context, &src_strings));
+
+        if let Err(err) = core::build_program(&obj_core, Some(devices), &cmplr_opts, None, None) {
+            let mut msg = format!("ocl::ProgramBuilder::build_for: Build failed: {}\n", err);
+
+            for device in devices {
+                let status = match core::get_program_build_info(&obj_core, device,
+                        ProgramBuildInfo::BuildStatus) {
+                    ProgramBuildInfoResult::BuildStatus(status) => format!("{:?}", status),
+                    other => other.to_string(),
+                };
+
+                msg.push_str(&format!("\nDevice '{}': {}\n{}\n", device.name(), status,
+                    core::get_program_build_info(&obj_core, device, ProgramBuildInfo::BuildLog)));
+            }
+
+            return OclError::err_string(msg);
+        }
+
+        Ok(Program(obj_core))
+    }
+
+    /// Enables double precision floating point (`double`) support by
+    /// prepending `#pragma OPENCL EXTENSION cl_khr_fp64 : enable` to the
+    /// program source.
+    ///
+    /// Building will fail if any of the devices being built for does not
+    /// support the `cl_khr_fp64` extension.
+    pub fn enable_fp64(self) -> ProgramBuilder {
+        self.enable_extension("cl_khr_fp64")
+    }
+
+    /// Enables half precision floating point (`half`) support by prepending
+    /// `#pragma OPENCL EXTENSION cl_khr_fp16 : enable` to the program source.
+    ///
+    /// Building will fail if any of the devices being built for does not
+    /// support the `cl_khr_fp16` extension.
+    pub fn enable_fp16(self) -> ProgramBuilder {
+        self.enable_extension("cl_khr_fp16")
+    }
+
+    /// Enables subgroup functions (such as `get_sub_group_size` and
+    /// `sub_group_reduce_add`) by prepending `#pragma OPENCL EXTENSION
+    /// cl_khr_subgroups : enable` to the program source.
+    ///
+    /// Building will fail if any of the devices being built for does not
+    /// support the `cl_khr_subgroups` extension. Devices supporting subgroups
+    /// as a core OpenCL 2.1 feature do not require the pragma (see
+    /// `Device::supports_subgroups`).
+    pub fn with_subgroup_extension(self) -> ProgramBuilder {
+        self.enable_extension("cl_khr_subgroups")
+    }
+
+    /// Adds `extension` to the list of extensions enabled with a pragma.
+    fn enable_extension(mut self, extension: &'static str) -> ProgramBuilder {
+        if !self.extensions.contains(&extension) {
+            self.extensions.push(extension);
+        }
+        self
+    }
+
+    /// Returns an error if any enabled extension is unsupported by any of
+    /// `devices`. `fn_name` is the name of the calling function, used in the
+    /// error message.
+    fn check_extensions(&self, devices: &[Device], fn_name: &'static str) -> OclResult<()> {
+        if self.extensions.is_empty() { return Ok(()); }
+
+        for device in devices {
+            for &ext in &self.extensions {
+                if !try!(device.supports_extension(ext)) {
+                    return OclError::err_string(format!("ocl::{}: The device '{}' does not \
+                        support the '{}' extension.", fn_name, device.name(), ext));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Adds a build option containing a compiler command line definition.
     /// Formatted as `-D {name}={val}`.
     ///
@@ -283,6 +549,25 @@ impl ProgramBuilder {
         self
     }
 
+    /// Adds a build option containing a compiler command line definition
+    /// with a typed value formatted as a correctly suffixed OpenCL C literal.
+    /// Formatted as `-D {name}={val}`.
+    ///
+    /// See [`DefineValue`] for formatting details.
+    ///
+    /// ## Example
+    ///
+    /// `...define_typed("SCALE", 0.5f32)...` (produces `-D SCALE=0.5f`)
+    ///
+    /// [`DefineValue`]: trait.DefineValue.html
+    ///
+    pub fn define_typed<S: Into<String>, V: DefineValue>(mut self, name: S, val: V)
+            -> ProgramBuilder
+    {
+        self.options.push(BuildOpt::CmplrDefine { ident: name.into(), val: val.to_define_string() });
+        self
+    }
+
     /// Adds a build option containing a raw compiler command line parameter.
     /// Formatted as `{}` (exact text).
     ///
@@ -316,6 +601,18 @@ impl ProgramBuilder {
         self
     }
 
+    /// Prefixes each source fragment with a `#line` directive so that line
+    /// numbers reported in compiler diagnostics (the build log) refer to the
+    /// fragment itself rather than to the concatenated program source.
+    ///
+    /// Fragments added with `::src` (or `BuildOpt::IncludeRawEof`) are
+    /// named `file_name[n]`, `n` being the zero-based index of the fragment.
+    /// Files added with `::src_file` are named by their path.
+    pub fn with_line_markers<S: Into<String>>(mut self, file_name: S) -> ProgramBuilder {
+        self.line_marker_name = Some(file_name.into());
+        self
+    }
+
     /// Adds SPIR-V or an implementation-defined intermediate language to this program.
     ///
     /// Any source files or source text added to this build will cause an
@@ -413,6 +710,11 @@ impl ProgramBuilder {
 
             try!(src_file_handle.read_to_end(&mut src_bytes));
             src_bytes.shrink_to_fit();
+
+            if self.line_marker_name.is_some() {
+                src_strings.push(try!(CString::new(line_marker(&srcpath.display()))));
+            }
+
             src_strings.push(try!(CString::new(src_bytes)));
         }
 
@@ -430,6 +732,11 @@ impl ProgramBuilder {
         let mut strings = Vec::with_capacity(64);
         strings.push(try!(CString::new("\n".as_bytes())));
 
+        for ext in &self.extensions {
+            strings.push(try!(CString::new(format!("#pragma OPENCL EXTENSION {} : enable\n", ext)
+                .into_bytes())));
+        }
+
         for option in &self.options {
             match *option {
                 BuildOpt::IncludeDefine { ref ident, ref val } => {
@@ -454,9 +761,17 @@ impl ProgramBuilder {
         let mut strings = Vec::with_capacity(64);
         strings.push(try!(CString::new("\n".as_bytes())));
 
+        let mut fragment_idx = 0;
+
         for option in &self.options {
             if let BuildOpt::IncludeRawEof(ref text) = *option {
+                if let Some(ref name) = self.line_marker_name {
+                    let fragment_name = format!("{}[{}]", name, fragment_idx);
+                    strings.push(try!(CString::new(line_marker(&fragment_name))));
+                }
+
                 strings.push(try!(CString::new(text.clone().into_bytes())));
+                fragment_idx += 1;
             }
         }
 
@@ -465,3 +780,164 @@ impl ProgramBuilder {
     }
 }
 
+
+
+/// The severity of a `BuildDiagnostic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildDiagnosticSeverity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl BuildDiagnosticSeverity {
+    /// Returns the severity named by `label` (as it appears in a build log).
+    fn from_label(label: &str) -> Option<BuildDiagnosticSeverity> {
+        match label {
+            "error" | "fatal error" => Some(BuildDiagnosticSeverity::Error),
+            "warning" => Some(BuildDiagnosticSeverity::Warning),
+            "note" => Some(BuildDiagnosticSeverity::Note),
+            _ => None,
+        }
+    }
+}
+
+
+/// A single entry parsed from a program build log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildDiagnostic {
+    pub severity: BuildDiagnosticSeverity,
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+    pub message: String,
+}
+
+impl BuildDiagnostic {
+    /// Parses a clang-style log line: `file:line[:column]: severity: message`.
+    ///
+    /// Returns `None` if `line` is not in that format.
+    fn parse(log_line: &str) -> Option<BuildDiagnostic> {
+        let labels = ["fatal error", "error", "warning", "note"];
+
+        for label in labels.iter() {
+            let marker = format!(": {}: ", label);
+
+            let marker_idx = match log_line.find(&marker[..]) {
+                Some(idx) => idx,
+                None => continue,
+            };
+
+            let location = &log_line[..marker_idx];
+            let message = log_line[marker_idx + marker.len()..].trim();
+            let mut parts = location.rsplitn(3, ':');
+
+            let (file, line, column) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(col), Some(line), Some(file)) => match (line.trim().parse(),
+                        col.trim().parse()) {
+                    (Ok(line), Ok(col)) => (file, line, Some(col)),
+                    // The "file" may itself contain a colon (e.g. `C:\..`):
+                    (Err(_), Ok(line)) => (&location[..location.len() - col.len() - 1],
+                        line, None),
+                    _ => continue,
+                },
+                (Some(line), Some(file), None) => match line.trim().parse() {
+                    Ok(line) => (file, line, None),
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+
+            return Some(BuildDiagnostic {
+                severity: BuildDiagnosticSeverity::from_label(label)
+                    .expect("BuildDiagnostic::parse: Invalid severity label."),
+                file: file.trim().to_owned(),
+                line: line,
+                column: column,
+                message: message.to_owned(),
+            });
+        }
+
+        None
+    }
+}
+
+impl std::fmt::Display for BuildDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let severity = match self.severity {
+            BuildDiagnosticSeverity::Error => "error",
+            BuildDiagnosticSeverity::Warning => "warning",
+            BuildDiagnosticSeverity::Note => "note",
+        };
+
+        match self.column {
+            Some(col) => write!(f, "{}:{}:{}: {}: {}", self.file, self.line, col, severity,
+                self.message),
+            None => write!(f, "{}:{}: {}: {}", self.file, self.line, severity, self.message),
+        }
+    }
+}
+
+
+/// A program build failure along with the diagnostics parsed from its build
+/// log.
+///
+/// Convert the error returned by `ProgramBuilder::build` (or `ProQue`,
+/// etc.) with `ProgramBuildError::from`, i.e.:
+/// `.build(&context).map_err(ProgramBuildError::from)`.
+///
+/// Log lines not in the common clang-style format
+/// (`file:line:column: severity: message`) are not parsed. When no line is
+/// recognized, `::diagnostics` is empty and only the raw log is available.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct ProgramBuildError {
+    log: String,
+    diagnostics: Vec<BuildDiagnostic>,
+}
+
+impl ProgramBuildError {
+    /// Returns a new build error, parsing diagnostics from `log`.
+    pub fn from_log<S: Into<String>>(log: S) -> ProgramBuildError {
+        let log = log.into();
+        let diagnostics = log.lines().filter_map(BuildDiagnostic::parse).collect();
+
+        ProgramBuildError { log: log, diagnostics: diagnostics }
+    }
+
+    /// Returns the diagnostics parsed from the build log.
+    pub fn diagnostics(&self) -> &[BuildDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns an iterator over only the error diagnostics.
+    pub fn errors<'a>(&'a self) -> Box<Iterator<Item=&'a BuildDiagnostic> + 'a> {
+        Box::new(self.diagnostics.iter()
+            .filter(|d| d.severity == BuildDiagnosticSeverity::Error))
+    }
+
+    /// Returns the raw, unparsed build log.
+    pub fn log(&self) -> &str {
+        &self.log
+    }
+}
+
+impl From<OclError> for ProgramBuildError {
+    fn from(err: OclError) -> ProgramBuildError {
+        ProgramBuildError::from_log(err.to_string())
+    }
+}
+
+impl std::fmt::Display for ProgramBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.log)
+    }
+}
+
+impl std::error::Error for ProgramBuildError {
+    fn description(&self) -> &str {
+        "OpenCL program build failed"
+    }
+}
@@ -2,10 +2,67 @@
 
 use std;
 use std::ops::{Deref, DerefMut};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use core::error::{Result as OclResult};
 use core::{self, CommandQueue as CommandQueueCore, CommandQueueInfo, CommandQueueInfoResult,
-    OpenclVersion, CommandQueueProperties, ClWaitListPtr, ClContextPtr};
-use standard::{Context, Device, Event};
+    OpenclVersion, CommandQueueProperties, ClWaitListPtr, ClContextPtr, QUEUE_PROFILING_ENABLE};
+use standard::{Context, Device, Event, Program, ClWaitListPtrEnum};
+#[cfg(feature = "opencl_version_2_0")]
+use ffi;
+#[cfg(feature = "opencl_version_2_0")]
+use num::FromPrimitive;
+#[cfg(feature = "opencl_version_2_0")]
+use core::{Status, ClDeviceIdPtr};
+#[cfg(feature = "opencl_version_2_0")]
+use core::error::Error as OclError;
+#[cfg(feature = "opencl_version_2_0")]
+use core::{OclPrm, MapFlags};
+#[cfg(feature = "opencl_version_2_0")]
+use standard::{SvmBuffer, SvmCmd, SvmCmdKind};
+
+/// `CL_QUEUE_DEVICE_DEFAULT` (OpenCL 2.1).
+#[cfg(feature = "opencl_version_2_1")]
+const CL_QUEUE_DEVICE_DEFAULT: ffi::cl_uint = 0x1095;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_QUEUE_PROPERTIES: u64 = 0x1093;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_QUEUE_PRIORITY_KHR: u64 = 0x1096;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_QUEUE_THROTTLE_KHR: u64 = 0x1097;
+
+/// Returns the value of the property `name` within the zero-terminated list
+/// of (name, value) pairs, `properties`.
+#[cfg(feature = "opencl_version_2_0")]
+fn property_value(properties: &[u64], name: u64) -> Option<u64> {
+    properties.chunks(2)
+        .take_while(|pair| pair[0] != 0)
+        .find(|pair| pair.len() == 2 && pair[0] == name)
+        .map(|pair| pair[1])
+}
+
+
+/// A queue priority hint (`cl_khr_priority_hints`).
+///
+/// See `QueueBuilder::priority`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Priority {
+    High = 1 << 0,
+    Med = 1 << 1,
+    Low = 1 << 2,
+}
+
+
+/// A queue throttle hint (`cl_khr_throttle_hints`), indicating the desired
+/// trade off between performance and power use.
+///
+/// See `QueueBuilder::throttle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Throttle {
+    High = 1 << 0,
+    Med = 1 << 1,
+    Low = 1 << 2,
+}
 
 /// A command queue which manages all actions taken on kernels, buffers, and
 /// images.
@@ -20,21 +77,97 @@ use standard::{Context, Device, Event};
 pub struct Queue {
     obj_core: CommandQueueCore,
     device_version: OpenclVersion,
+    profiling_enabled: bool,
+    priority_hint: Option<Priority>,
+    throttle_hint: Option<Throttle>,
+    profiling_sibling: Option<Box<Queue>>,
+    reduction_programs: Arc<Mutex<HashMap<&'static str, Program>>>,
 }
 
 impl Queue {
     /// Returns a new Queue on the device specified by `device`.
     pub fn new(context: &Context, device: Device, properties: Option<CommandQueueProperties>)
             -> OclResult<Queue> {
-        let obj_core = try!(core::create_command_queue(context, &device, properties));
+        let obj_core = core::create_command_queue(context, &device, properties);
+        trace_cl!("clCreateCommandQueue", &obj_core, "context: {:?}, device: {:?}, \
+            properties: {:?}", context.as_ptr(), device, properties);
+        let obj_core = try!(obj_core);
         let device_version = try!(device.version());
 
         Ok(Queue {
             obj_core: obj_core,
             device_version: device_version,
+            profiling_enabled: properties.map(|props| props.contains(QUEUE_PROFILING_ENABLE))
+                .unwrap_or(false),
+            priority_hint: None,
+            throttle_hint: None,
+            profiling_sibling: None,
+            reduction_programs: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
+    /// Returns a new, independent queue created with the same context,
+    /// device, and properties as this one.
+    ///
+    /// Unlike `::clone`, which returns another reference to the same queue,
+    /// commands enqueued on the returned queue are not ordered with respect
+    /// to those enqueued on this one (use events to synchronize the two). If
+    /// this queue has a profiling sibling (see `::enable_profiling_sibling`),
+    /// a new sibling is created for the duplicate as well.
+    pub fn duplicate(&self) -> OclResult<Queue> {
+        let mut queue = Queue::new(&self.context(), self.device(), Some(self.properties()?))?;
+        if self.profiling_sibling.is_some() {
+            queue.enable_profiling_sibling()?;
+        }
+        Ok(queue)
+    }
+
+    /// Creates a second, profiling-enabled queue on the same context and
+    /// device as this one and stores it alongside this queue.
+    ///
+    /// Commands can then be routed to the profiling queue on a per-command
+    /// basis by calling `::profiled` on a command builder (such as
+    /// `KernelCmd` or `BufferCmd`).
+    ///
+    /// The sibling queue inherits all other properties of this queue. Does
+    /// nothing if this queue already has profiling enabled or already has a
+    /// profiling sibling.
+    ///
+    /// [UNSTABLE]: Evaluate naming.
+    pub fn enable_profiling_sibling(&mut self) -> OclResult<()> {
+        if self.profiling_sibling.is_some() || self.profiling_enabled {
+            return Ok(());
+        }
+
+        let props = self.properties()?;
+        let sibling = Queue::new(&self.context(), self.device(),
+            Some(props | QUEUE_PROFILING_ENABLE))?;
+        self.profiling_sibling = Some(Box::new(sibling));
+        Ok(())
+    }
+
+    /// Returns a profiling-enabled queue associated with this queue, if any.
+    ///
+    /// Returns this queue itself if it was created with profiling enabled,
+    /// otherwise returns the sibling queue created with
+    /// `::enable_profiling_sibling` (or `None` if none exists).
+    pub fn profiled(&self) -> Option<&Queue> {
+        if self.profiling_enabled {
+            Some(self)
+        } else {
+            self.profiling_sibling.as_ref().map(|q| &**q)
+        }
+    }
+
+    /// Returns the compiled reduction programs (see `Buffer::sum`) shared by
+    /// this queue and its clones, keyed by element type name.
+    ///
+    /// Stored with the queue rather than globally so that the programs (and
+    /// the context they retain) are released along with the queue.
+    pub(crate) fn reduction_programs(&self) -> &Mutex<HashMap<&'static str, Program>> {
+        &self.reduction_programs
+    }
+
     /// Issues all previously queued OpenCL commands to the device.
     pub fn flush(&self) -> OclResult<()> {
         core::flush(&self.obj_core)
@@ -55,6 +188,39 @@ impl Queue {
             Some(&self.device_version)).map(|_| marker_event)
     }
 
+    /// Enqueues a marker which completes once all previously enqueued
+    /// commands have completed, flushes the queue, and returns the marker's
+    /// event for use as a future.
+    ///
+    /// Allows asynchronous host code to wait for the queue to reach this
+    /// point without blocking a thread: the returned `Event` is a `Future`
+    /// (and a `std::future::Future` with the `std_future` feature) which is
+    /// resolved by a completion callback. If the queue is already idle, the
+    /// marker completes immediately and the future resolves upon its first
+    /// poll without registering a callback.
+    pub fn barrier_future(&self) -> OclResult<Event> {
+        let marker_event = try!(self.enqueue_marker(None::<Event>));
+        try!(self.flush());
+        Ok(marker_event)
+    }
+
+    /// Enqueues a barrier which prevents any subsequently enqueued commands
+    /// from starting until every event in `events` has completed.
+    ///
+    /// Useful for making work on this queue depend on events from another
+    /// queue (or user events) without attaching them to a specific command.
+    /// Does nothing if `events` is empty.
+    ///
+    /// Events from another queue must belong to commands which have been
+    /// flushed (see `::flush`) or this queue may wait on them indefinitely.
+    pub fn wait_for_events(&self, events: &[Event]) -> OclResult<()> {
+        if events.is_empty() { return Ok(()); }
+
+        core::enqueue_barrier_with_wait_list(&self.obj_core,
+            Some(ClWaitListPtrEnum::EventSlice(events)), None::<&mut Event>,
+            Some(&self.device_version))
+    }
+
     /// Returns a reference to the core pointer wrapper, usable by functions in
     /// the `core` module.
     #[deprecated(since="0.13.0", note="Use `::core` instead.")]
@@ -89,6 +255,174 @@ impl Queue {
         core::get_command_queue_info(&self.obj_core, info_kind)
     }
 
+    /// Returns the properties this queue was created with.
+    pub fn properties(&self) -> OclResult<CommandQueueProperties> {
+        match self.info(CommandQueueInfo::Properties) {
+            CommandQueueInfoResult::Properties(props) => Ok(props),
+            CommandQueueInfoResult::Error(err) => Err(*err),
+            _ => panic!("Queue::properties: Unexpected 'CommandQueueInfoResult' variant."),
+        }
+    }
+
+    /// Returns true if this queue was created with profiling enabled
+    /// (`QUEUE_PROFILING_ENABLE`).
+    ///
+    /// Profiling info (see `Event::profiling_info`) is only available for
+    /// commands enqueued on a profiling-enabled queue.
+    ///
+    /// Determined when the queue is created (no query is made).
+    pub fn is_profiling_enabled(&self) -> OclResult<bool> {
+        Ok(self.profiling_enabled)
+    }
+
+    /// Returns the priority hint this queue was created with, if one was
+    /// applied (see `QueueBuilder::priority`).
+    ///
+    /// Returns `None` if the hint was ignored because the device does not
+    /// support `cl_khr_priority_hints`.
+    pub fn priority_hint(&self) -> Option<Priority> {
+        self.priority_hint
+    }
+
+    /// Returns the throttle hint this queue was created with, if one was
+    /// applied (see `QueueBuilder::throttle`).
+    ///
+    /// Returns `None` if the hint was ignored because the device does not
+    /// support `cl_khr_throttle_hints`.
+    pub fn throttle_hint(&self) -> Option<Throttle> {
+        self.throttle_hint
+    }
+
+    /// Returns the default on-device queue of the device associated with
+    /// this queue, if one has been set (see
+    /// `Context::set_default_device_queue`).
+    ///
+    /// The returned queue executes commands enqueued from within kernels
+    /// using `get_default_queue()`.
+    #[cfg(feature = "opencl_version_2_1")]
+    pub fn device_default(&self) -> OclResult<Option<Queue>> {
+        let mut queue_ptr: ffi::cl_command_queue = std::ptr::null_mut();
+
+        let errcode = unsafe {
+            ffi::clGetCommandQueueInfo(self.obj_core.as_ptr(), CL_QUEUE_DEVICE_DEFAULT,
+                std::mem::size_of::<ffi::cl_command_queue>(),
+                &mut queue_ptr as *mut _ as *mut ::libc::c_void, std::ptr::null_mut())
+        };
+
+        if errcode != ffi::CL_SUCCESS as ffi::cl_int {
+            return Err(format!("ocl::Queue::device_default: 'clGetCommandQueueInfo' failed \
+                with error code: {}", errcode).into());
+        }
+
+        if queue_ptr.is_null() { return Ok(None); }
+
+        let obj_core = unsafe { CommandQueueCore::from_raw_copied_ptr(queue_ptr) };
+
+        let profiling_enabled = match core::get_command_queue_info(&obj_core,
+                CommandQueueInfo::Properties) {
+            CommandQueueInfoResult::Properties(props) => props.contains(QUEUE_PROFILING_ENABLE),
+            CommandQueueInfoResult::Error(err) => return Err(*err),
+            _ => panic!("ocl::Queue::device_default: Unexpected 'CommandQueueInfoResult' variant."),
+        };
+
+        Ok(Some(Queue {
+            obj_core: obj_core,
+            device_version: self.device_version,
+            profiling_enabled: profiling_enabled,
+            priority_hint: None,
+            throttle_hint: None,
+            profiling_sibling: None,
+            reduction_programs: Arc::new(Mutex::new(HashMap::new())),
+        }))
+    }
+
+    /// Returns an SVM command builder which copies `bytes` bytes from `src`
+    /// to `dst`.
+    ///
+    /// Use `::src_offset` and `::dst_offset` to copy between sub-regions.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn svm_memcpy<'c, T: OclPrm>(&'c self, dst: &'c SvmBuffer<T>, src: &'c SvmBuffer<T>,
+            bytes: usize) -> SvmCmd<'c, T>
+    {
+        SvmCmd::new(self, SvmCmdKind::Memcpy { dst: dst, src: src }, bytes)
+    }
+
+    /// Returns an SVM command builder which fills `bytes` bytes of `dst`
+    /// with `pattern`.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn svm_memfill<'c, T: OclPrm>(&'c self, dst: &'c SvmBuffer<T>, pattern: T, bytes: usize)
+            -> SvmCmd<'c, T>
+    {
+        SvmCmd::new(self, SvmCmdKind::Fill { dst: dst, pattern: pattern }, bytes)
+    }
+
+    /// Returns an SVM command builder which maps `bytes` bytes of `buffer`
+    /// for host access with `flags`.
+    ///
+    /// The host may access the mapped region (see `SvmBuffer::as_slice`)
+    /// once the command completes and until `::svm_unmap` is enqueued.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn svm_map<'c, T: OclPrm>(&'c self, buffer: &'c SvmBuffer<T>, flags: MapFlags,
+            bytes: usize) -> SvmCmd<'c, T>
+    {
+        SvmCmd::new(self, SvmCmdKind::Map { buffer: buffer, flags: flags }, bytes)
+    }
+
+    /// Returns an SVM command builder which unmaps a region of `buffer`
+    /// previously mapped with `::svm_map`.
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn svm_unmap<'c, T: OclPrm>(&'c self, buffer: &'c SvmBuffer<T>) -> SvmCmd<'c, T> {
+        SvmCmd::new(self, SvmCmdKind::Unmap { buffer: buffer }, 0)
+    }
+
+    /// Returns a new `QueueBuilder` for a queue on `device`.
+    pub fn builder(context: &Context, device: Device) -> QueueBuilder {
+        QueueBuilder::new(context, device)
+    }
+
+    /// Creates a queue using `clCreateCommandQueueWithProperties` and the
+    /// zero-terminated property list, `properties`.
+    #[cfg(feature = "opencl_version_2_0")]
+    fn with_property_list(context: &Context, device: Device, properties: &[u64])
+            -> OclResult<Queue>
+    {
+        let mut errcode: ffi::cl_int = 0;
+        let queue_ptr = unsafe {
+            ffi::clCreateCommandQueueWithProperties(context.as_ptr(), device.as_core().as_ptr(),
+                properties.as_ptr(), &mut errcode)
+        };
+
+        if errcode != ffi::CL_SUCCESS as ffi::cl_int {
+            return OclError::err_string(format!("ocl::Queue::with_property_list: \
+                'clCreateCommandQueueWithProperties' failed with status: {:?} ({}).",
+                Status::from_i32(errcode), errcode));
+        }
+
+        Ok(Queue {
+            obj_core: unsafe { CommandQueueCore::from_raw_create_ptr(queue_ptr) },
+            device_version: try!(device.version()),
+            profiling_enabled: property_value(properties, CL_QUEUE_PROPERTIES)
+                .map(|props| (props & QUEUE_PROFILING_ENABLE.bits()) != 0)
+                .unwrap_or(false),
+            priority_hint: property_value(properties, CL_QUEUE_PRIORITY_KHR)
+                .and_then(|p| match p {
+                    1 => Some(Priority::High),
+                    2 => Some(Priority::Med),
+                    4 => Some(Priority::Low),
+                    _ => None,
+                }),
+            throttle_hint: property_value(properties, CL_QUEUE_THROTTLE_KHR)
+                .and_then(|t| match t {
+                    1 => Some(Throttle::High),
+                    2 => Some(Throttle::Med),
+                    4 => Some(Throttle::Low),
+                    _ => None,
+                }),
+            profiling_sibling: None,
+            reduction_programs: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
     fn fmt_info(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("Queue")
             .field("Context", &self.info(CommandQueueInfo::Context))
@@ -136,4 +470,118 @@ unsafe impl<'a> ClContextPtr for &'a Queue {
         self.context_ptr().expect("<&Queue as ClContextPtr>::as_ptr: \
             Unable to obtain a context pointer.")
     }
-}
\ No newline at end of file
+}
+
+
+/// A builder for `Queue` supporting priority and throttle hints.
+///
+/// Hints are only applied when the device supports the corresponding
+/// extension (`cl_khr_priority_hints` or `cl_khr_throttle_hints`) and the
+/// `opencl_version_2_0` feature is enabled. Otherwise they are ignored (a
+/// warning is logged when the `trace` feature is enabled). Use
+/// `Queue::priority_hint` and `::throttle_hint` to determine which hints
+/// were applied.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct QueueBuilder {
+    context: Context,
+    device: Device,
+    properties: Option<CommandQueueProperties>,
+    priority: Option<Priority>,
+    throttle: Option<Throttle>,
+}
+
+impl QueueBuilder {
+    /// Returns a new builder for a queue on `device`.
+    pub fn new(context: &Context, device: Device) -> QueueBuilder {
+        QueueBuilder {
+            context: context.clone(),
+            device: device,
+            properties: None,
+            priority: None,
+            throttle: None,
+        }
+    }
+
+    /// Sets the queue properties (such as `QUEUE_PROFILING_ENABLE` or
+    /// `QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE`).
+    pub fn properties(mut self, properties: CommandQueueProperties) -> QueueBuilder {
+        self.properties = Some(properties);
+        self
+    }
+
+    /// Sets a priority hint (`cl_khr_priority_hints`).
+    ///
+    /// Commands on higher priority queues may preempt those on lower
+    /// priority queues.
+    pub fn priority(mut self, priority: Priority) -> QueueBuilder {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Sets a throttle hint (`cl_khr_throttle_hints`).
+    pub fn throttle(mut self, throttle: Throttle) -> QueueBuilder {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Returns the hint if it may be applied, dropping it (with a logged
+    /// warning) otherwise.
+    fn supported_hint<H: std::fmt::Debug>(&self, hint: Option<H>, extension: &str)
+            -> OclResult<Option<H>>
+    {
+        let hint = match hint {
+            Some(h) => h,
+            None => return Ok(None),
+        };
+
+        if cfg!(feature = "opencl_version_2_0") && try!(self.device.supports_extension(extension)) {
+            Ok(Some(hint))
+        } else {
+            #[cfg(feature = "trace")]
+            warn!("ocl::QueueBuilder::build: The queue hint '{:?}' has been ignored. The device \
+                '{}' does not support '{}' (or the 'opencl_version_2_0' feature is disabled).",
+                hint, self.device.name(), extension);
+            Ok(None)
+        }
+    }
+
+    /// Builds and returns a new `Queue`.
+    pub fn build(self) -> OclResult<Queue> {
+        let priority = try!(self.supported_hint(self.priority, "cl_khr_priority_hints"));
+        let throttle = try!(self.supported_hint(self.throttle, "cl_khr_throttle_hints"));
+
+        if priority.is_none() && throttle.is_none() {
+            return Queue::new(&self.context, self.device, self.properties);
+        }
+
+        self.build_with_hints(priority, throttle)
+    }
+
+    #[cfg(feature = "opencl_version_2_0")]
+    fn build_with_hints(&self, priority: Option<Priority>, throttle: Option<Throttle>)
+            -> OclResult<Queue>
+    {
+        let mut properties = Vec::with_capacity(7);
+
+        if let Some(props) = self.properties {
+            properties.extend_from_slice(&[CL_QUEUE_PROPERTIES, props.bits()]);
+        }
+        if let Some(p) = priority {
+            properties.extend_from_slice(&[CL_QUEUE_PRIORITY_KHR, p as u64]);
+        }
+        if let Some(t) = throttle {
+            properties.extend_from_slice(&[CL_QUEUE_THROTTLE_KHR, t as u64]);
+        }
+        properties.push(0);
+
+        Queue::with_property_list(&self.context, self.device, &properties)
+    }
+
+    #[cfg(not(feature = "opencl_version_2_0"))]
+    fn build_with_hints(&self, _: Option<Priority>, _: Option<Throttle>) -> OclResult<Queue> {
+        Queue::new(&self.context, self.device, self.properties)
+    }
+}
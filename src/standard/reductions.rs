@@ -0,0 +1,199 @@
+//! Sum, minimum, and maximum reductions of buffers.
+//!
+//! The reduction kernels are bundled with the crate and are compiled the
+//! first time a reduction of a particular type is run on a queue (or any of
+//! its clones).
+
+use std::cmp;
+use core::{OclPrm, KernelWorkGroupInfo, KernelWorkGroupInfoResult};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Program, Kernel, Buffer, Queue};
+
+
+/// The largest local work size used by a reduction.
+const MAX_LWS: usize = 256;
+
+/// The largest number of work groups used by a single reduction pass.
+const MAX_GROUPS: usize = 256;
+
+static REDUCTION_SRC: &'static str = r#"
+    #define REDUCE_KERNEL(NAME, OP, IDENT)                                              \
+    __kernel void NAME(__global T const* src, __global T* dst, uint len,               \
+            __local T* scratch)                                                         \
+    {                                                                                   \
+        uint lid = get_local_id(0);                                                     \
+        T acc = IDENT;                                                                  \
+                                                                                        \
+        for (uint i = get_global_id(0); i < len; i += get_global_size(0)) {             \
+            acc = OP(acc, src[i]);                                                      \
+        }                                                                               \
+                                                                                        \
+        scratch[lid] = acc;                                                             \
+        barrier(CLK_LOCAL_MEM_FENCE);                                                   \
+                                                                                        \
+        for (uint s = get_local_size(0) / 2; s > 0; s >>= 1) {                          \
+            if (lid < s) { scratch[lid] = OP(scratch[lid], scratch[lid + s]); }         \
+            barrier(CLK_LOCAL_MEM_FENCE);                                               \
+        }                                                                               \
+                                                                                        \
+        if (lid == 0) { dst[get_group_id(0)] = scratch[0]; }                            \
+    }
+
+    #define ADD(a, b) ((a) + (b))
+
+    REDUCE_KERNEL(reduce_sum, ADD, (T)0)
+    REDUCE_KERNEL(reduce_min, min, T_MAX)
+    REDUCE_KERNEL(reduce_max, max, T_MIN)
+"#;
+
+
+/// A primitive type which can be reduced using `Buffer::sum`, `Buffer::min`,
+/// and `Buffer::max`.
+///
+/// Implemented for `f32`, `i32`, and `u32`.
+pub trait ReducePrm: OclPrm {
+    /// Returns the name of the corresponding OpenCL C type.
+    fn cl_type_name() -> &'static str;
+
+    /// Returns OpenCL C expressions for the largest and smallest values of
+    /// this type (used as the identities of the min and max reductions).
+    fn cl_limits() -> (&'static str, &'static str);
+}
+
+impl ReducePrm for f32 {
+    fn cl_type_name() -> &'static str { "float" }
+    fn cl_limits() -> (&'static str, &'static str) { ("INFINITY", "-INFINITY") }
+}
+
+impl ReducePrm for i32 {
+    fn cl_type_name() -> &'static str { "int" }
+    fn cl_limits() -> (&'static str, &'static str) { ("INT_MAX", "INT_MIN") }
+}
+
+impl ReducePrm for u32 {
+    fn cl_type_name() -> &'static str { "uint" }
+    fn cl_limits() -> (&'static str, &'static str) { ("UINT_MAX", "0") }
+}
+
+
+/// Returns the reduction program for `T` cached with `queue`, compiling it
+/// if necessary (see `Queue::reduction_programs`).
+fn reduction_program<T: ReducePrm>(queue: &Queue) -> OclResult<Program> {
+    let key = T::cl_type_name();
+    let mut cache = try!(queue.reduction_programs().lock()
+        .map_err(|_| OclError::from("ocl::reductions: Program cache lock poisoned.")));
+
+    if let Some(program) = cache.get(key) {
+        return Ok(program.clone());
+    }
+
+    let context = queue.context();
+    let (t_max, t_min) = T::cl_limits();
+    let program = try!(Program::builder()
+        .cmplr_opt(format!("-D T={} -D T_MAX={} -D T_MIN={}", T::cl_type_name(), t_max, t_min))
+        .src(REDUCTION_SRC)
+        .build(&context));
+
+    cache.insert(key, program.clone());
+    Ok(program)
+}
+
+/// Reduces `buffer` using the kernel named `kernel_name` with a tree
+/// reduction: each pass reduces the remaining values to one per work group
+/// until a single value remains.
+fn reduce<T: ReducePrm>(buffer: &Buffer<T>, kernel_name: &'static str) -> OclResult<T> {
+    let fn_name = kernel_name.trim_left_matches("reduce_");
+
+    let queue = match buffer.default_queue() {
+        Some(q) => q.clone(),
+        None => return OclError::err_string(format!("ocl::Buffer::{}: This buffer has no \
+            default queue.", fn_name)),
+    };
+
+    if buffer.len() == 0 {
+        return OclError::err_string(format!("ocl::Buffer::{}: This buffer is empty.", fn_name));
+    }
+
+    let program = try!(reduction_program::<T>(&queue));
+
+    let max_wg_size = match try!(Kernel::new(kernel_name, &program))
+            .wg_info(queue.device(), KernelWorkGroupInfo::WorkGroupSize) {
+        KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+        KernelWorkGroupInfoResult::Error(err) => return Err(*err),
+        _ => panic!("ocl::reductions::reduce: Unexpected 'KernelWorkGroupInfoResult' variant."),
+    };
+
+    // The largest power of two no greater than the maximum work group size:
+    let mut lws = 1;
+    while lws * 2 <= cmp::min(max_wg_size, MAX_LWS) { lws *= 2; }
+
+    let group_count = |len: usize| cmp::min((len + lws - 1) / lws, MAX_GROUPS);
+
+    let partials = [
+        try!(Buffer::<T>::builder().queue(queue.clone()).dims(group_count(buffer.len())).build()),
+        try!(Buffer::<T>::builder().queue(queue.clone()).dims(group_count(buffer.len())).build()),
+    ];
+
+    let mut len = buffer.len();
+    let mut result_idx = None;
+
+    while len > 1 || result_idx.is_none() {
+        let dst_idx = result_idx.map(|idx| idx ^ 1).unwrap_or(0);
+        let src = match result_idx {
+            Some(idx) => &partials[idx],
+            None => buffer,
+        };
+        let groups = group_count(len);
+
+        try!(try!(Kernel::new(kernel_name, &program))
+            .queue(queue.clone())
+            .gws(groups * lws)
+            .lws(lws)
+            .arg_buf(src)
+            .arg_buf(&partials[dst_idx])
+            .arg_scl(len as u32)
+            .arg_loc::<T>(lws)
+            .enq());
+
+        len = groups;
+        result_idx = Some(dst_idx);
+    }
+
+    let mut result = [T::default()];
+    try!(partials[result_idx.unwrap_or(0)].read(&mut result[..]).enq());
+    Ok(result[0])
+}
+
+impl<T: ReducePrm> Buffer<T> {
+    /// Returns the sum of every element of this buffer (blocking).
+    ///
+    /// The reduction is run on the buffer's default queue. The bundled
+    /// reduction kernels are compiled the first time a reduction of `T` is
+    /// run on that queue (or a clone of it) and are reused thereafter.
+    ///
+    /// Integer sums wrap on overflow. Floating point sums are computed in a
+    /// different order than a sequential host sum and may differ from one
+    /// slightly.
+    ///
+    /// ### Errors
+    ///
+    /// The buffer must have a default queue and must not be empty.
+    ///
+    pub fn sum(&self) -> OclResult<T> {
+        reduce(self, "reduce_sum")
+    }
+
+    /// Returns the smallest element of this buffer (blocking).
+    ///
+    /// See `::sum` for details.
+    pub fn min(&self) -> OclResult<T> {
+        reduce(self, "reduce_min")
+    }
+
+    /// Returns the largest element of this buffer (blocking).
+    ///
+    /// See `::sum` for details.
+    pub fn max(&self) -> OclResult<T> {
+        reduce(self, "reduce_max")
+    }
+}
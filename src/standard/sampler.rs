@@ -5,6 +5,27 @@ use std::ops::{Deref, DerefMut};
 use core::error::{Result as OclResult};
 use core::{self, Sampler as SamplerCore, AddressingMode, FilterMode, SamplerInfo, SamplerInfoResult};
 use standard::Context;
+#[cfg(feature = "opencl_version_2_0")]
+use ffi;
+#[cfg(feature = "opencl_version_2_0")]
+use num::FromPrimitive;
+#[cfg(feature = "opencl_version_2_0")]
+use core::{Status, ClContextPtr};
+#[cfg(feature = "opencl_version_2_0")]
+use core::error::Error as OclError;
+
+#[cfg(feature = "opencl_version_2_0")]
+const CL_SAMPLER_NORMALIZED_COORDS: u64 = 0x1152;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_SAMPLER_ADDRESSING_MODE: u64 = 0x1153;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_SAMPLER_FILTER_MODE: u64 = 0x1154;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_SAMPLER_MIP_FILTER_MODE_KHR: u64 = 0x1155;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_SAMPLER_LOD_MIN_KHR: u64 = 0x1156;
+#[cfg(feature = "opencl_version_2_0")]
+const CL_SAMPLER_LOD_MAX_KHR: u64 = 0x1157;
 
 /// An image sampler used to process images.
 #[repr(C)]
@@ -56,6 +77,57 @@ impl Sampler {
         Ok(Sampler(sampler_core))
     }
 
+    /// Creates and returns a new sampler for mipmapped images
+    /// (`cl_khr_mipmap_image`).
+    ///
+    /// `mip_filter_mode` selects how mip levels are filtered and `lod_min`
+    /// and `lod_max` clamp the level of detail computed when sampling. See
+    /// `::new` for the remaining arguments.
+    ///
+    /// ### Errors
+    ///
+    /// Every device in `context` must support the `cl_khr_mipmap_image`
+    /// extension.
+    ///
+    #[cfg(feature = "opencl_version_2_0")]
+    pub fn with_mipmaps(context: &Context, normalize_coords: bool,
+            addressing_mode: AddressingMode, filter_mode: FilterMode,
+            mip_filter_mode: FilterMode, lod_min: f32, lod_max: f32) -> OclResult<Sampler>
+    {
+        for device in context.devices() {
+            if !try!(device.supports_extension("cl_khr_mipmap_image")) {
+                return OclError::err_string(format!("ocl::Sampler::with_mipmaps: The device '{}' \
+                    does not support mipmapped images ('cl_khr_mipmap_image').",
+                    device.name()));
+            }
+        }
+
+        // Float values occupy the low 32 bits of their properties:
+        let properties: [ffi::cl_sampler_properties; 13] = [
+            CL_SAMPLER_NORMALIZED_COORDS, normalize_coords as u64,
+            CL_SAMPLER_ADDRESSING_MODE, addressing_mode as u64,
+            CL_SAMPLER_FILTER_MODE, filter_mode as u64,
+            CL_SAMPLER_MIP_FILTER_MODE_KHR, mip_filter_mode as u64,
+            CL_SAMPLER_LOD_MIN_KHR, lod_min.to_bits() as u64,
+            CL_SAMPLER_LOD_MAX_KHR, lod_max.to_bits() as u64,
+            0,
+        ];
+
+        let mut errcode: ffi::cl_int = 0;
+        let sampler_ptr = unsafe {
+            ffi::clCreateSamplerWithProperties(context.as_ptr(), properties.as_ptr(),
+                &mut errcode)
+        };
+
+        if errcode != ffi::CL_SUCCESS as ffi::cl_int {
+            return OclError::err_string(format!("ocl::Sampler::with_mipmaps: \
+                'clCreateSamplerWithProperties' failed with status: {:?} ({}).",
+                Status::from_i32(errcode), errcode));
+        }
+
+        Ok(Sampler(unsafe { SamplerCore::from_raw_create_ptr(sampler_ptr) }))
+    }
+
     /// Returns various kinds of information about the sampler.
     pub fn info(&self, info_kind: SamplerInfo) -> SamplerInfoResult {
         // match core::get_sampler_info(&self.0, info_kind) {
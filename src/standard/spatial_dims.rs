@@ -120,6 +120,50 @@ impl SpatialDims {
     pub fn is_unspecified(&self) -> bool {
         if let SpatialDims::Unspecified = *self { true } else { false }
     }
+
+    /// Returns the element-wise minimum of `self` and `other`.
+    ///
+    /// The result has as many dimensions as the larger of the two.
+    /// Dimensions not specified by one side (including all dimensions of an
+    /// `Unspecified` value) are treated as `1`. Returns `Unspecified` if both
+    /// are unspecified.
+    pub fn component_min<D: Into<SpatialDims>>(&self, other: D) -> SpatialDims {
+        self.zip_with(other.into(), ::std::cmp::min)
+    }
+
+    /// Returns the element-wise maximum of `self` and `other`.
+    ///
+    /// Dimensions are treated as described for `::component_min`.
+    pub fn component_max<D: Into<SpatialDims>>(&self, other: D) -> SpatialDims {
+        self.zip_with(other.into(), ::std::cmp::max)
+    }
+
+    /// Returns `self` with each dimension clamped to the range given by the
+    /// corresponding dimensions of `min` and `max` (i.e. for clamping a
+    /// global work size to device limits).
+    ///
+    /// Equivalent to `self.component_max(min).component_min(max)`, with
+    /// dimensions treated as described for `::component_min`. Where a
+    /// dimension of `min` exceeds that of `max`, `max` wins.
+    pub fn clamp<Dn, Dx>(&self, min: Dn, max: Dx) -> SpatialDims
+            where Dn: Into<SpatialDims>, Dx: Into<SpatialDims>
+    {
+        self.component_max(min).component_min(max)
+    }
+
+    /// Applies `f` to each pair of dimensions, treating unspecified
+    /// dimensions as `1`.
+    fn zip_with<F: Fn(usize, usize) -> usize>(&self, other: SpatialDims, f: F) -> SpatialDims {
+        let lens = |dims: &SpatialDims| dims.to_lens().unwrap_or([1, 1, 1]);
+        let (a, b) = (lens(self), lens(&other));
+
+        match ::std::cmp::max(self.dim_count(), other.dim_count()) {
+            0 => SpatialDims::Unspecified,
+            1 => SpatialDims::One(f(a[0], b[0])),
+            2 => SpatialDims::Two(f(a[0], b[0]), f(a[1], b[1])),
+            _ => SpatialDims::Three(f(a[0], b[0]), f(a[1], b[1]), f(a[2], b[2])),
+        }
+    }
 }
 
 impl MemLen for SpatialDims {
@@ -0,0 +1,223 @@
+//! A double-buffered pipeline for streaming data through a kernel.
+
+use std;
+use core::OclPrm;
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Buffer, Event};
+
+
+/// A single tile of a dataset being streamed through a `StreamPipeline`.
+///
+/// Passed to the per-tile closure of `StreamPipeline::run`.
+#[derive(Debug)]
+pub struct Tile<'p, T: OclPrm + 'p> {
+    idx: usize,
+    len: usize,
+    buffer: &'p Buffer<T>,
+    queue: &'p Queue,
+    upload_event: &'p Event,
+}
+
+impl<'p, T: OclPrm> Tile<'p, T> {
+    /// Returns the index of this tile within the dataset.
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    /// Returns the number of valid elements in this tile.
+    ///
+    /// This is equal to the pipeline tile length for every tile except
+    /// (possibly) the last, which may be shorter.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the device buffer containing this tile's data.
+    ///
+    /// The buffer is always the full tile length. Only the first `::len`
+    /// elements are uploaded and read back.
+    pub fn buffer(&self) -> &Buffer<T> {
+        self.buffer
+    }
+
+    /// Returns the compute queue on which work for this tile should be
+    /// enqueued.
+    pub fn queue(&self) -> &Queue {
+        self.queue
+    }
+
+    /// Returns the event associated with the upload of this tile's data.
+    ///
+    /// Work enqueued for this tile must wait on this event.
+    pub fn upload_event(&self) -> &Event {
+        self.upload_event
+    }
+}
+
+
+/// A double-buffered pipeline which streams a host dataset through the
+/// device one tile at a time, uploading tile `N + 1` while tile `N` is being
+/// processed.
+///
+/// Two device buffers (each one tile long) are used in alternation.
+/// Transfers are issued on a dedicated transfer queue and per-tile work on the
+/// compute queue passed to `::new`. Events are used to order the two.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+pub struct StreamPipeline<T: OclPrm> {
+    compute_queue: Queue,
+    transfer_queue: Queue,
+    tile_len: usize,
+    buffers: [Buffer<T>; 2],
+}
+
+impl<T: OclPrm> StreamPipeline<T> {
+    /// Returns a new pipeline which will process tiles of `tile_len`
+    /// elements using `compute_queue`.
+    ///
+    /// A second queue, used for transfers, is created on the same context
+    /// and device.
+    pub fn new(compute_queue: &Queue, tile_len: usize) -> OclResult<StreamPipeline<T>> {
+        if tile_len == 0 {
+            return OclError::err_string("ocl::StreamPipeline::new: Tile length must be non-zero.");
+        }
+
+        let transfer_queue = try!(Queue::new(&compute_queue.context(), compute_queue.device(),
+            None));
+
+        let buffer_0 = try!(Buffer::<T>::builder()
+            .queue(compute_queue.clone())
+            .dims(tile_len)
+            .build());
+        let buffer_1 = try!(Buffer::<T>::builder()
+            .queue(compute_queue.clone())
+            .dims(tile_len)
+            .build());
+
+        Ok(StreamPipeline {
+            compute_queue: compute_queue.clone(),
+            transfer_queue: transfer_queue,
+            tile_len: tile_len,
+            buffers: [buffer_0, buffer_1],
+        })
+    }
+
+    /// Streams `data` through the device, tile by tile, replacing its
+    /// contents with the processed results.
+    ///
+    /// `tile_fn` is called once per tile, in order, and must enqueue its
+    /// work on `tile.queue()`, waiting on `tile.upload_event()` and storing
+    /// the event associated with its final command in `enew`. Each tile's
+    /// buffer is read back into the corresponding portion of `data` once
+    /// that event completes.
+    ///
+    /// Blocks until all tiles have been read back (including when an error
+    /// occurs part way through).
+    pub fn run<F>(&mut self, data: &mut [T], mut tile_fn: F) -> OclResult<()>
+            where F: FnMut(&Tile<T>, &mut Event) -> OclResult<()>
+    {
+        let result = self.enqueue_tiles(data, &mut tile_fn);
+
+        // `data` must outlive all non-blocking transfers:
+        let finish_result = self.transfer_queue.finish().and(self.compute_queue.finish());
+        result.and(finish_result)
+    }
+
+    /// Enqueues an upload, `tile_fn`, and a read back for each tile.
+    fn enqueue_tiles<F>(&self, data: &mut [T], tile_fn: &mut F) -> OclResult<()>
+            where F: FnMut(&Tile<T>, &mut Event) -> OclResult<()>
+    {
+        let mut tiles: Vec<&mut [T]> = data.chunks_mut(self.tile_len).collect();
+        let tile_count = tiles.len();
+        let mut upload_events = [Event::empty(), Event::empty()];
+
+        if tile_count == 0 {
+            return Ok(());
+        }
+
+        unsafe { try!(self.upload(&tiles[0], 0, &mut upload_events[0])); }
+
+        for idx in 0..tile_count {
+            let buf_idx = idx % 2;
+
+            // Upload the next tile into the other buffer. The in-order
+            // transfer queue ensures that the previous read back from that
+            // buffer has completed first:
+            if idx + 1 < tile_count {
+                unsafe {
+                    try!(self.upload(&tiles[idx + 1], (idx + 1) % 2,
+                        &mut upload_events[(idx + 1) % 2]));
+                }
+            }
+
+            let mut compute_event = Event::empty();
+
+            {
+                let tile = Tile {
+                    idx: idx,
+                    len: tiles[idx].len(),
+                    buffer: &self.buffers[buf_idx],
+                    queue: &self.compute_queue,
+                    upload_event: &upload_events[buf_idx],
+                };
+
+                try!(tile_fn(&tile, &mut compute_event));
+            }
+
+            // Flush so that the read back (on the other queue) can wait:
+            try!(self.compute_queue.flush());
+
+            let tile_data: &mut [T] = &mut tiles[idx];
+            let len = tile_data.len();
+
+            unsafe {
+                try!(self.buffers[buf_idx].read(tile_data)
+                    .queue(&self.transfer_queue)
+                    .block(false)
+                    .len(len)
+                    .ewait(&compute_event)
+                    .enq());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enqueues a non-blocking upload of `src` into the buffer at `buf_idx`
+    /// then flushes the transfer queue (so that the compute queue can wait
+    /// on `enew`).
+    unsafe fn upload(&self, src: &[T], buf_idx: usize, enew: &mut Event) -> OclResult<()> {
+        try!(self.buffers[buf_idx].write(src)
+            .queue(&self.transfer_queue)
+            .block(false)
+            .len(src.len())
+            .enew(enew)
+            .enq());
+
+        self.transfer_queue.flush()
+    }
+
+    /// Returns the tile length.
+    pub fn tile_len(&self) -> usize {
+        self.tile_len
+    }
+
+    /// Returns the compute queue.
+    pub fn compute_queue(&self) -> &Queue {
+        &self.compute_queue
+    }
+
+    /// Returns the transfer queue.
+    pub fn transfer_queue(&self) -> &Queue {
+        &self.transfer_queue
+    }
+}
+
+impl<T: OclPrm> std::fmt::Debug for StreamPipeline<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("StreamPipeline")
+            .field("tile_len", &self.tile_len)
+            .finish()
+    }
+}
@@ -0,0 +1,383 @@
+//! Coarse-grained shared virtual memory (SVM) buffers and commands.
+//!
+//! [UNSTABLE]: Only coarse-grained buffer SVM is currently supported.
+
+use std;
+use std::ptr;
+use std::mem;
+use std::slice;
+use libc::c_void;
+use num::FromPrimitive;
+use ffi::{self, cl_int, cl_event};
+use core::{OclPrm, Status, MapFlags, ClWaitListPtr, ClNullEventPtr};
+use core::error::{Result as OclResult, Error as OclError};
+use standard::{Context, Queue, ClWaitListPtrEnum, ClNullEventPtrEnum};
+
+const CL_DEVICE_SVM_CAPABILITIES: ffi::cl_uint = 0x1053;
+const CL_DEVICE_SVM_COARSE_GRAIN_BUFFER: ffi::cl_bitfield = 1 << 0;
+
+
+/// Returns an error unless every device associated with `context` supports
+/// coarse-grained buffer SVM (`CL_DEVICE_SVM_COARSE_GRAIN_BUFFER`).
+fn check_svm_support(context: &Context, fn_name: &'static str) -> OclResult<()> {
+    for device in context.devices() {
+        let mut caps: ffi::cl_bitfield = 0;
+
+        // Devices predating OpenCL 2.0 do not recognize the query:
+        let errcode = unsafe {
+            ffi::clGetDeviceInfo(device.as_core().as_ptr(), CL_DEVICE_SVM_CAPABILITIES,
+                mem::size_of::<ffi::cl_bitfield>(), &mut caps as *mut _ as *mut c_void,
+                ptr::null_mut())
+        };
+
+        if errcode != ffi::CL_SUCCESS as cl_int ||
+                (caps & CL_DEVICE_SVM_COARSE_GRAIN_BUFFER) == 0
+        {
+            return OclError::err_string(format!("{}: The device '{}' does not support \
+                coarse-grained buffer shared virtual memory (CL_DEVICE_SVM_CAPABILITIES: {:#x}).",
+                fn_name, device.name(), caps));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts an error code into a result.
+fn eval_errcode(errcode: cl_int, fn_name: &'static str) -> OclResult<()> {
+    if errcode == ffi::CL_SUCCESS as cl_int {
+        Ok(())
+    } else {
+        OclError::err_string(format!("ocl::SvmCmd::enq: '{}' failed with status: {:?} ({})",
+            fn_name, Status::from_i32(errcode), errcode))
+    }
+}
+
+
+/// A coarse-grained, `MEM_READ_WRITE` shared virtual memory allocation of
+/// `len` elements of type `T` (`clSVMAlloc`).
+///
+/// The host may only access the contents of a coarse-grained buffer while
+/// the accessed region is mapped (see `Queue::svm_map`). The allocation is
+/// freed (`clSVMFree`) when dropped. Commands using the buffer must have
+/// completed beforehand.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Debug)]
+pub struct SvmBuffer<T: OclPrm> {
+    ptr: *mut T,
+    len: usize,
+    context: Context,
+}
+
+impl<T: OclPrm> SvmBuffer<T> {
+    /// Returns a new SVM buffer with room for `len` elements.
+    ///
+    /// The contents are uninitialized.
+    ///
+    /// ### Errors
+    ///
+    /// Every device associated with `context` must support coarse-grained
+    /// buffer SVM and `len` must be non-zero.
+    ///
+    pub fn new(context: &Context, len: usize) -> OclResult<SvmBuffer<T>> {
+        try!(check_svm_support(context, "ocl::SvmBuffer::new"));
+
+        if len == 0 {
+            return OclError::err_string("ocl::SvmBuffer::new: Length must be non-zero.");
+        }
+
+        let ptr = unsafe {
+            ffi::clSVMAlloc(context.core().as_ptr(), ffi::CL_MEM_READ_WRITE,
+                len * mem::size_of::<T>(), 0)
+        };
+
+        if ptr.is_null() {
+            return OclError::err_string(format!("ocl::SvmBuffer::new: 'clSVMAlloc' failed to \
+                allocate {} bytes.", len * mem::size_of::<T>()));
+        }
+
+        Ok(SvmBuffer {
+            ptr: ptr as *mut T,
+            len: len,
+            context: context.clone(),
+        })
+    }
+
+    /// Returns the contents of this buffer as a slice.
+    ///
+    /// ### Safety
+    ///
+    /// The entire buffer must be mapped for reading (see `Queue::svm_map`)
+    /// and no command may be writing to it.
+    ///
+    pub unsafe fn as_slice(&self) -> &[T] {
+        slice::from_raw_parts(self.ptr, self.len)
+    }
+
+    /// Returns the contents of this buffer as a mutable slice.
+    ///
+    /// ### Safety
+    ///
+    /// The entire buffer must be mapped for writing (see `Queue::svm_map`)
+    /// and no command may be accessing it.
+    ///
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [T] {
+        slice::from_raw_parts_mut(self.ptr, self.len)
+    }
+
+    /// Returns the SVM pointer, usable as a kernel argument
+    /// (`clSetKernelArgSVMPointer`).
+    #[inline]
+    pub fn as_ptr(&self) -> *mut T {
+        self.ptr
+    }
+
+    /// Returns the length of this buffer, in elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this buffer contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the size of this buffer, in bytes.
+    #[inline]
+    pub fn len_bytes(&self) -> usize {
+        self.len * mem::size_of::<T>()
+    }
+
+    /// Returns the context this buffer was allocated within.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+}
+
+impl<T: OclPrm> Drop for SvmBuffer<T> {
+    fn drop(&mut self) {
+        unsafe { ffi::clSVMFree(self.context.core().as_ptr(), self.ptr as *mut c_void); }
+    }
+}
+
+unsafe impl<T: OclPrm> Send for SvmBuffer<T> {}
+unsafe impl<T: OclPrm> Sync for SvmBuffer<T> {}
+
+
+/// The type of an SVM command.
+#[derive(Debug)]
+pub enum SvmCmdKind<'c, T> where T: OclPrm + 'c {
+    Memcpy { dst: &'c SvmBuffer<T>, src: &'c SvmBuffer<T> },
+    Fill { dst: &'c SvmBuffer<T>, pattern: T },
+    Map { buffer: &'c SvmBuffer<T>, flags: MapFlags },
+    Unmap { buffer: &'c SvmBuffer<T> },
+}
+
+
+/// An SVM command builder for copying, filling, mapping, and unmapping
+/// regions of `SvmBuffer`s.
+///
+/// Created using `Queue::svm_memcpy`, `::svm_memfill`, `::svm_map`, and
+/// `::svm_unmap`. Offsets and sizes are specified in bytes. Copy and map
+/// commands block by default.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[must_use = "commands do nothing unless enqueued"]
+pub struct SvmCmd<'c, T> where T: OclPrm + 'c {
+    queue: &'c Queue,
+    kind: SvmCmdKind<'c, T>,
+    bytes: usize,
+    dst_offset: usize,
+    src_offset: usize,
+    block: bool,
+    ewait: Option<ClWaitListPtrEnum<'c>>,
+    enew: Option<ClNullEventPtrEnum<'c>>,
+}
+
+impl<'c, T> SvmCmd<'c, T> where T: OclPrm {
+    /// Returns a new SVM command of `kind` affecting `bytes` bytes.
+    pub fn new(queue: &'c Queue, kind: SvmCmdKind<'c, T>, bytes: usize) -> SvmCmd<'c, T> {
+        SvmCmd {
+            queue: queue,
+            kind: kind,
+            bytes: bytes,
+            dst_offset: 0,
+            src_offset: 0,
+            block: true,
+            ewait: None,
+            enew: None,
+        }
+    }
+
+    /// Specifies the offset, in bytes, into the destination (or mapped)
+    /// buffer.
+    pub fn dst_offset(mut self, dst_offset: usize) -> SvmCmd<'c, T> {
+        self.dst_offset = dst_offset;
+        self
+    }
+
+    /// Specifies the offset, in bytes, into the source buffer of a copy.
+    ///
+    /// ### Panics
+    ///
+    /// Panics unless this is a copy command.
+    ///
+    pub fn src_offset(mut self, src_offset: usize) -> SvmCmd<'c, T> {
+        match self.kind {
+            SvmCmdKind::Memcpy { .. } => self.src_offset = src_offset,
+            _ => panic!("ocl::SvmCmd::src_offset: Only copy commands have a source offset."),
+        }
+        self
+    }
+
+    /// Specifies whether or not to block the current thread until the
+    /// command has completed (default: `true`).
+    ///
+    /// Fill and unmap commands are always enqueued without blocking and are
+    /// followed by a `Queue::finish` when blocking.
+    pub fn block(mut self, block: bool) -> SvmCmd<'c, T> {
+        self.block = block;
+        self
+    }
+
+    /// Specifies a list of events to wait on before the command will run.
+    pub fn ewait<'e, Ewl>(mut self, ewait: Ewl) -> SvmCmd<'c, T>
+            where 'e: 'c, Ewl: Into<ClWaitListPtrEnum<'e>>
+    {
+        self.ewait = Some(ewait.into());
+        self
+    }
+
+    /// Specifies the destination for a new, optionally created event
+    /// associated with this command.
+    pub fn enew<'e, En>(mut self, enew: En) -> SvmCmd<'c, T>
+            where 'e: 'c, En: Into<ClNullEventPtrEnum<'e>>
+    {
+        self.enew = Some(enew.into());
+        self
+    }
+
+    /// Returns an error if `offset` and `self.bytes` exceed the size of
+    /// `buffer`.
+    fn check_region(&self, buffer: &SvmBuffer<T>, offset: usize, name: &str) -> OclResult<()> {
+        if offset + self.bytes > buffer.len_bytes() {
+            OclError::err_string(format!("ocl::SvmCmd::enq: The {} region (offset: {}, size: {} \
+                bytes) exceeds the size of the buffer ({} bytes).", name, offset, self.bytes,
+                buffer.len_bytes()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns an error if the regions or sizes of this command are invalid.
+    fn check(&self) -> OclResult<()> {
+        match self.kind {
+            SvmCmdKind::Memcpy { dst, src } => {
+                try!(self.check_region(dst, self.dst_offset, "destination"));
+                try!(self.check_region(src, self.src_offset, "source"));
+
+                if dst.as_ptr() == src.as_ptr() && self.dst_offset < self.src_offset + self.bytes &&
+                        self.src_offset < self.dst_offset + self.bytes
+                {
+                    return OclError::err_string("ocl::SvmCmd::enq: The source and destination \
+                        regions of a copy must not overlap.");
+                }
+            },
+            SvmCmdKind::Fill { dst, .. } => {
+                try!(self.check_region(dst, self.dst_offset, "destination"));
+
+                if self.bytes % mem::size_of::<T>() != 0 ||
+                        self.dst_offset % mem::size_of::<T>() != 0
+                {
+                    return OclError::err_string(format!("ocl::SvmCmd::enq: The offset ({}) and \
+                        size ({}) of a fill must be multiples of the pattern size ({} bytes).",
+                        self.dst_offset, self.bytes, mem::size_of::<T>()));
+                }
+            },
+            SvmCmdKind::Map { buffer, .. } => {
+                try!(self.check_region(buffer, self.dst_offset, "mapped"));
+            },
+            SvmCmdKind::Unmap { .. } => (),
+        }
+
+        Ok(())
+    }
+
+    /// Enqueues this command.
+    ///
+    /// Nothing is enqueued (and no new event is allocated) if the command is
+    /// invalid.
+    pub fn enq(mut self) -> OclResult<()> {
+        // Validate before allocating a new event so that a failed command
+        // does not leave an empty event behind in an `enew` list:
+        try!(self.check());
+
+        let (wait_count, wait_ptr): (u32, *const cl_event) = match self.ewait {
+            Some(ref el) => unsafe { (el.count(), el.as_ptr_ptr()) },
+            None => (0, ptr::null()),
+        };
+
+        let new_event_ptr = match self.enew {
+            Some(ref mut ne) => ne.alloc_new(),
+            None => ptr::null_mut(),
+        };
+
+        let queue_ptr = self.queue.core().as_ptr();
+        let block = if self.block { ffi::CL_TRUE } else { ffi::CL_FALSE };
+
+        match self.kind {
+            SvmCmdKind::Memcpy { dst, src } => {
+                let dst_ptr = unsafe { (dst.as_ptr() as *mut u8)
+                    .offset(self.dst_offset as isize) };
+                let src_ptr = unsafe { (src.as_ptr() as *const u8)
+                    .offset(self.src_offset as isize) };
+
+                eval_errcode(unsafe { ffi::clEnqueueSVMMemcpy(queue_ptr, block,
+                    dst_ptr as *mut c_void, src_ptr as *const c_void, self.bytes, wait_count,
+                    wait_ptr, new_event_ptr) }, "clEnqueueSVMMemcpy")
+            },
+            SvmCmdKind::Fill { dst, ref pattern } => {
+                let dst_ptr = unsafe { (dst.as_ptr() as *mut u8)
+                    .offset(self.dst_offset as isize) };
+
+                try!(eval_errcode(unsafe { ffi::clEnqueueSVMMemFill(queue_ptr,
+                    dst_ptr as *mut c_void, pattern as *const T as *const c_void,
+                    mem::size_of::<T>(), self.bytes, wait_count, wait_ptr, new_event_ptr) },
+                    "clEnqueueSVMMemFill"));
+
+                if self.block { self.queue.finish() } else { Ok(()) }
+            },
+            SvmCmdKind::Map { buffer, flags } => {
+                let map_ptr = unsafe { (buffer.as_ptr() as *mut u8)
+                    .offset(self.dst_offset as isize) };
+
+                eval_errcode(unsafe { ffi::clEnqueueSVMMap(queue_ptr, block, flags.bits(),
+                    map_ptr as *mut c_void, self.bytes, wait_count, wait_ptr, new_event_ptr) },
+                    "clEnqueueSVMMap")
+            },
+            SvmCmdKind::Unmap { buffer } => {
+                try!(eval_errcode(unsafe { ffi::clEnqueueSVMUnmap(queue_ptr,
+                    buffer.as_ptr() as *mut c_void, wait_count, wait_ptr, new_event_ptr) },
+                    "clEnqueueSVMUnmap"));
+
+                if self.block { self.queue.finish() } else { Ok(()) }
+            },
+        }
+    }
+}
+
+impl<'c, T> std::fmt::Debug for SvmCmd<'c, T> where T: OclPrm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SvmCmd")
+            .field("kind", &self.kind)
+            .field("bytes", &self.bytes)
+            .field("dst_offset", &self.dst_offset)
+            .field("src_offset", &self.src_offset)
+            .field("block", &self.block)
+            .finish()
+    }
+}
@@ -0,0 +1,54 @@
+//! A kernel which may be shared between threads.
+
+use std::sync::{Arc, Mutex};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::Kernel;
+
+
+/// A `Kernel` guarded by a mutex, allowing one kernel to be shared between
+/// and launched from multiple threads.
+///
+/// The argument state of an OpenCL kernel object is not thread safe: if two
+/// threads set arguments on the same kernel, one may enqueue it with
+/// arguments set by the other. `::with_args` holds the lock while the
+/// closure sets arguments and enqueues the kernel, making the two atomic.
+///
+/// Clones share the same underlying kernel (and lock).
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct SyncKernel {
+    kernel: Arc<Mutex<Kernel>>,
+}
+
+impl SyncKernel {
+    /// Returns a new `SyncKernel` wrapping `kernel`.
+    pub fn new(kernel: Kernel) -> SyncKernel {
+        SyncKernel { kernel: Arc::new(Mutex::new(kernel)) }
+    }
+
+    /// Locks the kernel and calls `f` with it, returning its result.
+    ///
+    /// Set arguments and enqueue within `f`, i.e.:
+    /// `sync_kernel.with_args(|k| { k.set_arg(0, &buffer)?; k.enq() })`.
+    /// Other threads calling `::with_args` block until `f` returns.
+    ///
+    /// ### Errors
+    ///
+    /// Returns an error if another thread panicked while holding the lock.
+    ///
+    pub fn with_args<F, R>(&self, f: F) -> OclResult<R>
+            where F: FnOnce(&mut Kernel) -> OclResult<R>
+    {
+        let mut kernel = try!(self.kernel.lock()
+            .map_err(|_| OclError::from("SyncKernel::with_args: Kernel lock poisoned.")));
+        f(&mut kernel)
+    }
+}
+
+impl From<Kernel> for SyncKernel {
+    fn from(kernel: Kernel) -> SyncKernel {
+        SyncKernel::new(kernel)
+    }
+}
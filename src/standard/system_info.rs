@@ -0,0 +1,179 @@
+//! A description of all available platforms and devices.
+
+use std;
+use core::{DeviceInfo, DeviceInfoResult, DeviceType};
+use core::error::{Result as OclResult};
+use standard::{self, Platform, Device};
+
+
+/// Key attributes of a device.
+#[derive(Clone, Debug)]
+pub struct DeviceDesc {
+    /// The device name (`CL_DEVICE_NAME`).
+    pub name: String,
+    /// The device vendor (`CL_DEVICE_VENDOR`).
+    pub vendor: String,
+    /// The device type (`CL_DEVICE_TYPE`).
+    pub device_type: Option<DeviceType>,
+    /// The OpenCL version string (`CL_DEVICE_VERSION`).
+    pub version: String,
+    /// The driver version string (`CL_DRIVER_VERSION`).
+    pub driver_version: String,
+    /// The number of compute units (`CL_DEVICE_MAX_COMPUTE_UNITS`).
+    pub max_compute_units: Option<u32>,
+    /// The maximum work group size (`CL_DEVICE_MAX_WORK_GROUP_SIZE`).
+    pub max_work_group_size: Option<usize>,
+    /// The global memory size in bytes (`CL_DEVICE_GLOBAL_MEM_SIZE`).
+    pub global_mem_size: Option<u64>,
+    /// The local memory size in bytes (`CL_DEVICE_LOCAL_MEM_SIZE`).
+    pub local_mem_size: Option<u64>,
+    /// The largest single allocation in bytes (`CL_DEVICE_MAX_MEM_ALLOC_SIZE`).
+    pub max_mem_alloc_size: Option<u64>,
+    /// Whether or not the device is available (`CL_DEVICE_AVAILABLE`).
+    pub available: Option<bool>,
+    /// The supported extensions (`CL_DEVICE_EXTENSIONS`).
+    pub extensions: Vec<String>,
+}
+
+impl DeviceDesc {
+    /// Returns a description of `device`.
+    ///
+    /// Any attribute which cannot be queried is left as `None` (or empty).
+    pub fn new(device: &Device) -> DeviceDesc {
+        DeviceDesc {
+            name: device.name(),
+            vendor: device.vendor(),
+            device_type: match device.info(DeviceInfo::Type) {
+                DeviceInfoResult::Type(t) => Some(t),
+                _ => None,
+            },
+            version: standard::clean_info_string(device.info(DeviceInfo::Version).to_string()),
+            driver_version: standard::clean_info_string(
+                device.info(DeviceInfo::DriverVersion).to_string()),
+            max_compute_units: match device.info(DeviceInfo::MaxComputeUnits) {
+                DeviceInfoResult::MaxComputeUnits(n) => Some(n),
+                _ => None,
+            },
+            max_work_group_size: device.max_wg_size().ok(),
+            global_mem_size: match device.info(DeviceInfo::GlobalMemSize) {
+                DeviceInfoResult::GlobalMemSize(s) => Some(s),
+                _ => None,
+            },
+            local_mem_size: match device.info(DeviceInfo::LocalMemSize) {
+                DeviceInfoResult::LocalMemSize(s) => Some(s),
+                _ => None,
+            },
+            max_mem_alloc_size: match device.info(DeviceInfo::MaxMemAllocSize) {
+                DeviceInfoResult::MaxMemAllocSize(s) => Some(s),
+                _ => None,
+            },
+            available: device.is_available().ok(),
+            extensions: match device.info(DeviceInfo::Extensions) {
+                DeviceInfoResult::Extensions(exts) => standard::clean_info_string(exts)
+                    .split_whitespace()
+                    .map(|ext| ext.to_owned()).collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+
+/// Key attributes of a platform and descriptions of each of its devices.
+#[derive(Clone, Debug)]
+pub struct PlatformDesc {
+    /// The platform name (`CL_PLATFORM_NAME`).
+    pub name: String,
+    /// The platform vendor (`CL_PLATFORM_VENDOR`).
+    pub vendor: String,
+    /// The OpenCL version string (`CL_PLATFORM_VERSION`).
+    pub version: String,
+    /// The supported profile (`CL_PLATFORM_PROFILE`).
+    pub profile: String,
+    /// Descriptions of each of the platform's devices.
+    pub devices: Vec<DeviceDesc>,
+    /// The error encountered while listing the platform's devices, if any
+    /// (`devices` is empty in that case).
+    pub device_error: Option<String>,
+}
+
+
+/// A description of every platform (and device) available on the system.
+///
+/// Returned by `describe_system`. The `Display` implementation renders a
+/// tree suitable for inclusion in bug reports.
+#[derive(Clone, Debug)]
+pub struct SystemInfo {
+    /// Descriptions of each available platform.
+    pub platforms: Vec<PlatformDesc>,
+}
+
+impl std::fmt::Display for SystemInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn opt<T: std::fmt::Display>(val: &Option<T>) -> String {
+            val.as_ref().map(|v| v.to_string()).unwrap_or_else(|| String::from("<unknown>"))
+        }
+
+        for (p_idx, platform) in self.platforms.iter().enumerate() {
+            try!(writeln!(f, "Platform [{}]: {}", p_idx, platform.name));
+            try!(writeln!(f, "    Vendor: {}", platform.vendor));
+            try!(writeln!(f, "    Version: {}", platform.version));
+            try!(writeln!(f, "    Profile: {}", platform.profile));
+
+            if let Some(ref err) = platform.device_error {
+                try!(writeln!(f, "    Device Error: {}", err));
+            }
+
+            for (d_idx, device) in platform.devices.iter().enumerate() {
+                try!(writeln!(f, "    Device [{}]: {}", d_idx, device.name));
+                try!(writeln!(f, "        Vendor: {}", device.vendor));
+                try!(writeln!(f, "        Type: {}", device.device_type.as_ref()
+                    .map(|t| format!("{:?}", t)).unwrap_or_else(|| String::from("<unknown>"))));
+                try!(writeln!(f, "        Version: {}", device.version));
+                try!(writeln!(f, "        Driver Version: {}", device.driver_version));
+                try!(writeln!(f, "        Available: {}", opt(&device.available)));
+                try!(writeln!(f, "        Max Compute Units: {}", opt(&device.max_compute_units)));
+                try!(writeln!(f, "        Max Work Group Size: {}",
+                    opt(&device.max_work_group_size)));
+                try!(writeln!(f, "        Global Memory Size: {}", opt(&device.global_mem_size)));
+                try!(writeln!(f, "        Local Memory Size: {}", opt(&device.local_mem_size)));
+                try!(writeln!(f, "        Max Allocation Size: {}",
+                    opt(&device.max_mem_alloc_size)));
+                try!(writeln!(f, "        Extensions: {}", device.extensions.join(" ")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+/// Returns a description of all available platforms and their devices.
+///
+/// Intended for diagnostics (such as bug reports). Print the result to
+/// display a readable tree.
+///
+/// A platform whose devices cannot be listed is still described, with the
+/// error recorded in `PlatformDesc::device_error`, so that one failing
+/// platform does not hide the others.
+pub fn describe_system() -> OclResult<SystemInfo> {
+    let mut platforms = Vec::new();
+
+    for platform in Platform::list() {
+        let (devices, device_error) = match Device::list_all(&platform) {
+            Ok(devices) => (devices.iter().map(DeviceDesc::new).collect(), None),
+            Err(err) => (Vec::new(), Some(err.to_string())),
+        };
+
+        platforms.push(PlatformDesc {
+            name: platform.name(),
+            vendor: platform.vendor(),
+            version: platform.version(),
+            profile: platform.profile(),
+            devices: devices,
+            device_error: device_error,
+        });
+    }
+
+    Ok(SystemInfo { platforms: platforms })
+}
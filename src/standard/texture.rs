@@ -0,0 +1,226 @@
+//! A two dimensional texture backed by either an image or, where its format
+//! is unsupported, a buffer.
+
+use core::{OclPrm, Mem as MemCore, AsMem, MemObjectType, ImageFormat, ImageChannelOrder,
+    ImageChannelDataType};
+use core::error::{Error as OclError, Result as OclResult};
+use standard::{Queue, Image, Buffer};
+use super::image::channel_count;
+
+
+/// The storage backing a `Texture`.
+#[derive(Clone, Debug)]
+enum TextureStorage<T: OclPrm> {
+    Image(Image<T>),
+    Buffer(Buffer<T>),
+}
+
+
+/// A two dimensional, `MEM_READ_WRITE` texture stored in an `Image` when its
+/// format is supported by the context and in a tightly packed `Buffer`
+/// otherwise.
+///
+/// Pixels within the buffer-backed representation are stored in row-major
+/// order with each pixel's channels stored contiguously (the same layout
+/// used for host data by `Image`). Use `::index` to compute the element
+/// index of a pixel within a kernel accessing the buffer directly.
+///
+/// The read, write, and sample methods are identical for either storage.
+///
+/// [UNSTABLE]: This type is new and its API may change.
+///
+#[derive(Clone, Debug)]
+pub struct Texture<T: OclPrm> {
+    storage: TextureStorage<T>,
+    dims: [usize; 2],
+    channels: usize,
+}
+
+impl<T: OclPrm> Texture<T> {
+    /// Returns a new texture with the format described by `channel_order`
+    /// and `channel_data_type`.
+    ///
+    /// An image is created if the format is listed by the context's
+    /// `supported_image_formats` (for `MEM_READ_WRITE` 2D images). A buffer
+    /// is created otherwise.
+    pub fn new(queue: &Queue, channel_order: ImageChannelOrder,
+            channel_data_type: ImageChannelDataType, dims: [usize; 2])
+            -> OclResult<Texture<T>>
+    {
+        let supported = try!(queue.context().is_image_format_supported(::flags::MEM_READ_WRITE,
+            MemObjectType::Image2d, channel_order, channel_data_type));
+
+        if supported {
+            let image = try!(Image::<T>::builder()
+                .channel_order(channel_order)
+                .channel_data_type(channel_data_type)
+                .image_type(MemObjectType::Image2d)
+                .dims(dims)
+                .flags(::flags::MEM_READ_WRITE)
+                .queue(queue.clone())
+                .build());
+
+            let channels = image.pixel_element_len();
+
+            Ok(Texture {
+                storage: TextureStorage::Image(image),
+                dims: dims,
+                channels: channels,
+            })
+        } else {
+            Texture::new_buffer_backed(queue, channel_order, channel_data_type, dims)
+        }
+    }
+
+    /// Returns a new buffer-backed texture regardless of whether or not its
+    /// format is supported for images.
+    ///
+    /// Useful for testing the fallback path on devices which support the
+    /// format.
+    pub fn new_buffer_backed(queue: &Queue, channel_order: ImageChannelOrder,
+            channel_data_type: ImageChannelDataType, dims: [usize; 2])
+            -> OclResult<Texture<T>>
+    {
+        if dims[0] == 0 || dims[1] == 0 {
+            return OclError::err_string("ocl::Texture::new_buffer_backed: Dimensions must be \
+                non-zero.");
+        }
+
+        let channels = channel_count(&ImageFormat {
+            channel_order: channel_order,
+            channel_data_type: channel_data_type,
+        });
+
+        let buffer = try!(Buffer::<T>::builder()
+            .queue(queue.clone())
+            .flags(::flags::MEM_READ_WRITE)
+            .dims(dims[0] * dims[1] * channels)
+            .build());
+
+        Ok(Texture {
+            storage: TextureStorage::Buffer(buffer),
+            dims: dims,
+            channels: channels,
+        })
+    }
+
+    /// Returns the element index of the first channel of the pixel at
+    /// (`x`, `y`) within the texture's (buffer-backed or host) layout.
+    #[inline]
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        ((y * self.dims[0]) + x) * self.channels
+    }
+
+    /// Returns an error unless `len` is the length of the entire texture.
+    fn check_len(&self, len: usize, caller: &str) -> OclResult<()> {
+        if len != self.len() {
+            OclError::err_string(format!("ocl::Texture::{}: The length of the host slice ({}) does \
+                not match the length of the texture ({} elements).", caller, len, self.len()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes `data`, the entire texture, from the host (blocking).
+    pub fn write(&self, data: &[T]) -> OclResult<()> {
+        try!(self.check_len(data.len(), "write"));
+
+        match self.storage {
+            TextureStorage::Image(ref image) => image.write(data).enq(),
+            TextureStorage::Buffer(ref buffer) => buffer.write(data).enq(),
+        }
+    }
+
+    /// Reads the entire texture into `data` (blocking).
+    pub fn read(&self, data: &mut [T]) -> OclResult<()> {
+        try!(self.check_len(data.len(), "read"));
+
+        match self.storage {
+            TextureStorage::Image(ref image) => image.read(data).enq(),
+            TextureStorage::Buffer(ref buffer) => buffer.read(data).enq(),
+        }
+    }
+
+    /// Reads the channels of the pixel at (`x`, `y`) (blocking).
+    ///
+    /// This is an (unfiltered) host-side read of a single pixel and is not
+    /// intended for use in tight loops.
+    pub fn sample(&self, x: usize, y: usize) -> OclResult<Vec<T>> {
+        if x >= self.dims[0] || y >= self.dims[1] {
+            return OclError::err_string(format!("ocl::Texture::sample: Coordinates ({}, {}) out of \
+                range (dims: {:?}).", x, y, self.dims));
+        }
+
+        let mut pixel = vec![T::default(); self.channels];
+
+        match self.storage {
+            TextureStorage::Image(ref image) => try!(image.read(&mut pixel)
+                .origin([x, y, 0])
+                .region([1, 1, 1])
+                .enq()),
+            TextureStorage::Buffer(ref buffer) => {
+                let len = pixel.len();
+                try!(buffer.read(&mut pixel)
+                    .offset(self.index(x, y))
+                    .len(len)
+                    .enq())
+            },
+        }
+
+        Ok(pixel)
+    }
+
+    /// Returns true if this texture is stored in a buffer rather than an
+    /// image.
+    pub fn is_buffer_backed(&self) -> bool {
+        match self.storage {
+            TextureStorage::Buffer(_) => true,
+            TextureStorage::Image(_) => false,
+        }
+    }
+
+    /// Returns the underlying image if this texture is image-backed.
+    pub fn image(&self) -> Option<&Image<T>> {
+        match self.storage {
+            TextureStorage::Image(ref image) => Some(image),
+            TextureStorage::Buffer(_) => None,
+        }
+    }
+
+    /// Returns the underlying buffer if this texture is buffer-backed.
+    pub fn buffer(&self) -> Option<&Buffer<T>> {
+        match self.storage {
+            TextureStorage::Buffer(ref buffer) => Some(buffer),
+            TextureStorage::Image(_) => None,
+        }
+    }
+
+    /// Returns the dimensions (width, height) of this texture.
+    pub fn dims(&self) -> [usize; 2] {
+        self.dims
+    }
+
+    /// Returns the number of elements per pixel.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns the total number of elements in this texture.
+    pub fn len(&self) -> usize {
+        self.dims[0] * self.dims[1] * self.channels
+    }
+
+    /// Returns true if this texture contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: OclPrm> AsMem<T> for Texture<T> {
+    fn as_mem(&self) -> &MemCore {
+        match self.storage {
+            TextureStorage::Image(ref image) => image.as_mem(),
+            TextureStorage::Buffer(ref buffer) => buffer.as_mem(),
+        }
+    }
+}
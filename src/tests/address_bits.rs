@@ -0,0 +1,22 @@
+//! Tests verifying memory object sizes against device address bits.
+
+use standard::{self, Platform, Device};
+
+#[test]
+fn address_bits_exceeded() {
+    // A 4 GiB buffer is beyond the reach of a 32-bit device:
+    let err = standard::verify_address_bits(32, 1 << 32).unwrap_err();
+    assert!(err.to_string().contains("exceeds the address space of the device"));
+
+    standard::verify_address_bits(32, (1 << 32) - 1).unwrap();
+    standard::verify_address_bits(64, 1 << 40).unwrap();
+}
+
+#[test]
+fn address_bits_device() {
+    let device = Device::first(Platform::default());
+    let address_bits = device.address_bits().unwrap();
+
+    assert!(address_bits == 32 || address_bits == 64);
+    device.verify_address_bits(1 << 20).unwrap();
+}
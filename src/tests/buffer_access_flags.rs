@@ -0,0 +1,44 @@
+//! Tests setting the device access flags of a buffer with the typed builder
+//! methods.
+
+use core::{self, MemInfo, MemInfoResult, MemFlags};
+use standard::{ProQue, Buffer};
+
+fn mem_flags(buffer: &Buffer<u32>) -> MemFlags {
+    match core::get_mem_object_info(buffer, MemInfo::Flags) {
+        MemInfoResult::Flags(flags) => flags,
+        res => panic!("Unexpected mem info result: {:?}", res),
+    }
+}
+
+#[test]
+fn buffer_access_flags() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(1 << 10)
+        .read_only()
+        .build().unwrap();
+
+    let flags = mem_flags(&buffer);
+    assert!(flags.contains(::flags::MEM_READ_ONLY));
+    assert!(!flags.contains(::flags::MEM_READ_WRITE));
+    assert!(!flags.contains(::flags::MEM_WRITE_ONLY));
+
+    // The last access flag set wins and other flags are preserved:
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_ALLOC_HOST_PTR)
+        .dims(1 << 10)
+        .read_only()
+        .write_only()
+        .build().unwrap();
+
+    let flags = mem_flags(&buffer);
+    assert!(flags.contains(::flags::MEM_WRITE_ONLY | ::flags::MEM_ALLOC_HOST_PTR));
+    assert!(!flags.contains(::flags::MEM_READ_ONLY));
+}
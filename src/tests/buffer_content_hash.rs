@@ -0,0 +1,37 @@
+//! Tests hashing the contents of a buffer.
+
+use standard::{ProQue, Buffer};
+
+fn buffer_from(pro_que: &ProQue, data: &[u32]) -> Buffer<u32> {
+    Buffer::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(data.len())
+        .host_data(data)
+        .build().unwrap()
+}
+
+#[test]
+fn buffer_content_hash() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let data: Vec<u32> = (0..1 << 10).collect();
+    let mut other_data = data.clone();
+    other_data[500] += 1;
+
+    let buffer_a = buffer_from(&pro_que, &data);
+    let buffer_b = buffer_from(&pro_que, &data);
+    let buffer_c = buffer_from(&pro_que, &other_data);
+
+    let hash_a = buffer_a.content_hash().unwrap();
+    assert_eq!(hash_a, buffer_a.content_hash().unwrap());
+    assert_eq!(hash_a, buffer_b.content_hash().unwrap());
+    assert!(hash_a != buffer_c.content_hash().unwrap());
+
+    // Writing matching contents makes the hashes equal:
+    buffer_c.write(&data[..]).enq().unwrap();
+    assert_eq!(hash_a, buffer_c.content_hash().unwrap());
+}
@@ -0,0 +1,52 @@
+//! Tests copying between buffers belonging to different contexts.
+
+use core;
+use standard::{Platform, Device, Context, Queue, Buffer, Event};
+
+#[test]
+fn buffer_copy_to_foreign() {
+    let platform = Platform::default();
+    let devices = Device::list_all(&platform).unwrap();
+
+    if devices.len() < 2 {
+        println!("buffer_copy_to_foreign: Fewer than two devices available. Skipping.");
+        return;
+    }
+
+    let context_a = Context::builder().platform(platform).devices(devices[0]).build().unwrap();
+    let context_b = Context::builder().platform(platform).devices(devices[1]).build().unwrap();
+    let queue_a = Queue::new(&context_a, devices[0], None).unwrap();
+    let queue_b = Queue::new(&context_b, devices[1], None).unwrap();
+
+    let len = 1 << 14;
+    let src_vec: Vec<u32> = (0..len as u32).collect();
+
+    let src_buffer = Buffer::<u32>::builder()
+        .queue(queue_a.clone())
+        .flags(core::MEM_READ_WRITE | core::MEM_COPY_HOST_PTR)
+        .dims(len)
+        .host_data(&src_vec)
+        .build().unwrap();
+
+    let dst_buffer = Buffer::<u32>::builder()
+        .queue(queue_b.clone())
+        .dims(len)
+        .fill_val(0, None::<&mut Event>)
+        .build().unwrap();
+
+    let mut copy_event = Event::empty();
+    src_buffer.copy_to_foreign(&dst_buffer, None::<&Event>, Some(&mut copy_event)).unwrap();
+    copy_event.wait_for().unwrap();
+
+    let mut dst_vec = vec![0u32; len];
+    dst_buffer.read(&mut dst_vec).enq().unwrap();
+    assert_eq!(dst_vec, src_vec);
+
+    // Mismatched lengths:
+    let short_buffer = Buffer::<u32>::builder()
+        .queue(queue_b)
+        .dims(len / 2)
+        .build().unwrap();
+
+    assert!(src_buffer.copy_to_foreign(&short_buffer, None::<&Event>, None::<&mut Event>).is_err());
+}
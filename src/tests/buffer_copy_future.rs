@@ -0,0 +1,37 @@
+//! Tests awaiting the completion of a buffer to buffer copy.
+
+use futures::Future;
+use standard::{ProQue, Buffer};
+
+#[test]
+fn buffer_copy_future() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 14])
+        .build().unwrap();
+
+    let src_data: Vec<u32> = (0..1 << 14).collect();
+
+    let src_buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(1 << 14)
+        .host_data(&src_data)
+        .build().unwrap();
+    let dst_buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let future = src_buffer.copy(&dst_buffer, None, None).into_future().unwrap();
+    future.wait().unwrap();
+
+    let mut vec = vec![0u32; 1 << 14];
+    dst_buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, src_data);
+
+    // A zero-length copy is immediately ready:
+    let empty = src_buffer.copy(&dst_buffer, None, Some(0)).into_future().unwrap();
+    assert!(empty.is_complete().unwrap());
+    empty.wait().unwrap();
+
+    // Only copies are supported:
+    assert!(src_buffer.cmd().into_future().is_err());
+}
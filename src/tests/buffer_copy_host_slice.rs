@@ -0,0 +1,49 @@
+//! Tests creating buffers from various host containers.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void nop(__global int* buffer) {}
+"#;
+
+#[test]
+fn buffer_copy_host_slice() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([8])
+        .build().unwrap();
+
+    let array = [1i32, 2, 3, 4, 5, 6, 7, 8];
+    let vec: Vec<i32> = array.iter().map(|&v| v * 10).collect();
+    let boxed: Box<[i32]> = array.iter().map(|&v| v * 100).collect::<Vec<_>>().into_boxed_slice();
+
+    let from_array = Buffer::<i32>::builder()
+        .queue(pro_que.queue().clone())
+        .copy_host_slice(&array)
+        .build().unwrap();
+
+    let from_vec = Buffer::<i32>::builder()
+        .queue(pro_que.queue().clone())
+        .copy_host_slice(&vec)
+        .build().unwrap();
+
+    let from_boxed = Buffer::<i32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_ONLY)
+        .copy_host_slice(&boxed)
+        .build().unwrap();
+
+    assert!(from_boxed.flags().contains(::flags::MEM_READ_ONLY));
+    assert!(from_boxed.flags().contains(::flags::MEM_COPY_HOST_PTR));
+
+    let mut read = vec![0i32; 8];
+
+    from_array.read(&mut read).enq().unwrap();
+    assert_eq!(&read[..], &array[..]);
+
+    from_vec.read(&mut read).enq().unwrap();
+    assert_eq!(read, vec);
+
+    from_boxed.read(&mut read).enq().unwrap();
+    assert_eq!(&read[..], &boxed[..]);
+}
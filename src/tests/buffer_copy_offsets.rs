@@ -0,0 +1,38 @@
+//! Tests partial buffer copies between specific offsets.
+
+use standard::{ProQue, Buffer};
+
+#[test]
+fn buffer_copy_offsets() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([16])
+        .build().unwrap();
+
+    let src_vals: Vec<u32> = (0..16).collect();
+    let src = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .copy_host_slice(&src_vals)
+        .build().unwrap();
+    let dst = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(8)
+        .fill_val(99u32, None::<()>)
+        .build().unwrap();
+
+    // Copy elements 5..10 of `src` into positions 0..5 of `dst`:
+    src.cmd().copy(&dst, None, None)
+        .src_offset(5)
+        .dst_offset(0)
+        .len(5)
+        .enq().unwrap();
+
+    let mut vec = vec![0u32; dst.len()];
+    dst.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, [5, 6, 7, 8, 9, 99, 99, 99]);
+
+    // Ranges exceeding either buffer are rejected:
+    assert!(src.cmd().copy(&dst, None, None).src_offset(12).len(5).enq().is_err());
+    assert!(src.cmd().copy(&dst, None, None).dst_offset(4).len(5).enq().is_err());
+    assert!(src.copy(&dst, None, None).enq().is_err());
+}
@@ -0,0 +1,34 @@
+//! Tests the overlap check made for copies within a single buffer.
+
+use standard::{ProQue, Buffer};
+
+#[test]
+fn buffer_copy_overlap() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let src: Vec<u32> = (0..1 << 10).collect();
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(1 << 10)
+        .host_data(&src)
+        .build().unwrap();
+
+    // Overlapping ranges are rejected:
+    let err = buffer.copy(&buffer, Some(100), Some(200)).enq().unwrap_err();
+    assert!(err.to_string().contains("overlap"));
+
+    // Disjoint ranges within the same buffer are fine:
+    buffer.copy(&buffer, Some(512), Some(256)).enq().unwrap();
+
+    let mut vec = vec![0u32; 1 << 10];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for idx in 0..256 {
+        assert_eq!(vec[512 + idx], idx as u32);
+    }
+}
@@ -0,0 +1,50 @@
+//! Tests creating a buffer which takes ownership of a `Vec` for use as host
+//! memory.
+
+use standard::{ProQue, Buffer, Kernel};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn buffer_from_vec() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let vec = vec![1.0f32; pro_que.dims().to_len()];
+    let buffer = Buffer::from_vec(pro_que.queue().clone(), vec, None).unwrap();
+
+    {
+        let kernel = Kernel::new("add", pro_que.program()).unwrap()
+            .queue(pro_que.queue().clone())
+            .gws(pro_que.dims().clone())
+            .arg_buf(&buffer)
+            .arg_scl(5.0f32);
+
+        kernel.enq().unwrap();
+
+        // Host memory cannot be reclaimed while another clone exists:
+        let buffer_clone = buffer.clone();
+        assert!(buffer_clone.into_vec().is_err());
+
+        // The failed attempt consumed the clone and the original remains
+        // usable:
+        kernel.enq().unwrap();
+        let mut vec = vec![0.0f32; buffer.len()];
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&val| val == 11.0));
+    }
+
+    // Once the kernel (and its reference to the buffer) has been dropped,
+    // the host memory is returned with the device's changes:
+    let vec = buffer.into_vec().unwrap();
+
+    for &val in vec.iter() {
+        assert_eq!(val, 11.0);
+    }
+}
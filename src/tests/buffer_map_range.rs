@@ -0,0 +1,44 @@
+//! Tests mapping a sub-range of a buffer.
+
+use standard::ProQue;
+
+#[test]
+fn buffer_map_range() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 8])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    {
+        let mut mem_map = buffer.map().write().offset(10).len(10).enq().unwrap();
+        assert_eq!(mem_map.len(), 10);
+
+        for (i, ele) in mem_map.iter_mut().enumerate() {
+            *ele = (i + 10) as u32;
+        }
+
+        mem_map.unmap().enq().unwrap();
+    }
+
+    // Without a length, the remainder of the buffer is mapped:
+    {
+        let mem_map = buffer.map().read().offset(250).enq().unwrap();
+        assert_eq!(mem_map.len(), 6);
+    }
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (i, &ele) in vec.iter().enumerate() {
+        if i >= 10 && i < 20 {
+            assert_eq!(ele, i as u32);
+        } else {
+            assert_eq!(ele, 0);
+        }
+    }
+
+    // Out of range regions are rejected:
+    assert!(buffer.map().read().offset(250).len(10).enq().is_err());
+}
@@ -0,0 +1,51 @@
+//! Tests mapping a buffer sub-region with `MAP_WRITE_INVALIDATE_REGION` to
+//! upload data.
+
+use standard::{ProQue, Buffer};
+
+#[test]
+fn buffer_map_write_invalidate() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_ALLOC_HOST_PTR)
+        .dims(1 << 10)
+        .fill_val(7u32, None::<()>)
+        .build().unwrap();
+
+    let offset = 256;
+    let len = 512;
+
+    {
+        let mut map = buffer.map().write_invalidate().offset(offset).len(len).enq().unwrap();
+        assert!(map.is_write_only());
+        assert!(map.map_flags().contains(::flags::MAP_WRITE_INVALIDATE_REGION));
+        assert_eq!(map.len(), len);
+
+        // Overwrite the entire mapped region:
+        for (idx, val) in map.iter_mut().enumerate() {
+            *val = idx as u32;
+        }
+
+        map.unmap().enq().unwrap();
+    }
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (idx, &val) in vec.iter().enumerate() {
+        if idx >= offset && idx < offset + len {
+            assert_eq!(val, (idx - offset) as u32);
+        } else {
+            assert_eq!(val, 7);
+        }
+    }
+
+    // Read maps are not write only:
+    let map = buffer.map().read().enq().unwrap();
+    assert!(!map.is_write_only());
+}
@@ -0,0 +1,31 @@
+//! Tests using the raw parts of a buffer with the `core` API.
+
+use core::{self, Mem as MemCore, Event as EventCore};
+use standard::ProQue;
+
+#[test]
+fn buffer_raw_parts() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void nop(__global int* buffer) {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let src_vec: Vec<i32> = (0..buffer.len() as i32).collect();
+    buffer.write(&src_vec).enq().unwrap();
+
+    let (mem_ptr, len) = buffer.raw_parts();
+    assert_eq!(len, buffer.len());
+    assert_eq!(mem_ptr, buffer.core().as_ptr());
+
+    // Wrapping the raw handle increments the reference count:
+    let mem_core = unsafe { MemCore::from_raw_copied_ptr(mem_ptr).unwrap() };
+    let mut dst_vec = vec![0i32; len];
+
+    unsafe {
+        core::enqueue_read_buffer(pro_que.queue(), &mem_core, true, 0, &mut dst_vec,
+            None::<EventCore>, None::<&mut EventCore>).unwrap();
+    }
+
+    assert_eq!(dst_vec, src_vec);
+}
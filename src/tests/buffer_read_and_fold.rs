@@ -0,0 +1,52 @@
+//! Tests folding a buffer of per-group partial results on the host.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void partial_sums(__global const uint* data, __global uint* partials,
+            uint group_len)
+    {
+        uint group = get_global_id(0);
+        uint sum = 0;
+
+        for (uint i = 0; i < group_len; i++) {
+            sum += data[(group * group_len) + i];
+        }
+
+        partials[group] = sum;
+    }
+"#;
+
+const GROUP_COUNT: usize = 64;
+const GROUP_LEN: usize = 256;
+
+#[test]
+fn buffer_read_and_fold() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(GROUP_COUNT)
+        .build().unwrap();
+
+    let data: Vec<u32> = (0..(GROUP_COUNT * GROUP_LEN) as u32).collect();
+    let data_buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .copy_host_slice(&data)
+        .build().unwrap();
+    let partials = pro_que.create_buffer::<u32>().unwrap();
+
+    pro_que.create_kernel("partial_sums").unwrap()
+        .arg_buf(&data_buffer)
+        .arg_buf(&partials)
+        .arg_scl(GROUP_LEN as u32)
+        .enq().unwrap();
+
+    // Sum of 0..n:
+    let n = (GROUP_COUNT * GROUP_LEN) as u64;
+    let expected = n * (n - 1) / 2;
+
+    let total = partials.read_and_fold(0u64, |acc, partial| acc + partial as u64).unwrap();
+    assert_eq!(total, expected);
+
+    let max = partials.read_and_fold(0u32, ::std::cmp::max).unwrap();
+    assert_eq!(max, data[(GROUP_COUNT - 1) * GROUP_LEN..].iter().sum::<u32>());
+}
@@ -0,0 +1,20 @@
+//! Tests reading a single element from a buffer.
+
+use standard::ProQue;
+
+#[test]
+fn buffer_read_one() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1024])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<i32>().unwrap();
+    let data: Vec<i32> = (0..1024).map(|i| i * 3).collect();
+    buffer.write(&data[..]).enq().unwrap();
+
+    assert_eq!(buffer.read_one(0).unwrap(), 0);
+    assert_eq!(buffer.read_one(517).unwrap(), 517 * 3);
+    assert_eq!(buffer.read_one(1023).unwrap(), 1023 * 3);
+    assert!(buffer.read_one(1024).is_err());
+}
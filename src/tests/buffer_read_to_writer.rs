@@ -0,0 +1,39 @@
+//! Tests reading buffers into an `io::Write` sink.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void nop(__global uint* buffer) {}
+"#;
+
+#[test]
+fn buffer_read_to_writer() {
+    // Larger than a single chunk (and not a multiple of the chunk length):
+    const LEN: usize = (1 << 18) + 123;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([LEN])
+        .build().unwrap();
+
+    let vals: Vec<u32> = (0..LEN as u32).map(|i| i.wrapping_mul(0x01020304)).collect();
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(LEN)
+        .host_data(&vals)
+        .build().unwrap();
+
+    let mut sink: Vec<u8> = Vec::new();
+    let bytes_written = buffer.read_to_writer(&mut sink).unwrap();
+    assert_eq!(bytes_written, LEN * 4);
+    assert_eq!(sink.len(), LEN * 4);
+
+    if cfg!(target_endian = "little") {
+        for (idx, &val) in vals.iter().enumerate() {
+            let expected = [val as u8, (val >> 8) as u8, (val >> 16) as u8, (val >> 24) as u8];
+            assert_eq!(&sink[idx * 4..idx * 4 + 4], &expected);
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Tests the bundled buffer reductions.
+
+use standard::{ProQue, Buffer, Queue};
+
+static SRC: &'static str = r#"
+    __kernel void nop(__global float* buffer) {}
+"#;
+
+fn buffer_from<T: ::OclPrm>(queue: &Queue, vals: &[T]) -> Buffer<T> {
+    Buffer::<T>::builder()
+        .queue(queue.clone())
+        .flags(::flags::MEM_READ_ONLY | ::flags::MEM_COPY_HOST_PTR)
+        .dims(vals.len())
+        .host_data(vals)
+        .build().unwrap()
+}
+
+#[test]
+fn buffer_reductions() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1])
+        .build().unwrap();
+    let queue = pro_que.queue();
+
+    // Several lengths, including ones requiring multiple passes and ones
+    // which are not multiples of the work group size:
+    for &len in &[1usize, 7, 256, 1000, 1 << 16, (1 << 18) + 3] {
+        let ints: Vec<i32> = (0..len).map(|i| ((i * 7919) % 2001) as i32 - 1000).collect();
+        let int_buf = buffer_from(queue, &ints);
+        assert_eq!(int_buf.sum().unwrap(), ints.iter().fold(0i32, |a, &b| a.wrapping_add(b)));
+        assert_eq!(int_buf.min().unwrap(), *ints.iter().min().unwrap());
+        assert_eq!(int_buf.max().unwrap(), *ints.iter().max().unwrap());
+
+        let uints: Vec<u32> = (0..len).map(|i| ((i * 104729) % 65536) as u32).collect();
+        let uint_buf = buffer_from(queue, &uints);
+        assert_eq!(uint_buf.sum().unwrap(), uints.iter().fold(0u32, |a, &b| a.wrapping_add(b)));
+        assert_eq!(uint_buf.min().unwrap(), *uints.iter().min().unwrap());
+        assert_eq!(uint_buf.max().unwrap(), *uints.iter().max().unwrap());
+
+        // Small integers (exactly representable sums):
+        let floats: Vec<f32> = ints.iter().map(|&v| (v % 16) as f32).collect();
+        let float_buf = buffer_from(queue, &floats);
+        assert_eq!(float_buf.sum().unwrap(), floats.iter().fold(0.0f32, |a, &b| a + b));
+        assert_eq!(float_buf.min().unwrap(), floats.iter().cloned().fold(::std::f32::INFINITY, f32::min));
+        assert_eq!(float_buf.max().unwrap(), floats.iter().cloned().fold(::std::f32::NEG_INFINITY, f32::max));
+    }
+
+    // One program per type is cached with the queue (and released with it):
+    assert_eq!(queue.reduction_programs().lock().unwrap().len(), 3);
+}
@@ -0,0 +1,61 @@
+//! Tests growing and shrinking a buffer while preserving its contents.
+
+use standard::{ProQue, Buffer};
+
+#[test]
+fn buffer_resize() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([100])
+        .build().unwrap();
+
+    let data: Vec<u32> = (0..100).collect();
+    let mut buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(100)
+        .host_data(&data)
+        .build().unwrap();
+
+    buffer.resize(250).unwrap();
+    assert_eq!(buffer.len(), 250);
+    assert!(!buffer.flags().contains(::flags::MEM_COPY_HOST_PTR));
+
+    // The prefix is preserved and the remainder is writable:
+    let extra: Vec<u32> = (100..250).collect();
+    buffer.write(&extra[..]).offset(100).enq().unwrap();
+
+    let mut vec = vec![0u32; 250];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().enumerate().all(|(idx, &val)| val == idx as u32));
+
+    buffer.resize(10).unwrap();
+    let mut vec = vec![0u32; 10];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, (0..10).collect::<Vec<u32>>());
+
+    assert!(buffer.resize(0).is_err());
+}
+
+#[test]
+fn buffer_resize_pinned() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([100])
+        .build().unwrap();
+
+    let mut buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_ALLOC_HOST_PTR)
+        .dims(100)
+        .fill_val(7u32, None::<&mut ::Event>)
+        .build().unwrap();
+
+    // A pinned (host allocated) buffer remains pinned:
+    buffer.resize(200).unwrap();
+    assert!(buffer.flags().contains(::flags::MEM_ALLOC_HOST_PTR));
+
+    let mut vec = vec![0u32; 100];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 7));
+}
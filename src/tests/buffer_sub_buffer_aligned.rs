@@ -0,0 +1,39 @@
+//! Tests creating sub-buffers at automatically aligned offsets.
+
+use standard::ProQue;
+
+#[test]
+fn buffer_sub_buffer_aligned() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 14])
+        .build().unwrap();
+
+    let data: Vec<u32> = (0..1 << 14).collect();
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+    buffer.write(&data[..]).enq().unwrap();
+
+    // Chosen to be unaligned on any device (alignment is a power of two):
+    let desired_offset = 1001;
+    let len = 100;
+
+    let (sub_buffer, residual) = buffer.create_sub_buffer_aligned(None, desired_offset, len)
+        .unwrap();
+
+    assert!(residual <= desired_offset);
+    assert_eq!(sub_buffer.len(), residual + len);
+
+    // The origin must be aligned (in bytes) for every device:
+    let origin_bytes = (desired_offset - residual) * ::std::mem::size_of::<u32>();
+    for device in pro_que.context().devices() {
+        let align_bytes = device.mem_base_addr_align().unwrap() as usize / 8;
+        assert_eq!(origin_bytes % align_bytes, 0);
+    }
+
+    let mut vec = vec![0u32; sub_buffer.len()];
+    sub_buffer.read(&mut vec).enq().unwrap();
+
+    for i in 0..len {
+        assert_eq!(vec[residual + i], (desired_offset + i) as u32);
+    }
+}
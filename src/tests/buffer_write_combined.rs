@@ -0,0 +1,32 @@
+//! Tests creating write-combined (host write only) staging buffers.
+
+use core::{self, MemInfo, MemInfoResult};
+use standard::{ProQue, Buffer};
+
+#[test]
+fn buffer_write_combined() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(1 << 10)
+        .host_write_combined()
+        .build().unwrap();
+
+    let flags = match core::get_mem_object_info(&buffer, MemInfo::Flags) {
+        MemInfoResult::Flags(flags) => flags,
+        res => panic!("Unexpected mem info result: {:?}", res),
+    };
+
+    assert!(flags.contains(::flags::MEM_ALLOC_HOST_PTR));
+    assert!(flags.contains(::flags::MEM_HOST_WRITE_ONLY));
+
+    assert_eq!(buffer.flags(), flags);
+
+    // Mapping for writing is permitted:
+    let mut map = buffer.map().write_invalidate().enq().unwrap();
+    for val in map.iter_mut() { *val = 1.0; }
+}
@@ -0,0 +1,42 @@
+//! Tests the flags selected by `BufferBuilder::prefer_zero_copy`.
+
+use flags;
+use standard::{ProQue, Buffer, BufferBuilder};
+
+#[test]
+fn zero_copy_flags() {
+    let unified = BufferBuilder::<f32>::zero_copy_flags(flags::MEM_READ_WRITE, true);
+    let discrete = BufferBuilder::<f32>::zero_copy_flags(flags::MEM_READ_WRITE, false);
+
+    assert_eq!(unified, flags::MEM_READ_WRITE | flags::MEM_ALLOC_HOST_PTR);
+    assert_eq!(discrete, flags::MEM_READ_WRITE);
+    assert!(unified != discrete);
+
+    // `MEM_USE_HOST_PTR` is never combined with `MEM_ALLOC_HOST_PTR`:
+    let use_host = flags::MEM_READ_WRITE | flags::MEM_USE_HOST_PTR;
+    assert_eq!(BufferBuilder::<f32>::zero_copy_flags(use_host, true), use_host);
+}
+
+#[test]
+fn prefer_zero_copy() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let unified = pro_que.queue().device().is_unified_memory().unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(1 << 10)
+        .prefer_zero_copy()
+        .build().unwrap();
+
+    let buffer_flags = buffer.flags();
+    assert_eq!(buffer_flags.contains(flags::MEM_ALLOC_HOST_PTR), unified);
+
+    buffer.write(&vec![1.0f32; 1 << 10]).enq().unwrap();
+    let mut vec = vec![0.0f32; 1 << 10];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 1.0));
+}
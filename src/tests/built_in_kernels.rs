@@ -0,0 +1,23 @@
+//! Tests listing built-in kernels and creating programs from them.
+
+use standard::{Platform, Device, Context, Program, Kernel};
+
+#[test]
+fn built_in_kernels() {
+    let platform = Platform::default();
+
+    for device in Device::list_all(&platform).unwrap() {
+        let names = device.built_in_kernels().unwrap();
+        assert!(names.iter().all(|name| !name.is_empty() && !name.contains(';')));
+
+        let context = Context::builder().platform(platform).devices(device).build().unwrap();
+        assert!(Program::from_built_in_kernels(&context, &[device], &[]).is_err());
+
+        if let Some(name) = names.first() {
+            let program = Program::from_built_in_kernels(&context, &[device], &[name.as_str()])
+                .unwrap();
+            let kernel = Kernel::new(name.clone(), &program).unwrap();
+            assert_eq!(&kernel.name(), name);
+        }
+    }
+}
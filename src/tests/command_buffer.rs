@@ -0,0 +1,53 @@
+//! Tests recording and replaying a `CommandBuffer`.
+
+use futures::Future;
+use standard::{ProQue, CommandBuffer};
+use async::RwVec;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel void mul(__global float* buffer, float coeff) {
+        buffer[get_global_id(0)] *= coeff;
+    }
+"#;
+
+#[test]
+fn command_buffer_replay() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let add = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let mul = pro_que.create_kernel("mul").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(2.0f32);
+
+    let rw_vec = RwVec::from(vec![0.0f32; buffer.len()]);
+
+    let mut cmd_buf = CommandBuffer::new();
+    cmd_buf.kernel(&add).kernel(&mul).read(&buffer, &rw_vec);
+    assert_eq!(cmd_buf.len(), 3);
+
+    // Each step computes `val = (val + 1) * 2`:
+    let mut expected = 0.0f32;
+
+    for _ in 0..3 {
+        cmd_buf.replay(pro_que.queue()).unwrap().wait_for().unwrap();
+        expected = (expected + 1.0) * 2.0;
+
+        let guard = rw_vec.clone().read().wait().unwrap();
+
+        for &val in guard.iter() {
+            assert_eq!(val, expected);
+        }
+    }
+}
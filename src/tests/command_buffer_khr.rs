@@ -0,0 +1,86 @@
+//! Tests native command buffers (only run on devices supporting the
+//! `cl_khr_command_buffer` extension).
+
+use standard::{ProQue, Event, CommandBufferKhr};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn command_buffer_khr() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    if !CommandBufferKhr::is_supported(&pro_que.queue().device()) {
+        println!("Device does not support 'cl_khr_command_buffer'. Skipping test.");
+        return;
+    }
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(5.0f32);
+
+    let mut cmd_buf = CommandBufferKhr::new(pro_que.queue()).unwrap();
+    cmd_buf.kernel(&kernel).unwrap();
+    cmd_buf.finalize().unwrap();
+
+    for _ in 0..2 {
+        let mut event = Event::empty();
+        cmd_buf.enq(None::<&Event>, Some(&mut event)).unwrap();
+        event.wait_for().unwrap();
+    }
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for &val in vec.iter() {
+        assert_eq!(val, 10.0);
+    }
+}
+
+#[test]
+fn command_buffer_khr_ranges() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    if !CommandBufferKhr::is_supported(&pro_que.queue().device()) {
+        println!("Device does not support 'cl_khr_command_buffer'. Skipping test.");
+        return;
+    }
+
+    let src = pro_que.create_buffer::<f32>().unwrap();
+    let dst = pro_que.create_buffer::<f32>().unwrap();
+    let len = src.len();
+
+    let mut cmd_buf = CommandBufferKhr::new(pro_que.queue()).unwrap();
+
+    // Out of range offsets and lengths are rejected:
+    assert!(cmd_buf.fill(&dst, 1.0f32, len + 1, None).is_err());
+    assert!(cmd_buf.fill(&dst, 1.0f32, len / 2, Some(len)).is_err());
+    assert!(cmd_buf.copy(&src, &dst, len + 1, 0, None).is_err());
+    assert!(cmd_buf.copy(&src, &dst, 0, len / 2, None).is_err());
+
+    // The default length is the remainder after the offset:
+    cmd_buf.fill(&src, 3.0f32, 0, None).unwrap();
+    cmd_buf.fill(&dst, 0.0f32, 0, None).unwrap();
+    cmd_buf.copy(&src, &dst, len / 2, 0, None).unwrap();
+    cmd_buf.finalize().unwrap();
+
+    let mut event = Event::empty();
+    cmd_buf.enq(None::<&Event>, Some(&mut event)).unwrap();
+    event.wait_for().unwrap();
+
+    let mut vec = vec![0.0f32; len];
+    dst.read(&mut vec).enq().unwrap();
+    assert!(vec[..len / 2].iter().all(|&val| val == 3.0));
+    assert!(vec[len / 2..].iter().all(|&val| val == 0.0));
+}
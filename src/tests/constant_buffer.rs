@@ -0,0 +1,40 @@
+//! Tests constant buffers shared across kernel launches.
+
+use standard::{ProQue, ConstantBuffer};
+use prm::Float4;
+
+static SRC: &'static str = r#"
+    __kernel void scale_shift(__constant float4* params, __global float* buffer) {
+        uint idx = get_global_id(0);
+        buffer[idx] = (float)idx * params->x + params->y;
+    }
+"#;
+
+#[test]
+fn constant_buffer_update() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let params = ConstantBuffer::new(pro_que.queue(), &Float4::new(2.0, 1.0, 0.0, 0.0)).unwrap();
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("scale_shift").unwrap()
+        .arg_buf(&params)
+        .arg_buf(&buffer);
+
+    let mut vec = vec![0.0f32; buffer.len()];
+
+    for &(scale, shift) in &[(2.0f32, 1.0f32), (-0.5, 3.0), (0.0, 7.0)] {
+        params.update(&Float4::new(scale, shift, 0.0, 0.0)).unwrap();
+        assert_eq!(params.get().unwrap(), Float4::new(scale, shift, 0.0, 0.0));
+
+        kernel.enq().unwrap();
+        buffer.read(&mut vec).enq().unwrap();
+
+        for (idx, &val) in vec.iter().enumerate() {
+            assert_eq!(val, idx as f32 * scale + shift);
+        }
+    }
+}
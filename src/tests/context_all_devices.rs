@@ -0,0 +1,28 @@
+//! Tests building a context over every GPU on a platform with one queue per
+//! device.
+
+use standard::{Platform, Device, Context};
+
+#[test]
+fn context_all_devices_of_type() {
+    for platform in Platform::list() {
+        let gpus = Device::list(platform, Some(::flags::DEVICE_TYPE_GPU)).unwrap();
+
+        // Guard: skip platforms without GPUs.
+        if gpus.is_empty() {
+            assert!(Context::all_devices_of_type(platform, ::flags::DEVICE_TYPE_GPU).is_err());
+            continue;
+        }
+
+        let context = Context::all_devices_of_type(platform, ::flags::DEVICE_TYPE_GPU).unwrap();
+        assert_eq!(context.devices().len(), gpus.len());
+
+        let queues = context.queues_per_device().unwrap();
+        assert_eq!(queues.len(), gpus.len());
+
+        for (queue, device) in queues.iter().zip(context.devices()) {
+            assert_eq!(queue.device(), device);
+            queue.finish().unwrap();
+        }
+    }
+}
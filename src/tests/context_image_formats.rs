@@ -0,0 +1,19 @@
+//! Tests querying the image formats supported by a context.
+
+use standard::Context;
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+#[test]
+fn context_image_formats() {
+    let context = Context::builder().build().unwrap();
+    let flags = ::flags::MEM_READ_WRITE;
+
+    let formats = context.supported_image_formats(flags, MemObjectType::Image2d).unwrap();
+
+    // RGBA8 is among the minimum set of formats required by the spec:
+    assert!(formats.iter().any(|fmt| fmt.channel_order == ImageChannelOrder::Rgba &&
+        fmt.channel_data_type == ImageChannelDataType::UnormInt8));
+
+    assert!(context.is_image_format_supported(flags, MemObjectType::Image2d,
+        ImageChannelOrder::Rgba, ImageChannelDataType::UnormInt8).unwrap());
+}
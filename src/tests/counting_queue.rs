@@ -0,0 +1,46 @@
+//! Tests counting the commands enqueued through a `CountingQueue`.
+
+use standard::{ProQue, Buffer, CountingQueue};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const ENQ_COUNT: usize = 17;
+
+#[test]
+fn counting_queue() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1 << 10)
+        .build().unwrap();
+
+    let queue = CountingQueue::new(pro_que.queue().clone());
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(1 << 10)
+        .build().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let zeros = vec![0.0f32; 1 << 10];
+    queue.enq(buffer.write(&zeros)).unwrap();
+
+    for _ in 0..ENQ_COUNT - 2 {
+        queue.enq(kernel.cmd()).unwrap();
+    }
+
+    let mut vec = vec![0.0f32; 1 << 10];
+    queue.enq(buffer.read(&mut vec)).unwrap();
+
+    assert_eq!(queue.command_count(), ENQ_COUNT);
+    assert!(vec.iter().all(|&val| val == (ENQ_COUNT - 2) as f32));
+
+    assert_eq!(queue.reset_count(), ENQ_COUNT);
+    assert_eq!(queue.command_count(), 0);
+}
@@ -0,0 +1,56 @@
+//! Tests setting the default on-device queue used for device-side enqueue.
+
+use core::{self, OpenclVersion};
+use standard::{Context, Program, Queue, Kernel, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void parent(__global uint* buffer, uint len) {
+        enqueue_kernel(get_default_queue(), CLK_ENQUEUE_FLAGS_NO_WAIT, ndrange_1D(len),
+            ^{ buffer[get_global_id(0)] = 7; });
+    }
+"#;
+
+#[test]
+fn default_device_queue() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    // Device-side enqueue requires OpenCL 2.0 and setting the default
+    // on-device queue ('clSetDefaultDeviceCommandQueue') requires OpenCL 2.1:
+    if device.version().unwrap() < OpenclVersion::new(2, 1) { return; }
+
+    let host_queue = Queue::new(&context, device, None).unwrap();
+    let device_queue = Queue::new(&context, device, Some(core::QUEUE_ON_DEVICE |
+        core::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE)).unwrap();
+
+    // Queues not created with 'QUEUE_ON_DEVICE' are rejected:
+    assert!(context.set_default_device_queue(&device, &host_queue).is_err());
+
+    context.set_default_device_queue(&device, &device_queue).unwrap();
+    assert!(host_queue.device_default().unwrap().is_some());
+
+    let program = Program::builder()
+        .src(SRC)
+        .devices(device)
+        .cmplr_opt("-cl-std=CL2.0")
+        .build(&context).unwrap();
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(host_queue.clone())
+        .dims(256)
+        .fill_val(0u32, None::<&mut ::Event>)
+        .build().unwrap();
+
+    Kernel::new("parent", &program).unwrap()
+        .queue(host_queue.clone())
+        .gws(1)
+        .arg_buf(&buffer)
+        .arg_scl(256u32)
+        .enq().unwrap();
+
+    host_queue.finish().unwrap();
+
+    let mut vec = vec![0u32; 256];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 7));
+}
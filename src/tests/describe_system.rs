@@ -0,0 +1,30 @@
+//! Tests the platform/device topology description.
+
+use standard::describe_system;
+
+#[test]
+fn describe_system_populated() {
+    let info = describe_system().unwrap();
+    assert!(!info.platforms.is_empty());
+
+    for platform in &info.platforms {
+        assert!(!platform.name.is_empty());
+        // No devices are listed for a platform whose listing failed:
+        assert!(platform.device_error.is_none() || platform.devices.is_empty());
+
+        for device in &platform.devices {
+            assert!(!device.name.is_empty());
+            assert!(device.max_compute_units.map(|n| n > 0).unwrap_or(true));
+        }
+    }
+
+    let tree = info.to_string();
+    assert!(tree.contains("Platform [0]"));
+
+    for platform in &info.platforms {
+        match platform.device_error {
+            Some(ref err) => assert!(tree.contains(err.as_str())),
+            None => assert!(platform.devices.iter().all(|d| tree.contains(&d.name))),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Tests rendering a device capability table.
+
+use core::{DeviceInfo, DeviceInfoResult};
+use standard::{Platform, Device};
+
+#[test]
+fn device_capability_table() {
+    let device = Device::first(Platform::default());
+    let table = device.capability_table().unwrap();
+
+    let compute_units = match device.info(DeviceInfo::MaxComputeUnits) {
+        DeviceInfoResult::MaxComputeUnits(n) => n,
+        res => panic!("Unexpected device info result: {:?}", res),
+    };
+
+    let mut lines = table.lines();
+    let header = lines.next().unwrap();
+    assert!(header.starts_with("Property") && header.ends_with("| Value"));
+    assert!(lines.next().unwrap().contains("-+-"));
+
+    let cu_row = table.lines().find(|line| line.starts_with("Max Compute Units")).unwrap();
+    assert_eq!(cu_row.split('|').nth(1).unwrap().trim(), compute_units.to_string());
+
+    for label in &["Name", "Max Clock Frequency (MHz)", "Global Memory Size (bytes)",
+            "Max Work Group Size", "Max Work Item Sizes", "Extensions"] {
+        assert!(table.lines().any(|line| line.starts_with(label)), "Missing row: {}", label);
+    }
+
+    // All separators are aligned:
+    let sep_idx = header.find('|').unwrap();
+    assert!(table.lines().skip(2).all(|line| line.find('|') == Some(sep_idx)));
+}
@@ -0,0 +1,17 @@
+//! Tests querying every device attribute as a map.
+
+use standard::{Platform, Device};
+
+#[test]
+fn device_info_map() {
+    for platform in Platform::list() {
+        for device in Device::list_all(&platform).unwrap() {
+            let map = device.info_map().unwrap();
+
+            assert!(map.contains_key("Name"));
+            assert!(map.contains_key("MaxComputeUnits"));
+            assert_eq!(map["Name"], device.name());
+            assert!(map["MaxComputeUnits"].parse::<u32>().unwrap() > 0);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Tests querying the free memory of a device.
+
+use standard::{Platform, Device};
+
+#[test]
+fn device_memory_usage() {
+    for platform in Platform::list() {
+        for device in Device::list_all(&platform).unwrap() {
+            if !device.supports_extension("cl_amd_device_attribute_query").unwrap() {
+                assert!(device.memory_usage().is_err());
+                continue;
+            }
+
+            let usage = device.memory_usage().unwrap();
+            assert!(usage.free > 0);
+            assert!(usage.free <= usage.total);
+            assert_eq!(usage.used(), usage.total - usage.free);
+        }
+    }
+}
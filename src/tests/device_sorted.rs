@@ -0,0 +1,24 @@
+//! Tests the stable ordering of devices across enumerations.
+
+use core::ClDeviceIdPtr;
+use standard::{Platform, Device};
+
+#[test]
+fn device_list_all_sorted() {
+    let ptrs = |devices: Vec<Device>| -> Vec<usize> {
+        devices.iter().map(|d| d.as_ptr() as usize).collect()
+    };
+
+    let sorted_0 = ptrs(Device::list_all_sorted().unwrap());
+    let sorted_1 = ptrs(Device::list_all_sorted().unwrap());
+    assert_eq!(sorted_0, sorted_1);
+
+    // Every device on every platform is included exactly once:
+    let mut all: Vec<usize> = Platform::list().iter()
+        .flat_map(|p| ptrs(Device::list_all(p).unwrap()))
+        .collect();
+    let mut sorted = sorted_0.clone();
+    all.sort();
+    sorted.sort();
+    assert_eq!(all, sorted);
+}
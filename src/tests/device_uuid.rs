@@ -0,0 +1,27 @@
+//! Tests querying stable device identifiers.
+
+use standard::{Platform, Device};
+
+#[test]
+fn device_uuid() {
+    for device in Device::list_all(Platform::default()).unwrap() {
+        if !device.supports_extension("cl_khr_device_uuid").unwrap() {
+            assert!(device.uuid().is_err());
+            continue;
+        }
+
+        let uuid = device.uuid().unwrap();
+        assert!(uuid.iter().any(|&b| b != 0));
+        assert_eq!(uuid, device.uuid().unwrap());
+    }
+}
+
+#[test]
+fn device_pci_bus_info() {
+    for device in Device::list_all(Platform::default()).unwrap() {
+        if let Ok(info) = device.pci_bus_info() {
+            assert!(info.device < 32 && info.function < 8);
+            assert_eq!(info, device.pci_bus_info().unwrap());
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Tests the cached platform and device enumeration.
+
+use core::{ClPlatformIdPtr, ClDeviceIdPtr};
+use standard::{Platform, Device};
+
+#[test]
+fn enumeration_cache() {
+    let platform_ptrs = |platforms: Vec<Platform>| -> Vec<usize> {
+        platforms.iter().map(|p| p.as_ptr() as usize).collect()
+    };
+    let device_ptrs = |devices: Vec<Device>| -> Vec<usize> {
+        devices.iter().map(|d| d.as_ptr() as usize).collect()
+    };
+
+    let listed = platform_ptrs(Platform::list());
+    let cached_0 = platform_ptrs(Platform::list_cached());
+    let cached_1 = platform_ptrs(Platform::list_cached());
+    assert_eq!(cached_0, cached_1);
+    assert_eq!(cached_0, listed);
+
+    let platform = Platform::list_cached()[0];
+    let devices_0 = device_ptrs(Device::list_all_cached(&platform).unwrap());
+    let enumerations = Platform::cache_enumerations();
+    let devices_1 = device_ptrs(Device::list_all_cached(&platform).unwrap());
+    assert_eq!(devices_0, devices_1);
+    assert_eq!(devices_0, device_ptrs(Device::list_all(&platform).unwrap()));
+
+    // Cached calls do not enumerate:
+    assert_eq!(Platform::cache_enumerations(), enumerations);
+
+    // Re-enumerates (the device set is unchanged):
+    Platform::invalidate_cache();
+    assert_eq!(platform_ptrs(Platform::list_cached()), listed);
+    assert_eq!(Platform::cache_enumerations(), enumerations + 1);
+    assert_eq!(device_ptrs(Device::list_all_cached(&platform).unwrap()), devices_0);
+    assert_eq!(Platform::cache_enumerations(), enumerations + 2);
+}
@@ -0,0 +1,49 @@
+//! Tests clearing and reusing an `EventList`.
+
+use core::{EventInfo, EventInfoResult};
+use standard::{ProQue, Event, EventList};
+
+static SRC: &'static str = r#"
+    __kernel void nop(__global float* buffer) {}
+"#;
+
+fn ref_count(event: &Event) -> u32 {
+    match event.info(EventInfo::ReferenceCount) {
+        EventInfoResult::ReferenceCount(count) => count,
+        res => panic!("Unexpected 'EventInfoResult': {:?}", res),
+    }
+}
+
+#[test]
+fn event_list_clear_reuse() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .build().unwrap();
+
+    let event = pro_que.queue().enqueue_marker(None::<&Event>).unwrap();
+    pro_que.queue().finish().unwrap();
+    let base_count = ref_count(&event);
+
+    // Enough to exceed the stack-allocated array:
+    let event_count = 20;
+    let mut list = EventList::new();
+
+    for _ in 0..event_count { list.push(event.clone()); }
+    assert_eq!(ref_count(&event), base_count + event_count);
+    let capacity = list.capacity();
+
+    list.clear();
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.capacity(), capacity);
+    assert_eq!(ref_count(&event), base_count);
+
+    // Refill:
+    for _ in 0..event_count { list.push(event.clone()); }
+    assert_eq!(list.len(), event_count as usize);
+    assert_eq!(list.capacity(), capacity);
+    assert_eq!(ref_count(&event), base_count + event_count);
+
+    list.clear();
+    assert_eq!(ref_count(&event), base_count);
+}
@@ -0,0 +1,61 @@
+//! Tests the detection of wait lists referencing events which do not belong
+//! to previously submitted commands, e.g. empty events (requires the
+//! `event_order_debug` feature).
+
+use standard::{Event, EventList};
+use tests;
+
+#[test]
+fn event_order_wait_on_empty_event() {
+    let (_pro_que, buffer, kernel) = tests::pro_que_buffer_kernel::<f32, _>(tests::ADD_SRC,
+        "add", [1 << 10]);
+    let kernel = kernel.arg_scl(1.0f32);
+
+    // `later_event` belongs to the second command but is waited on by the
+    // first (while still empty):
+    let mut later_event = Event::empty();
+    let err = kernel.cmd().ewait(&later_event).enq().unwrap_err();
+    assert!(format!("{}", err).contains("has not yet been enqueued"));
+    kernel.cmd().enew(&mut later_event).enq().unwrap();
+
+    // The same applies to buffer commands:
+    let mut vec = vec![0.0f32; buffer.len()];
+    let err = buffer.read(&mut vec).ewait(&Event::empty()).enq().unwrap_err();
+    assert!(format!("{}", err).contains("has not yet been enqueued"));
+}
+
+#[test]
+fn event_order_wait_on_empty_event_in_list() {
+    let (_pro_que, _buffer, kernel) = tests::pro_que_buffer_kernel::<f32, _>(tests::ADD_SRC,
+        "add", [1 << 10]);
+    let kernel = kernel.arg_scl(1.0f32);
+
+    let mut enqueued_event = Event::empty();
+    kernel.cmd().enew(&mut enqueued_event).enq().unwrap();
+
+    // A slice or list containing any empty event is rejected:
+    let events = [enqueued_event.clone(), Event::empty()];
+    assert!(kernel.cmd().ewait(&events[..]).enq().is_err());
+
+    let mut event_list = EventList::new();
+    event_list.push(enqueued_event);
+    event_list.push(Event::empty());
+    assert!(kernel.cmd().ewait(&event_list).enq().is_err());
+}
+
+#[test]
+fn event_order_wait_on_enqueued_event() {
+    let (_pro_que, buffer, kernel) = tests::pro_que_buffer_kernel::<f32, _>(tests::ADD_SRC,
+        "add", [1 << 10]);
+    let kernel = kernel.arg_scl(1.0f32);
+
+    // Waiting on events of previously enqueued commands (on the same queue)
+    // is unaffected:
+    let mut earlier_event = Event::empty();
+    kernel.cmd().enew(&mut earlier_event).enq().unwrap();
+    kernel.cmd().ewait(&earlier_event).enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).ewait(&earlier_event).enq().unwrap();
+    assert!(vec.iter().all(|&v| v == 2.0));
+}
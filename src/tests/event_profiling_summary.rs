@@ -0,0 +1,83 @@
+//! Tests aggregating profiling times across an event list.
+
+use core::{self, ProfilingInfo, ProfilingInfoResult};
+use standard::{Context, Program, Queue, Kernel, Buffer, Event, EventList};
+
+static SRC: &'static str = r#"
+    __kernel void spin(__global float* buffer, uint iters) {
+        uint idx = get_global_id(0);
+        float val = buffer[idx];
+        for (uint i = 0; i < iters; i++) {
+            val = val * 0.999f + 0.001f;
+        }
+        buffer[idx] = val;
+    }
+"#;
+
+fn start_end(event: &Event) -> (u64, u64) {
+    let start = match event.profiling_info(ProfilingInfo::Start) {
+        ProfilingInfoResult::Start(time) => time,
+        res => panic!("Unexpected profiling info result: {:?}", res),
+    };
+    let end = match event.profiling_info(ProfilingInfo::End) {
+        ProfilingInfoResult::End(time) => time,
+        res => panic!("Unexpected profiling info result: {:?}", res),
+    };
+    (start, end)
+}
+
+#[test]
+fn event_profiling_summary() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let queue = match Queue::new(&context, device, Some(core::QUEUE_PROFILING_ENABLE |
+            core::QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE)) {
+        Ok(q) => q,
+        // Out of order execution is not supported by every device:
+        Err(_) => return,
+    };
+
+    let program = Program::builder().src(SRC).devices(device).build(&context).unwrap();
+    let mut events = EventList::new();
+
+    let buffers: Vec<Buffer<f32>> = (0..2).map(|_| Buffer::builder()
+        .queue(queue.clone())
+        .dims(1 << 14)
+        .build().unwrap()).collect();
+
+    for buffer in buffers.iter() {
+        let mut event = Event::empty();
+
+        Kernel::new("spin", &program).unwrap()
+            .queue(queue.clone())
+            .gws(buffer.len())
+            .arg_buf(buffer)
+            .arg_scl(1u32 << 12)
+            .cmd()
+            .enew(&mut event)
+            .enq().unwrap();
+
+        events.push(event);
+    }
+
+    events.wait_for().unwrap();
+    let summary = events.profiling_summary().unwrap();
+
+    let (start_0, end_0) = start_end(&events.as_slice()[0]);
+    let (start_1, end_1) = start_end(&events.as_slice()[1]);
+
+    assert_eq!(summary.event_count, 2);
+    assert_eq!(summary.total_ns, (end_0 - start_0) + (end_1 - start_1));
+    assert_eq!(summary.earliest_start_ns, ::std::cmp::min(start_0, start_1));
+    assert_eq!(summary.latest_end_ns, ::std::cmp::max(end_0, end_1));
+
+    // Overlapping commands yield a union span shorter than their sum:
+    if start_1 < end_0 && start_0 < end_1 {
+        assert!(summary.span_ns() < summary.total_ns);
+    } else {
+        assert!(summary.span_ns() >= summary.total_ns);
+    }
+
+    assert!(EventList::new().profiling_summary().is_err());
+}
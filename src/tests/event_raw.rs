@@ -0,0 +1,60 @@
+//! Tests converting events to and from raw `cl_event` pointers.
+
+use std::thread;
+use std::time::Duration;
+use core::{EventInfo, EventInfoResult};
+use standard::{ProQue, Event};
+
+/// Returns the (approximate) reference count of `event`.
+///
+/// Reference counts reported by `CL_EVENT_REFERENCE_COUNT` are not reliable
+/// enough to compare exactly and are only checked for sanity.
+fn ref_count(event: &Event) -> u32 {
+    match event.info(EventInfo::ReferenceCount) {
+        EventInfoResult::ReferenceCount(count) => count,
+        res => panic!("Unexpected 'EventInfoResult': {:?}", res),
+    }
+}
+
+#[test]
+fn event_raw_round_trip() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    assert!(Event::empty().as_raw().is_null());
+
+    let gate = Event::user(pro_que.context()).unwrap();
+    let observer = gate.clone();
+
+    // Borrowing returns the underlying pointer:
+    let borrowed = gate.as_raw();
+    assert!(!borrowed.is_null());
+
+    // A round trip preserves the pointer and keeps the event alive:
+    let raw = gate.into_raw();
+    assert_eq!(raw, borrowed);
+    assert!(ref_count(&observer) >= 1);
+
+    let gate = unsafe { Event::from_raw(raw) };
+    assert_eq!(gate.as_raw(), raw);
+    assert!(!gate.is_complete().unwrap());
+
+    // The reconstructed event still waits correctly:
+    let setter = gate.clone();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        setter.set_complete().unwrap();
+    });
+
+    gate.wait_for().unwrap();
+    assert!(observer.is_complete().unwrap());
+    handle.join().unwrap();
+
+    // Dropping the reconstructed event leaves other references usable:
+    drop(gate);
+    assert!(ref_count(&observer) >= 1);
+    assert!(observer.is_complete().unwrap());
+    observer.wait_for().unwrap();
+}
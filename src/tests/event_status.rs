@@ -0,0 +1,37 @@
+//! Tests polling the execution status of an event without blocking.
+
+use std::thread;
+use std::time::{Duration, Instant};
+use standard::{Event, EventStatus};
+use tests;
+
+#[test]
+fn event_status() {
+    let (pro_que, _buffer, kernel) = tests::pro_que_buffer_kernel::<f32, _>(tests::ADD_SRC,
+        "add", [1 << 16]);
+
+    let mut event = Event::empty();
+    assert!(event.status().is_err());
+
+    kernel.arg_scl(1.0f32)
+        .cmd()
+        .enew(&mut event)
+        .enq().unwrap();
+
+    pro_que.queue().flush().unwrap();
+    let start = Instant::now();
+
+    loop {
+        match event.status().unwrap() {
+            EventStatus::Complete => break,
+            EventStatus::Error(errcode) => panic!("Kernel terminated abnormally: {}", errcode),
+            status => assert!(!status.is_finished()),
+        }
+
+        assert!(start.elapsed() < Duration::from_secs(30), "Timed out polling event status.");
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    assert_eq!(EventStatus::from_raw(-5), EventStatus::Error(-5));
+    assert!(EventStatus::from_raw(-5).is_finished());
+}
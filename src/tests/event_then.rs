@@ -0,0 +1,55 @@
+//! Tests chaining commands with `Event::then`.
+
+use standard::{ProQue, Buffer, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const LEN: usize = 1 << 12;
+
+#[test]
+fn event_then_write_kernel_read() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(LEN)
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(LEN)
+        .build().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(5.0f32);
+
+    let mut write_event = Event::empty();
+    buffer.write(&vec![10.0f32; LEN]).enew(&mut write_event).enq().unwrap();
+
+    // The read back destination must outlive the (non-blocking) read:
+    let mut vec = vec![0.0f32; LEN];
+    let vec_addr = vec.as_mut_ptr() as usize;
+    let read_buffer = buffer.clone();
+
+    let done = write_event
+        .then(pro_que.queue().clone(), move |queue| {
+            let mut event = Event::empty();
+            try!(kernel.cmd().queue(queue).enew(&mut event).enq());
+            Ok(event)
+        }).unwrap()
+        .then(pro_que.queue().clone(), move |queue| {
+            let mut event = Event::empty();
+            unsafe {
+                let dst = ::std::slice::from_raw_parts_mut(vec_addr as *mut f32, LEN);
+                try!(read_buffer.read(dst).queue(queue).block(false).enew(&mut event).enq());
+            }
+            Ok(event)
+        }).unwrap();
+
+    done.wait_for().unwrap();
+
+    assert!(vec.iter().all(|&val| val == 15.0));
+}
@@ -0,0 +1,36 @@
+//! Tests waiting on events with a timeout.
+
+use std::time::{Duration, Instant};
+use standard::Event;
+use tests;
+
+#[test]
+fn event_wait_for_timeout() {
+    let (pro_que, _buffer, kernel) = tests::pro_que_buffer_kernel::<f32, _>(tests::ADD_SRC,
+        "add", [1 << 10]);
+    let kernel = kernel.arg_scl(1.0f32);
+
+    // Fast:
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    assert!(event.wait_for_timeout(Duration::from_secs(10)).unwrap());
+
+    // Already complete:
+    assert!(event.wait_for_timeout(Duration::from_millis(0)).unwrap());
+
+    // Delayed (blocked on a user event which is not completed in time):
+    let gate = Event::user(pro_que.context()).unwrap();
+    let mut blocked_event = Event::empty();
+    kernel.cmd().ewait(&gate).enew(&mut blocked_event).enq().unwrap();
+    pro_que.queue().flush().unwrap();
+
+    let start = Instant::now();
+    assert!(!blocked_event.wait_for_timeout(Duration::from_millis(100)).unwrap());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    gate.set_complete().unwrap();
+    assert!(blocked_event.wait_for_timeout(Duration::from_secs(10)).unwrap());
+
+    // Empty:
+    assert!(Event::empty().wait_for_timeout(Duration::from_millis(1)).is_err());
+}
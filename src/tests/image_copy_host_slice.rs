@@ -0,0 +1,73 @@
+//! Tests creating an image initialized from a host slice.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+#[test]
+fn image_copy_host_slice() {
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([2, 2])
+        .build().unwrap();
+
+    let pixels: Vec<u8> = (0..16).collect();
+
+    let image = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims([2, 2])
+        .copy_host_slice(&pixels)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let mut read_vec = vec![0u8; 16];
+    image.read(&mut read_vec).enq().unwrap();
+    assert_eq!(read_vec, pixels);
+
+    // Too short for a 2x2 RGBA8 image:
+    assert!(Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims([2, 2])
+        .copy_host_slice(&pixels[..12])
+        .queue(proque.queue().clone())
+        .build().is_err());
+}
+
+#[test]
+fn image_copy_host_slice_vector_pixels() {
+    use prm::Uchar4;
+
+    let proque = ProQue::builder()
+        .src("__kernel void nop() {}")
+        .dims([2, 2])
+        .build().unwrap();
+
+    // One `Uchar4` per RGBA8 pixel:
+    let pixels: Vec<Uchar4> = (0..4u8).map(|i| Uchar4::new(i, i + 1, i + 2, i + 3)).collect();
+
+    let image = Image::<Uchar4>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims([2, 2])
+        .copy_host_slice(&pixels)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let mut read_vec = vec![Uchar4::new(0, 0, 0, 0); 4];
+    image.read(&mut read_vec).enq().unwrap();
+    assert_eq!(read_vec, pixels);
+
+    // Too short for a 2x2 RGBA8 image:
+    assert!(Image::<Uchar4>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims([2, 2])
+        .copy_host_slice(&pixels[..3])
+        .queue(proque.queue().clone())
+        .build().is_err());
+}
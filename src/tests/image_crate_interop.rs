@@ -0,0 +1,22 @@
+//! Tests round-tripping an `image` crate image through an OpenCL image.
+
+use image::{DynamicImage, ImageBuffer, Rgb};
+use standard::{ProQue, Image};
+
+#[test]
+fn image_crate_round_trip() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    // An RGB (non-RGBA) source image, converted on upload:
+    let rgb = ImageBuffer::from_fn(16, 8, |x, y| Rgb([x as u8 * 16, y as u8 * 32, 200]));
+    let src = DynamicImage::ImageRgb8(rgb);
+
+    let img = Image::from_dynamic_image(proque.queue(), &src).unwrap();
+    assert_eq!(img.dims().to_lens().unwrap(), [16, 8, 1]);
+
+    let read_back = img.to_rgba_image().unwrap();
+    assert_eq!(read_back.into_raw(), src.to_rgba().into_raw());
+}
@@ -0,0 +1,32 @@
+//! Tests `Device::image_limits` and the validation of image dimensions
+//! against those limits.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+#[test]
+fn image_limits() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let limits = proque.queue().device().image_limits().unwrap();
+
+    if limits.image2d_max_width == 0 {
+        // No image support.
+        return;
+    }
+
+    assert!(limits.image2d_max_height > 0);
+
+    let err = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims([limits.image2d_max_width + 1, 1])
+        .queue(proque.queue().clone())
+        .build().unwrap_err();
+
+    assert!(err.to_string().contains("exceeds the maximum supported by the device"));
+}
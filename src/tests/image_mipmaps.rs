@@ -0,0 +1,69 @@
+//! Tests writing and reading individual levels of a mipmapped image.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+const DIMS: [usize; 2] = [32, 16];
+
+#[test]
+fn image_mipmaps() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(DIMS)
+        .build().unwrap();
+
+    let builder = || Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(DIMS)
+        .num_mip_levels(3)
+        .queue(proque.queue().clone());
+
+    if !proque.queue().device().supports_extension("cl_khr_mipmap_image").unwrap() {
+        assert!(builder().build().is_err());
+        return;
+    }
+
+    let img = builder().build().unwrap();
+
+    // Level 1 is half the size in each dimension:
+    let level_len = (DIMS[0] / 2) * (DIMS[1] / 2) * 4;
+    let level_1: Vec<u8> = (0..level_len).map(|i| (i % 251) as u8).collect();
+    img.write(&level_1).mip_level(1).enq().unwrap();
+
+    let mut read_back = vec![0u8; level_len];
+    img.read(&mut read_back).mip_level(1).enq().unwrap();
+    assert_eq!(read_back, level_1);
+
+    // Level 0 is unaffected:
+    let mut level_0 = vec![1u8; DIMS[0] * DIMS[1] * 4];
+    img.write(&vec![0u8; level_0.len()]).enq().unwrap();
+    img.read(&mut level_0).enq().unwrap();
+    assert!(level_0.iter().all(|&val| val == 0));
+    img.read(&mut read_back).mip_level(1).enq().unwrap();
+    assert_eq!(read_back, level_1);
+}
+
+#[test]
+fn image_mipmaps_context_only() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(DIMS)
+        .build().unwrap();
+
+    // Without a queue, every device in the context is checked:
+    let supported = proque.context().devices().iter()
+        .all(|device| device.supports_extension("cl_khr_mipmap_image").unwrap());
+
+    let res = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(DIMS)
+        .num_mip_levels(3)
+        .context(proque.context())
+        .build();
+
+    assert_eq!(res.is_ok(), supported);
+}
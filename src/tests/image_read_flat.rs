@@ -0,0 +1,45 @@
+//! Tests reading an entire image into a flat vector.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+static SRC: &'static str = r#"
+    __kernel void fill(write_only image2d_t img) {
+        int2 coord = (int2)(get_global_id(0), get_global_id(1));
+        uint4 pixel = (uint4)(coord.x, coord.y, coord.x + coord.y, 255);
+        write_imageui(img, coord, pixel);
+    }
+"#;
+
+const DIMS: [usize; 2] = [24, 10];
+
+#[test]
+fn image_read_flat() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims(DIMS)
+        .build().unwrap();
+
+    let img = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(DIMS)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    proque.create_kernel("fill").unwrap()
+        .arg_img(&img)
+        .enq().unwrap();
+
+    let flat = img.read_flat().unwrap();
+    assert_eq!(flat.len(), DIMS[0] * DIMS[1] * 4);
+    assert_eq!(flat.len(), img.element_count());
+
+    for y in 0..DIMS[1] {
+        for x in 0..DIMS[0] {
+            let idx = (y * DIMS[0] + x) * 4;
+            assert_eq!(&flat[idx..idx + 4], &[x as u8, y as u8, (x + y) as u8, 255]);
+        }
+    }
+}
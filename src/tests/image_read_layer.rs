@@ -0,0 +1,45 @@
+//! Tests reading individual layers of a 2D image array.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+const DIMS: [usize; 2] = [16, 8];
+const LAYERS: usize = 3;
+
+#[test]
+fn image_read_layer() {
+    let proque = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims(DIMS)
+        .build().unwrap();
+
+    let layer_len = DIMS[0] * DIMS[1] * 4;
+
+    // Each layer is filled with its own index:
+    let data: Vec<u8> = (0..LAYERS * layer_len).map(|i| (i / layer_len) as u8).collect();
+
+    let img = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnsignedInt8)
+        .image_type(MemObjectType::Image2dArray)
+        .dims(DIMS)
+        .array_size(LAYERS)
+        .copy_host_slice(&data)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    let mut layers = vec![vec![0xFFu8; layer_len]; LAYERS];
+
+    for (layer_idx, layer) in layers.iter_mut().enumerate() {
+        img.read_layer(layer_idx, layer).unwrap().enq().unwrap();
+    }
+
+    for (layer_idx, layer) in layers.iter().enumerate() {
+        assert!(layer.iter().all(|&val| val == layer_idx as u8));
+    }
+
+    // Out of range layers and incorrectly sized destinations are rejected:
+    let mut layer = vec![0u8; layer_len];
+    assert!(img.read_layer(LAYERS, &mut layer).is_err());
+    assert!(img.read_layer(0, &mut layer[1..]).is_err());
+}
@@ -0,0 +1,46 @@
+//! Tests writing host data to images with channel data type verification.
+
+use standard::{ProQue, Image};
+use enums::{ImageChannelOrder, ImageChannelDataType, MemObjectType};
+
+static SRC: &'static str = r#"
+    __kernel void nop(__global float* buffer) {}
+"#;
+
+const DIMS: [usize; 2] = [32, 16];
+
+#[test]
+fn image_write_checked() {
+    let proque = ProQue::builder()
+        .src(SRC)
+        .dims(DIMS)
+        .build().unwrap();
+
+    let vec = vec![7u8; proque.dims().to_len() * 4];
+
+    // Matching:
+    let img_unorm = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::UnormInt8)
+        .image_type(MemObjectType::Image2d)
+        .dims(DIMS)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    img_unorm.write_checked(&vec).unwrap().enq().unwrap();
+
+    let mut read_vec = vec![0u8; vec.len()];
+    img_unorm.read(&mut read_vec).enq().unwrap();
+    assert_eq!(read_vec, vec);
+
+    // Mismatched (`u8` data to a `Float` image):
+    let img_float = Image::<u8>::builder()
+        .channel_order(ImageChannelOrder::Rgba)
+        .channel_data_type(ImageChannelDataType::Float)
+        .image_type(MemObjectType::Image2d)
+        .dims(DIMS)
+        .queue(proque.queue().clone())
+        .build().unwrap();
+
+    assert!(img_float.write_checked(&vec).is_err());
+}
@@ -0,0 +1,44 @@
+//! Tests the cleanup of strings returned by info queries.
+
+use standard::{self, Platform, Device};
+
+#[test]
+fn clean_info_string_mock_blobs() {
+    let blobs: &[(&[u8], &str)] = &[
+        (b"GeForce GTX 1080", "GeForce GTX 1080"),
+        (b"GeForce GTX 1080\0", "GeForce GTX 1080"),
+        (b"GeForce GTX 1080\0\0\0", "GeForce GTX 1080"),
+        (b"GeForce GTX 1080  \0", "GeForce GTX 1080"),
+        (b"GeForce GTX 1080 \n", "GeForce GTX 1080"),
+        (b"cl_khr_fp64 cl_khr_gl_sharing \0garbage", "cl_khr_fp64 cl_khr_gl_sharing"),
+        (b"  Leading Kept\0", "  Leading Kept"),
+        (b"", ""),
+        (b"\0", ""),
+    ];
+
+    for &(blob, expected) in blobs {
+        let raw = String::from_utf8(blob.to_vec()).unwrap();
+        assert_eq!(standard::clean_info_string(raw), expected);
+    }
+}
+
+#[test]
+fn info_strings_clean() {
+    fn assert_clean(info: &str) {
+        assert!(!info.contains('\0'), "Info string contains a null byte: {:?}", info);
+        assert_eq!(info, info.trim_right(), "Info string has trailing whitespace: {:?}", info);
+    }
+
+    for platform in Platform::list() {
+        assert_clean(&platform.name());
+        assert_clean(&platform.vendor());
+        assert_clean(&platform.version());
+        assert_clean(&platform.profile());
+        assert_clean(&platform.extensions());
+
+        for device in Device::list_all(&platform).unwrap() {
+            assert_clean(&device.name());
+            assert_clean(&device.vendor());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Tests joining several events into a single future.
+
+use futures::Future;
+use standard::{ProQue, Buffer, Event, join_events};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn join_kernel_events() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1 << 14)
+        .build().unwrap();
+
+    let buffers: Vec<Buffer<f32>> = (0..2).map(|_| {
+        Buffer::<f32>::builder()
+            .queue(pro_que.queue().clone())
+            .dims(1 << 14)
+            .fill_val(0.0f32, None::<()>)
+            .build().unwrap()
+    }).collect();
+
+    let mut events = Vec::new();
+
+    for (idx, buffer) in buffers.iter().enumerate() {
+        let kernel = pro_que.create_kernel("add").unwrap()
+            .arg_buf(buffer)
+            .arg_scl((idx + 1) as f32);
+
+        let mut event = Event::empty();
+        kernel.cmd().enew(&mut event).enq().unwrap();
+        events.push(event);
+    }
+
+    pro_que.queue().flush().unwrap();
+    join_events(events.clone()).wait().unwrap();
+
+    for event in events.iter() {
+        assert!(event.is_complete().unwrap());
+    }
+
+    for (idx, buffer) in buffers.iter().enumerate() {
+        let mut vec = vec![0.0f32; buffer.len()];
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&val| val == (idx + 1) as f32));
+    }
+}
+
+#[test]
+fn join_no_events() {
+    join_events(Vec::new()).wait().unwrap();
+}
@@ -0,0 +1,53 @@
+//! Tests setting `bool` and enum kernel arguments.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void select(__global int* buffer, int negate, int mode) {
+        int val = negate ? -1 : 1;
+        buffer[get_global_id(0)] = val * (mode + 1);
+    }
+"#;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Single = 0,
+    Triple = 2,
+}
+
+impl From<Mode> for i32 {
+    fn from(mode: Mode) -> i32 {
+        mode as i32
+    }
+}
+
+#[test]
+fn kernel_arg_bool() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(64)
+        .build().unwrap();
+
+    let buffer = Buffer::<i32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(64)
+        .build().unwrap();
+
+    let mut kernel = pro_que.create_kernel("select").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(0i32)
+        .arg_scl(0i32);
+
+    let mut vec = vec![0i32; 64];
+
+    for &(negate, mode, expected) in &[(false, Mode::Single, 1), (true, Mode::Single, -1),
+        (true, Mode::Triple, -3), (false, Mode::Triple, 3)]
+    {
+        kernel.set_arg_bool(1, negate).unwrap();
+        kernel.set_arg_enum(2, mode).unwrap();
+        kernel.enq().unwrap();
+
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&val| val == expected));
+    }
+}
@@ -0,0 +1,78 @@
+//! Tests setting a list of buffers to consecutive kernel arguments.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void fill(float base, float step, __global float* a, __global float* b,
+            __global float* c)
+    {
+        uint idx = get_global_id(0);
+        a[idx] = base;
+        b[idx] = base + step;
+        c[idx] = base + step * 2.0f;
+    }
+
+    __kernel void pair(__global float* a, __global float* b, int val) {
+        uint idx = get_global_id(0);
+        a[idx] = (float)val;
+        b[idx] = (float)val;
+    }
+"#;
+
+#[test]
+fn kernel_arg_buffers() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([256])
+        .build().unwrap();
+
+    let buffers: Vec<Buffer<f32>> = (0..3).map(|_| pro_que.create_buffer().unwrap()).collect();
+
+    let mut kernel = pro_que.create_kernel("fill").unwrap()
+        .arg_scl(10.0f32)
+        .arg_scl(1.0f32)
+        .arg_buf_named::<f32, Buffer<f32>>("a", None::<Buffer<f32>>)
+        .arg_buf_named::<f32, Buffer<f32>>("b", None::<Buffer<f32>>)
+        .arg_buf_named::<f32, Buffer<f32>>("c", None::<Buffer<f32>>);
+
+    // Too many buffers for the remaining argument slots:
+    assert!(kernel.set_arg_buffers(3, &[&buffers[0], &buffers[1], &buffers[2]]).is_err());
+
+    kernel.set_arg_buffers(2, &[&buffers[0], &buffers[1], &buffers[2]]).unwrap();
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0.0f32; pro_que.dims().to_len()];
+    for (i, buffer) in buffers.iter().enumerate() {
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&val| val == 10.0 + i as f32));
+    }
+}
+
+#[test]
+fn kernel_arg_buffers_type_mismatch() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([256])
+        .build().unwrap();
+
+    let buffers: Vec<Buffer<f32>> = (0..3).map(|_| pro_que.create_buffer().unwrap()).collect();
+    for buffer in buffers.iter() {
+        buffer.cmd().fill(0.0, None).enq().unwrap();
+    }
+
+    let mut kernel = pro_que.create_kernel("pair").unwrap()
+        .arg_buf(&buffers[1])
+        .arg_buf(&buffers[2])
+        .arg_scl(5i32);
+
+    // The third argument is not a float buffer. No argument may be set:
+    assert!(kernel.set_arg_buffers(0, &[&buffers[0], &buffers[0], &buffers[0]]).is_err());
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0.0f32; pro_que.dims().to_len()];
+    for (i, buffer) in buffers.iter().enumerate() {
+        buffer.read(&mut vec).enq().unwrap();
+        let expected = if i == 0 { 0.0 } else { 5.0 };
+        assert!(vec.iter().all(|&val| val == expected));
+    }
+}
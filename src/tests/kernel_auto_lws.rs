@@ -0,0 +1,56 @@
+//! Tests the automatic local work size heuristic.
+
+use core::{KernelWorkGroupInfo, KernelWorkGroupInfoResult};
+use standard::{ProQue, SpatialDims};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn kernel_auto_lws() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 16])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let device = pro_que.queue().device();
+    let max_size = match kernel.wg_info(device, KernelWorkGroupInfo::WorkGroupSize) {
+        KernelWorkGroupInfoResult::WorkGroupSize(s) => s,
+        res => panic!("Unexpected 'KernelWorkGroupInfoResult': {:?}", res),
+    };
+    let multiple = match kernel.wg_info(device,
+            KernelWorkGroupInfo::PreferredWorkGroupSizeMultiple) {
+        KernelWorkGroupInfoResult::PreferredWorkGroupSizeMultiple(s) => s,
+        res => panic!("Unexpected 'KernelWorkGroupInfoResult': {:?}", res),
+    };
+
+    // A global size which factors nicely:
+    let gws = multiple * 64;
+    assert!(gws <= buffer.len());
+    match kernel.auto_lws(gws) {
+        SpatialDims::One(lws) => {
+            assert_eq!(gws % lws, 0);
+            assert_eq!(lws % multiple, 0);
+            assert!(lws <= max_size);
+
+            let kernel = kernel.clone().gws(gws).lws(lws);
+            kernel.enq().unwrap();
+        },
+        dims => panic!("Unexpected local work size: {:?}", dims),
+    }
+
+    // A prime global size (whose only divisor no greater than the maximum
+    // is one, even when the preferred multiple is one):
+    match kernel.auto_lws(1_000_003) {
+        SpatialDims::Unspecified => (),
+        dims => panic!("Unexpected local work size: {:?}", dims),
+    }
+}
@@ -0,0 +1,61 @@
+//! Tests recording kernel argument values for debugging.
+
+use std::mem;
+use standard::{ProQue, DebugArg};
+
+static SRC: &'static str = r#"
+    __kernel void multiply(__global float* buffer, float coeff, int offset, uint count) {
+        buffer[get_global_id(0)] *= coeff;
+    }
+"#;
+
+fn bytes_of<T>(val: &T) -> Vec<u8> {
+    unsafe {
+        ::std::slice::from_raw_parts(val as *const T as *const u8, mem::size_of::<T>()).to_vec()
+    }
+}
+
+#[test]
+fn kernel_debug_args() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let mut kernel = pro_que.create_kernel("multiply").unwrap()
+        .track_args(true)
+        .arg_buf(&buffer)
+        .arg_scl_named("coeff", Some(2.5f32))
+        .arg_scl(-3i32)
+        .arg_scl(7u32);
+
+    kernel.set_arg_scl_named("coeff", 4.0f32).unwrap();
+
+    let debug_args = kernel.debug_args();
+    assert_eq!(debug_args.len(), 4);
+
+    match debug_args[0] {
+        DebugArg::Mem { handle } => assert_eq!(handle, buffer.core().as_ptr() as usize),
+        ref arg => panic!("Unexpected debug arg: {:?}", arg),
+    }
+
+    match debug_args[1] {
+        DebugArg::Scalar { ref bytes, .. } => assert_eq!(*bytes, bytes_of(&4.0f32)),
+        ref arg => panic!("Unexpected debug arg: {:?}", arg),
+    }
+
+    match debug_args[2] {
+        DebugArg::Scalar { ref bytes, .. } => assert_eq!(*bytes, bytes_of(&-3i32)),
+        ref arg => panic!("Unexpected debug arg: {:?}", arg),
+    }
+
+    match debug_args[3] {
+        DebugArg::Scalar { ref bytes, .. } => assert_eq!(*bytes, bytes_of(&7u32)),
+        ref arg => panic!("Unexpected debug arg: {:?}", arg),
+    }
+
+    let kernel = kernel.track_args(false);
+    assert!(kernel.debug_args().is_empty());
+}
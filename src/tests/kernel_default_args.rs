@@ -0,0 +1,49 @@
+//! Tests default kernel arguments re-applied after per-launch overrides.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void linear(__global float* buffer, float coeff, float addend) {
+        buffer[get_global_id(0)] = coeff * (float)get_global_id(0) + addend;
+    }
+"#;
+
+#[test]
+fn kernel_default_args() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(256)
+        .build().unwrap();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(256)
+        .build().unwrap();
+
+    let mut kernel = pro_que.create_kernel("linear").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(0.0f32)
+        .arg_scl(0.0f32);
+
+    kernel.set_default_arg(1, 2.0f32).unwrap();
+    kernel.set_default_arg(2, 3.0f32).unwrap();
+
+    let mut vec = vec![0.0f32; 256];
+
+    for iter in 0..6 {
+        // Override the coefficient on even iterations only:
+        let coeff = if iter % 2 == 0 {
+            kernel.set_arg(1, iter as f32).unwrap();
+            iter as f32
+        } else {
+            2.0
+        };
+
+        kernel.enq().unwrap();
+        buffer.read(&mut vec).enq().unwrap();
+
+        for (idx, &val) in vec.iter().enumerate() {
+            assert_eq!(val, coeff * idx as f32 + 3.0);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Tests creating independent copies of a kernel with `Kernel::duplicate`.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn kernel_duplicate() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer_a: Buffer<f32> = pro_que.create_buffer().unwrap();
+    let buffer_b: Buffer<f32> = pro_que.create_buffer().unwrap();
+
+    let mut kernel_a = pro_que.create_kernel("add").unwrap()
+        .track_args(true)
+        .arg_buf(&buffer_a)
+        .arg_scl_named("addend", Some(1.0f32));
+
+    let mut kernel_b = kernel_a.duplicate().unwrap();
+
+    // Arguments set on one copy must not affect the other:
+    kernel_b.set_arg(0, &buffer_b).unwrap();
+    kernel_b.set_arg_scl_named("addend", 5.0f32).unwrap();
+    kernel_a.set_arg_scl_named("addend", 2.0f32).unwrap();
+
+    kernel_a.enq().unwrap();
+    kernel_b.enq().unwrap();
+
+    let mut vec = vec![0.0f32; buffer_a.len()];
+    buffer_a.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 2.0));
+
+    buffer_b.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 5.0));
+
+    // The duplicate inherits the original's (tracked) arguments:
+    let kernel_c = kernel_a.duplicate().unwrap();
+    kernel_c.enq().unwrap();
+    buffer_a.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 4.0));
+}
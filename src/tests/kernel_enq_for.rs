@@ -0,0 +1,43 @@
+//! Tests launching a kernel with dimensions derived from its data.
+
+use standard::{ProQue, Buffer};
+use traits::Dimensioned;
+
+static SRC: &'static str = r#"
+    __kernel void add_idx(__global uint* buffer) {
+        buffer[get_global_id(0)] += get_global_id(0);
+    }
+"#;
+
+#[test]
+fn kernel_enq_for() {
+    let len = 1000;
+
+    // The default work size is deliberately smaller than the data:
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1)
+        .build().unwrap();
+
+    let vec = vec![5u32; len];
+    assert_eq!(vec.dims().dim_count(), 1);
+    assert_eq!(vec.dims().to_lens().unwrap(), [len, 1, 1]);
+    assert_eq!(vec[..10].dims().to_lens().unwrap(), [10, 1, 1]);
+
+    let buffer = Buffer::<u32>::builder()
+        .queue(pro_que.queue().clone())
+        .copy_host_slice(&vec)
+        .build().unwrap();
+
+    let kernel = pro_que.create_kernel("add_idx").unwrap()
+        .arg_buf(&buffer);
+
+    kernel.enq_for(&vec).unwrap();
+
+    let mut result = vec![0u32; len];
+    buffer.read(&mut result).enq().unwrap();
+
+    for (idx, &val) in result.iter().enumerate() {
+        assert_eq!(val, 5 + idx as u32);
+    }
+}
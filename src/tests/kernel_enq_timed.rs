@@ -0,0 +1,44 @@
+//! Tests enqueuing a kernel and retrieving its profiling times in one call.
+
+use standard::ProQue;
+
+#[test]
+fn kernel_enq_timed() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1 << 16])
+        .profiling_sibling(true)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32);
+
+    let (_event, nanos) = kernel.enq_timed(&pro_que.queue().device()).unwrap();
+    assert!(nanos.end > nanos.start);
+    assert!(nanos.duration_ns() > 0);
+    assert!(nanos.start >= nanos.submit && nanos.submit >= nanos.queued);
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 10.0));
+}
+
+#[test]
+fn kernel_enq_timed_unprofiled() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let kernel = pro_que.create_kernel("dummy").unwrap();
+    assert!(kernel.enq_timed(&pro_que.queue().device()).is_err());
+}
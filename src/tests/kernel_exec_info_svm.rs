@@ -0,0 +1,82 @@
+//! Tests declaring indirectly accessed SVM pointers for a kernel.
+
+use std::{mem, ptr};
+use libc::c_void;
+use ffi;
+use core::OpenclVersion;
+use standard::{Context, Program, Queue, Kernel};
+
+static SRC: &'static str = r#"
+    __kernel void chase(__global int* __global* table) {
+        __global int* data = table[0];
+        data[get_global_id(0)] = (int)get_global_id(0);
+    }
+"#;
+
+const LEN: usize = 256;
+
+/// Maps `size` bytes at `svm_ptr` for writing, calls `f`, then unmaps.
+unsafe fn with_svm_mapped<F: FnOnce()>(queue: &Queue, svm_ptr: *mut c_void, size: usize,
+        flags: ffi::cl_map_flags, f: F)
+{
+    assert_eq!(ffi::clEnqueueSVMMap(queue.core().as_ptr(), ffi::CL_TRUE, flags, svm_ptr, size,
+        0, ptr::null(), ptr::null_mut()), ffi::CL_SUCCESS as ffi::cl_int);
+    f();
+    assert_eq!(ffi::clEnqueueSVMUnmap(queue.core().as_ptr(), svm_ptr, 0, ptr::null(),
+        ptr::null_mut()), ffi::CL_SUCCESS as ffi::cl_int);
+    queue.finish().unwrap();
+}
+
+#[test]
+fn kernel_exec_info_svm() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    // SVM requires OpenCL 2.0:
+    if device.version().unwrap() < OpenclVersion::new(2, 0) { return; }
+
+    let queue = Queue::new(&context, device, None).unwrap();
+
+    let program = Program::builder()
+        .src(SRC)
+        .devices(device)
+        .cmplr_opt("-cl-std=CL2.0")
+        .build(&context).unwrap();
+
+    let mut kernel = Kernel::new("chase", &program).unwrap()
+        .queue(queue.clone())
+        .gws(LEN);
+
+    unsafe {
+        let data = ffi::clSVMAlloc(context.core().as_ptr(), ffi::CL_MEM_READ_WRITE,
+            LEN * mem::size_of::<i32>(), 0);
+        let table = ffi::clSVMAlloc(context.core().as_ptr(), ffi::CL_MEM_READ_WRITE,
+            mem::size_of::<*mut c_void>(), 0);
+        assert!(!data.is_null() && !table.is_null());
+
+        with_svm_mapped(&queue, table, mem::size_of::<*mut c_void>(), ffi::CL_MAP_WRITE, || {
+            *(table as *mut *mut c_void) = data;
+        });
+
+        // Devices without SVM support are rejected:
+        if kernel.set_exec_info_svm_ptrs(&[data as *const c_void]).is_err() {
+            ffi::clSVMFree(context.core().as_ptr(), table);
+            ffi::clSVMFree(context.core().as_ptr(), data);
+            return;
+        }
+
+        assert_eq!(ffi::clSetKernelArgSVMPointer(kernel.core().as_ptr(), 0, table),
+            ffi::CL_SUCCESS as ffi::cl_int);
+
+        kernel.enq().unwrap();
+        queue.finish().unwrap();
+
+        with_svm_mapped(&queue, data, LEN * mem::size_of::<i32>(), ffi::CL_MAP_READ, || {
+            let vals = ::std::slice::from_raw_parts(data as *const i32, LEN);
+            assert!(vals.iter().enumerate().all(|(idx, &val)| val == idx as i32));
+        });
+
+        ffi::clSVMFree(context.core().as_ptr(), table);
+        ffi::clSVMFree(context.core().as_ptr(), data);
+    }
+}
@@ -0,0 +1,37 @@
+//! Tests enqueuing kernels which declare a required work group size.
+
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel __attribute__((reqd_work_group_size(16, 1, 1)))
+    void local_size(__global uint* buffer) {
+        buffer[get_global_id(0)] = get_local_size(0);
+    }
+"#;
+
+#[test]
+fn kernel_reqd_wg_size() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let kernel = pro_que.create_kernel("local_size").unwrap()
+        .arg_buf(&buffer);
+
+    // No local work size specified:
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 16));
+
+    // Matching:
+    kernel.cmd().lws(16).enq().unwrap();
+
+    // Conflicting:
+    let err = kernel.cmd().lws(32).enq().unwrap_err();
+    assert!(format!("{}", err).contains("required work group size"));
+}
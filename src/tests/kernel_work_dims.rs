@@ -0,0 +1,43 @@
+//! Tests validating a kernel's required number of work dimensions.
+
+use tests;
+
+static SRC: &'static str = r#"
+    __kernel void index_2d(__global int* data) {
+        uint idx = get_global_id(1) * get_global_size(0) + get_global_id(0);
+        data[idx] = (int)idx;
+    }
+"#;
+
+const DIMS: [usize; 2] = [16, 8];
+
+#[test]
+fn kernel_work_dims() {
+    let (_pro_que, buffer, kernel) = tests::pro_que_buffer_kernel::<i32, _>(SRC, "index_2d",
+        DIMS);
+    let kernel = kernel.work_dims(2);
+
+    assert_eq!(kernel.get_work_dims(), Some(2));
+
+    // A 1D global work size is rejected:
+    assert!(kernel.cmd().gws(DIMS[0] * DIMS[1]).enq().is_err());
+
+    // As is a mismatched local work size:
+    assert!(kernel.cmd().lws(4).enq().is_err());
+
+    kernel.enq().unwrap();
+
+    let mut vec = vec![0; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().enumerate().all(|(idx, &val)| val == idx as i32));
+}
+
+#[test]
+#[should_panic(expected = "Invalid work dimension count")]
+fn kernel_work_dims_invalid() {
+    let (_pro_que, _buffer, kernel) = tests::pro_que_buffer_kernel::<i32, _>(SRC, "index_2d",
+        DIMS);
+
+    // Invalid dimension counts are rejected when set:
+    let _ = kernel.work_dims(4);
+}
@@ -0,0 +1,57 @@
+//! Tests the `launch!` macro against manually set kernel arguments.
+
+use standard::{ProQue, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void mul_add(__global float const* a, __global float const* b,
+            float coeff, __global float* out) {
+        uint idx = get_global_id(0);
+        out[idx] = a[idx] * coeff + b[idx];
+    }
+"#;
+
+#[test]
+fn launch_macro() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 12])
+        .build().unwrap();
+
+    let len = pro_que.dims().to_len();
+    let a_vec: Vec<f32> = (0..len).map(|i| i as f32).collect();
+    let b_vec: Vec<f32> = (0..len).map(|i| (len - i) as f32).collect();
+
+    let buf_a: Buffer<f32> = pro_que.create_buffer().unwrap();
+    let buf_b: Buffer<f32> = pro_que.create_buffer().unwrap();
+    let out_manual: Buffer<f32> = pro_que.create_buffer().unwrap();
+    let out_launch: Buffer<f32> = pro_que.create_buffer().unwrap();
+    buf_a.write(&a_vec).enq().unwrap();
+    buf_b.write(&b_vec).enq().unwrap();
+
+    // Manual path:
+    pro_que.create_kernel("mul_add").unwrap()
+        .arg_buf(&buf_a)
+        .arg_buf(&buf_b)
+        .arg_scl(1.5f32)
+        .arg_buf(&out_manual)
+        .enq().unwrap();
+
+    // Macro path:
+    let kernel = launch!(pro_que.create_kernel("mul_add").unwrap(), len,
+        (&buf_a, &buf_b, 1.5f32, &out_launch)).unwrap();
+    assert_eq!(kernel.get_gws().to_len(), len);
+
+    let mut manual_vec = vec![0.0f32; len];
+    let mut launch_vec = vec![0.0f32; len];
+    out_manual.read(&mut manual_vec).enq().unwrap();
+    out_launch.read(&mut launch_vec).enq().unwrap();
+    assert_eq!(manual_vec, launch_vec);
+
+    for i in 0..len {
+        assert_eq!(launch_vec[i], a_vec[i] * 1.5 + b_vec[i]);
+    }
+
+    // Arity mismatch:
+    assert!(launch!(pro_que.create_kernel("mul_add").unwrap(), len,
+        (&buf_a, &buf_b, 1.5f32)).is_err());
+}
@@ -0,0 +1,28 @@
+//! Tests converting a `MemMap` into an owned `Vec`.
+
+use standard::ProQue;
+
+#[test]
+fn mem_map_into_vec() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 8])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let mut mem_map = buffer.map().enq().unwrap();
+
+    for (i, ele) in mem_map.iter_mut().enumerate() {
+        *ele = i as u32 * 3;
+    }
+
+    let vec = mem_map.into_vec().unwrap();
+    assert_eq!(vec.len(), buffer.len());
+    assert!(vec.iter().enumerate().all(|(i, &ele)| ele == i as u32 * 3));
+
+    // The changes made through the map must also reach the device:
+    let mut read_vec = vec![0u32; buffer.len()];
+    buffer.read(&mut read_vec).enq().unwrap();
+    assert_eq!(read_vec, vec);
+}
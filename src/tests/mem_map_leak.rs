@@ -0,0 +1,61 @@
+//! Tests that leaking a `MemMap` (or disabling its automatic unmap) prevents
+//! the drop-time unmap.
+
+use core;
+use standard::{ProQue, Event};
+
+fn map_with_target(pro_que: &ProQue) -> (::MemMap<u32>, Event, ::Buffer<u32>) {
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+
+    let mut future_map = buffer.map().enq_async().unwrap();
+    let unmap_target: Event = future_map.create_unmap_target_event().unwrap().clone();
+    let map = future_map.wait().unwrap();
+
+    (map, unmap_target, buffer)
+}
+
+#[test]
+fn mem_map_leak() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 8])
+        .build().unwrap();
+
+    let (mut map, unmap_target, buffer) = map_with_target(&pro_que);
+    map[0] = 42;
+
+    let (map_core, len, mem, queue) = map.leak();
+    assert_eq!(len, buffer.len());
+    queue.finish().unwrap();
+
+    // No unmap has been enqueued:
+    assert!(!unmap_target.is_complete().unwrap());
+
+    // The region is still mapped and may be unmapped manually:
+    assert_eq!(unsafe { *map_core.as_ptr() }, 42);
+    core::enqueue_unmap_mem_object(&queue, &mem, &map_core, None::<&Event>,
+        None::<&mut Event>).unwrap();
+    queue.finish().unwrap();
+
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec[0], 42);
+}
+
+#[test]
+fn mem_map_auto_unmap_disabled() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 8])
+        .build().unwrap();
+
+    let (mut map, unmap_target, _buffer) = map_with_target(&pro_que);
+    assert!(map.is_auto_unmap());
+    map.set_auto_unmap(false);
+
+    let queue = map.queue().clone();
+    drop(map);
+    queue.finish().unwrap();
+
+    assert!(!unmap_target.is_complete().unwrap());
+}
@@ -0,0 +1,41 @@
+//! Tests remapping a `MemMap` after it has been unmapped.
+
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global uint* buffer, uint addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn mem_map_remap() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 8])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(100u32);
+
+    let mut mem_map = buffer.map().offset(16).enq().unwrap();
+    assert!(mem_map.remap().is_err());
+
+    for (i, ele) in mem_map.iter_mut().enumerate() {
+        *ele = i as u32;
+    }
+
+    mem_map.unmap().enq().unwrap();
+    kernel.enq().unwrap();
+
+    mem_map.remap().unwrap();
+    assert!(!mem_map.is_unmapped());
+    assert_eq!(mem_map.len(), buffer.len() - 16);
+
+    // The second mapping covers the same region and sees the kernel's output:
+    for (i, &ele) in mem_map.iter().enumerate() {
+        assert_eq!(ele, i as u32 + 100);
+    }
+}
@@ -0,0 +1,51 @@
+//! Tests sharing a read-only `MemMap` between threads.
+
+use std::thread;
+use std::sync::Arc;
+use standard::{ProQue, Event};
+
+#[test]
+fn mem_map_shared() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+    let vals: Vec<u32> = (0..buffer.len() as u32).collect();
+    buffer.write(&vals).enq().unwrap();
+
+    let mut future_map = buffer.map().read().enq_async().unwrap();
+    let unmap_target: Event = future_map.create_unmap_target_event().unwrap().clone();
+    let shared = future_map.wait().unwrap().into_shared();
+    assert_eq!(shared.len(), buffer.len());
+
+    let threads: Vec<_> = (0..2).map(|_| {
+        let shared = shared.clone();
+        let vals = vals.clone();
+        thread::spawn(move || {
+            assert_eq!(&shared[..], &vals[..]);
+        })
+    }).collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // Nothing is unmapped while any reference remains:
+    let queue = shared.queue();
+    queue.finish().unwrap();
+    assert_eq!(Arc::strong_count(&shared), 1);
+    assert!(!unmap_target.is_complete().unwrap());
+
+    // The final drop enqueues the (single) unmap:
+    drop(shared);
+    queue.finish().unwrap();
+    unmap_target.wait_for().unwrap();
+    assert!(unmap_target.is_complete().unwrap());
+
+    // The buffer is usable again once unmapped:
+    let mut vec = vec![0u32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert_eq!(vec, vals);
+}
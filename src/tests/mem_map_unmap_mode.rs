@@ -0,0 +1,62 @@
+//! Tests selecting the unmap completion mode of a `MemMap` at runtime.
+
+use standard::{ProQue, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+fn unmap_completes(blocking: bool) {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 12])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let mut future_map = buffer.map().write().enq_async().unwrap();
+    let unmap_target: Event = future_map.create_unmap_target_event().unwrap().clone();
+    let mut map = future_map.wait().unwrap();
+
+    map.set_blocking_unmap(blocking);
+    assert_eq!(map.is_blocking_unmap(), blocking);
+
+    for val in map.iter_mut() {
+        *val = 5.0;
+    }
+
+    // The unmap must be enqueued before any command waiting on its target
+    // event (in an in-order queue, it would otherwise be stuck behind it):
+    let mut unmap_event = Event::empty();
+    map.unmap().enew(&mut unmap_event).enq().unwrap();
+    assert!(!unmap_event.is_empty());
+    if blocking {
+        assert!(unmap_target.is_complete().unwrap());
+    }
+
+    // The kernel must not run until the unmap target event is triggered:
+    pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32)
+        .cmd()
+        .ewait(&unmap_target)
+        .enq().unwrap();
+
+    unmap_target.wait_for().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 6.0));
+}
+
+#[test]
+fn mem_map_unmap_blocking() {
+    unmap_completes(true);
+}
+
+#[test]
+fn mem_map_unmap_callback() {
+    unmap_completes(false);
+}
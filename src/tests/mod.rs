@@ -18,15 +18,149 @@ pub mod kernel_arg_ptr;
 pub mod vector_types;
 pub mod context_props;
 pub mod async;
+pub mod profiled_cmd;
+pub mod command_buffer;
+pub mod command_buffer_khr;
+pub mod buffer_from_vec;
+pub mod program_defines;
+pub mod kernel_debug_args;
+pub mod queue_barrier;
+pub mod buffer_map_range;
+pub mod buffer_copy_foreign;
+pub mod launch_macro;
+pub mod built_in_kernels;
+pub mod stream_pipeline;
+pub mod queue_properties;
+pub mod image_write_checked;
+pub mod event_list_reuse;
+pub mod subgroups;
+pub mod constant_buffer;
+pub mod program_line_markers;
+pub mod buffer_raw_parts;
+pub mod kernel_auto_lws;
+pub mod describe_system;
+pub mod ping_pong;
+pub mod info_strings;
+pub mod image_read_flat;
+pub mod pinned_buffer;
+pub mod queue_duplicate;
+pub mod kernel_reqd_wg_size;
+pub mod buffer_read_to_writer;
+pub mod buffer_copy_host_slice;
+pub mod event_wait_timeout;
+pub mod mem_map_unmap_mode;
+pub mod buffer_reductions;
+pub mod program_build_diagnostics;
+pub mod context_image_formats;
+pub mod mem_map_into_vec;
+pub mod mem_map_leak;
+pub mod program_build_for;
+pub mod kernel_arg_buffers;
+pub mod program_enable_fp64;
+pub mod event_profiling_summary;
+pub mod mem_map_remap;
+pub mod image_copy_host_slice;
+pub mod address_bits;
+pub mod kernel_duplicate;
+pub mod nd_range;
+pub mod buffer_content_hash;
+pub mod buffer_sub_buffer_aligned;
+pub mod buffer_write_combined;
+pub mod program_source;
+pub mod kernel_enq_timed;
+pub mod read_all;
+pub mod enumeration_cache;
+pub mod device_capability_table;
+pub mod spatial_dims_clamp;
+pub mod buffer_read_one;
+pub mod user_event;
+pub mod sync_kernel;
+pub mod buffer_copy_future;
+pub mod device_sorted;
+pub mod device_uuid;
+pub mod queue_barrier_future;
+pub mod image_mipmaps;
+pub mod buffer_resize;
+pub mod image_limits;
+pub mod pipeline;
+pub mod program_build_options;
+pub mod buffer_zero_copy;
+pub mod event_then;
+pub mod device_memory_usage;
+pub mod counting_queue;
+pub mod subgroup_support;
+pub mod buffer_copy_overlap;
+pub mod queue_priority;
+pub mod join_events;
+pub mod kernel_arg_bool;
+pub mod kernel_default_args;
+pub mod mem_map_shared;
+pub mod buffer_access_flags;
+pub mod buffer_copy_offsets;
+pub mod context_all_devices;
+pub mod image_read_layer;
+pub mod event_status;
+pub mod texture_fallback;
+pub mod wait_strategy;
+pub mod kernel_enq_for;
+pub mod buffer_map_write_invalidate;
+pub mod device_info_map;
+pub mod buffer_read_and_fold;
+pub mod event_raw;
+pub mod kernel_work_dims;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "ndarray")]
+pub mod ndarray_rw;
+#[cfg(feature = "image")]
+pub mod image_crate_interop;
+#[cfg(feature = "std_future")]
+pub mod std_future;
+#[cfg(feature = "event_order_debug")]
+pub mod event_order_debug;
+#[cfg(feature = "opencl_version_2_1")]
+pub mod default_device_queue;
+#[cfg(feature = "opencl_version_2_0")]
+pub mod kernel_exec_info_svm;
+#[cfg(feature = "opencl_version_2_0")]
+pub mod svm_memcpy_fill;
 
 use rand::{self, Rng};
 use core::error::{Error as OclError, Result as OclResult};
-use core::OclScl;
+use core::{OclPrm, OclScl};
+use standard::{ProQue, Buffer, Kernel, SpatialDims};
 
 const PRINT_ITERS_MAX: i32 = 3;
 const PRINT_SLICES_MAX: usize = 16;
 const PRINT: bool = false;
 
+/// A kernel adding `addend` to every element of `buffer`, for tests which
+/// need a simple command to enqueue (see `pro_que_buffer_kernel`).
+static ADD_SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+/// Returns a `ProQue` built from `src` with `dims`, a zero-filled buffer of
+/// the same dimensions, and the kernel named `kernel_name` with the buffer
+/// set as its first argument (further arguments may be chained).
+fn pro_que_buffer_kernel<T, D>(src: &str, kernel_name: &str, dims: D) -> (ProQue, Buffer<T>, Kernel)
+        where T: OclPrm, D: Into<SpatialDims>
+{
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims(dims)
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<T>().unwrap();
+
+    let kernel = pro_que.create_kernel(kernel_name).unwrap()
+        .arg_buf(&buffer);
+
+    (pro_que, buffer, kernel)
+}
+
 fn gen_region_origin(dims: &[usize; 3]) -> ([usize; 3], [usize; 3]) {
     let mut rng = rand::weak_rng();
 
@@ -0,0 +1,27 @@
+//! Tests rounding global work sizes up to multiples of the local size.
+
+use standard::{self, SpatialDims};
+
+#[test]
+fn nd_range_exact() {
+    let (global, local) = standard::nd_range([256, 64], [16, 16]).unwrap();
+    assert_eq!(global.to_lens().unwrap(), [256, 64, 1]);
+    assert_eq!(local.to_lens().unwrap(), [16, 16, 1]);
+}
+
+#[test]
+fn nd_range_rounded() {
+    let (global, _) = standard::nd_range(1000, 64).unwrap();
+    assert_eq!(global.to_lens().unwrap(), [1024, 1, 1]);
+
+    let (global, _) = standard::nd_range([17, 9, 3], [8, 8, 2]).unwrap();
+    assert_eq!(global.dim_count(), 3);
+    assert_eq!(global.to_lens().unwrap(), [24, 16, 4]);
+}
+
+#[test]
+fn nd_range_invalid() {
+    assert!(standard::nd_range([100, 100], 16).is_err());
+    assert!(standard::nd_range(100, 0).is_err());
+    assert!(standard::nd_range(SpatialDims::Unspecified, SpatialDims::Unspecified).is_err());
+}
@@ -0,0 +1,45 @@
+//! Tests round-tripping `ndarray` arrays through a buffer.
+
+use ndarray::Array2;
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn ndarray_round_trip() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([12])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let array = Array2::from_shape_fn((3, 4), |(r, c)| (r * 4 + c) as f32);
+    buffer.write_from_array(&array.view()).unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    kernel.enq().unwrap();
+
+    let result = buffer.read_to_array2((3, 4)).unwrap();
+    assert_eq!(result.dim(), (3, 4));
+
+    for ((r, c), &val) in result.indexed_iter() {
+        assert_eq!(val, array[[r, c]] + 1.0);
+    }
+
+    // Non-contiguous (transposed) views are copied in logical order:
+    let transposed = array.t();
+    buffer.write_from_array(&transposed).unwrap();
+    let result = buffer.read_to_array2((4, 3)).unwrap();
+    assert_eq!(result, transposed);
+
+    // Shapes larger than the buffer are rejected:
+    assert!(buffer.read_to_array2((4, 4)).is_err());
+}
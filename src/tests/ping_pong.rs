@@ -0,0 +1,58 @@
+//! Tests alternating a pair of buffers between iterations.
+
+use standard::{ProQue, Buffer, PingPong};
+
+static SRC: &'static str = r#"
+    __kernel void shift_right(__global float const* prev, __global float* cur) {
+        uint idx = get_global_id(0);
+        cur[idx] = idx > 0 ? prev[idx - 1] : 0.0f;
+    }
+"#;
+
+#[test]
+fn ping_pong_shift() {
+    const ITERS: usize = 3;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .build().unwrap();
+
+    let mut init = vec![0.0f32; pro_que.dims().to_len()];
+    init[0] = 1.0;
+
+    let current = pro_que.create_buffer::<f32>().unwrap();
+    let previous = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(pro_que.dims().clone())
+        .host_data(&init)
+        .build().unwrap();
+
+    let mut pp = PingPong::new(current, previous);
+
+    let mut kernel = pro_que.create_kernel("shift_right").unwrap()
+        .arg_buf_named("prev", Some(pp.previous()))
+        .arg_buf_named("cur", Some(pp.current()));
+
+    for _ in 0..ITERS {
+        kernel.set_arg_buf_named("prev", Some(pp.previous())).unwrap();
+        kernel.set_arg_buf_named("cur", Some(pp.current())).unwrap();
+        kernel.enq().unwrap();
+        pp.swap();
+    }
+
+    // After the final swap the most recently written buffer is `previous`:
+    let (older, latest) = pp.into_buffers();
+    let mut vec = vec![0.0f32; latest.len()];
+
+    latest.read(&mut vec).enq().unwrap();
+    for (idx, &val) in vec.iter().enumerate() {
+        assert_eq!(val, if idx == ITERS { 1.0 } else { 0.0 });
+    }
+
+    older.read(&mut vec).enq().unwrap();
+    for (idx, &val) in vec.iter().enumerate() {
+        assert_eq!(val, if idx == ITERS - 1 { 1.0 } else { 0.0 });
+    }
+}
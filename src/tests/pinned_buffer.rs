@@ -0,0 +1,83 @@
+//! Tests transfers using pinned host buffers.
+
+use standard::{ProQue, Buffer, PinnedBuffer};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn pinned_buffer_round_trip() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 14])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let mut pinned = PinnedBuffer::<f32>::new(pro_que.queue(), buffer.len()).unwrap();
+    assert!(pinned.iter().all(|&val| val == 0.0));
+
+    for (idx, val) in pinned.iter_mut().enumerate() {
+        *val = idx as f32;
+    }
+
+    pinned.upload_to(&buffer).unwrap();
+
+    pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32)
+        .enq().unwrap();
+
+    // Clobber the host copy to ensure that the results come from the device:
+    for val in pinned.iter_mut() {
+        *val = -1.0;
+    }
+
+    pinned.download_from(&buffer).unwrap();
+
+    for (idx, &val) in pinned.iter().enumerate() {
+        assert_eq!(val, idx as f32 + 10.0);
+    }
+
+    // Mismatched lengths:
+    let short_buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(16)
+        .build().unwrap();
+    assert!(pinned.upload_to(&short_buffer).is_err());
+    assert!(pinned.download_from(&short_buffer).is_err());
+}
+
+/// Repeats pinned round trips, verifying the data after each.
+#[test]
+fn pinned_buffer_repeated_transfers() {
+    const ITERS: usize = 10;
+
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 22])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+    let mut pinned = PinnedBuffer::<f32>::new(pro_que.queue(), buffer.len()).unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    for iter in 0..ITERS {
+        for (idx, val) in pinned.iter_mut().enumerate() {
+            *val = (idx % 1000 + iter) as f32;
+        }
+
+        pinned.upload_to(&buffer).unwrap();
+        kernel.enq().unwrap();
+        pinned.download_from(&buffer).unwrap();
+
+        for (idx, &val) in pinned.iter().enumerate() {
+            assert_eq!(val, (idx % 1000 + iter) as f32 + 1.0);
+        }
+    }
+}
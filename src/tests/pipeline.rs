@@ -0,0 +1,56 @@
+//! Tests a two-stage `Pipeline`.
+
+use standard::{ProQue, Buffer, Pipeline};
+
+static SRC: &'static str = r#"
+    __kernel void scale(__global float* buffer, float coeff) {
+        buffer[get_global_id(0)] *= coeff;
+    }
+
+    __kernel void offset(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn pipeline() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(1 << 12)
+        .build().unwrap();
+
+    let initial: Vec<f32> = (0..pro_que.dims().to_len()).map(|i| i as f32).collect();
+
+    let buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(pro_que.dims().clone())
+        .host_data(&initial)
+        .build().unwrap();
+
+    let scale = pro_que.create_kernel("scale").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(3.0f32);
+
+    let offset = pro_que.create_kernel("offset").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32);
+
+    // The second stage only covers the first half of the buffer:
+    let half_len = buffer.len() / 2;
+
+    let event = Pipeline::new(pro_que.queue())
+        .stage(&scale, buffer.len())
+        .stage(&offset, half_len)
+        .run().unwrap();
+
+    event.wait_for().unwrap();
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+
+    for (idx, (&val, &init)) in vec.iter().zip(initial.iter()).enumerate() {
+        let expected = if idx < half_len { init * 3.0 + 10.0 } else { init * 3.0 };
+        assert_eq!(val, expected);
+    }
+}
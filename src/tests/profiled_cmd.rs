@@ -0,0 +1,127 @@
+//! Tests routing individual commands to a profiling-enabled sibling queue.
+
+use core::{ProfilingInfo, ProfilingInfoResult};
+use flags;
+use standard::{ProQue, Queue, Event};
+
+#[test]
+fn profiled_cmd() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1 << 12])
+        .profiling_sibling(true)
+        .build().unwrap();
+
+    assert!(pro_que.queue().profiled().is_some());
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32);
+
+    let mut unprofiled_event = Event::empty();
+    kernel.cmd().enew(&mut unprofiled_event).enq().unwrap();
+
+    let mut profiled_event = Event::empty();
+    kernel.cmd().profiled().ewait(&unprofiled_event).enew(&mut profiled_event).enq().unwrap();
+
+    unprofiled_event.wait_for().unwrap();
+    profiled_event.wait_for().unwrap();
+
+    let start = match profiled_event.profiling_info(ProfilingInfo::Start) {
+        ProfilingInfoResult::Start(time) => time,
+        res => panic!("Unexpected profiling info result: {:?}", res),
+    };
+
+    let end = match profiled_event.profiling_info(ProfilingInfo::End) {
+        ProfilingInfoResult::End(time) => time,
+        res => panic!("Unexpected profiling info result: {:?}", res),
+    };
+
+    assert!(end >= start);
+
+    match unprofiled_event.profiling_info(ProfilingInfo::Start) {
+        ProfilingInfoResult::Error(_) => (),
+        res => panic!("Expected an error when retrieving profiling info from an \
+            unprofiled queue, got: {:?}", res),
+    }
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).profiled().enq().unwrap();
+
+    for &ele in vec.iter() {
+        assert_eq!(ele, 20.0);
+    }
+}
+
+#[test]
+fn profiled_cmd_without_profiling_queue() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1 << 12])
+        .build().unwrap();
+
+    assert!(pro_que.queue().profiled().is_none());
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32);
+
+    // Missing profiling queues are reported when enqueuing:
+    assert!(kernel.cmd().profiled().enq().is_err());
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    assert!(buffer.read(&mut vec).profiled().enq().is_err());
+}
+
+#[test]
+fn profiled_cmd_specified_queue() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1 << 12])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(10.0f32);
+
+    // A specified queue with profiling enabled is used in place of the
+    // (unprofiled) default queue:
+    let profiling_queue = Queue::new(pro_que.context(), pro_que.queue().device(),
+        Some(flags::QUEUE_PROFILING_ENABLE)).unwrap();
+    let mut event = Event::empty();
+    kernel.cmd().queue(&profiling_queue).profiled().enew(&mut event).enq().unwrap();
+    event.wait_for().unwrap();
+
+    match event.profiling_info(ProfilingInfo::End) {
+        ProfilingInfoResult::End(_) => (),
+        res => panic!("Unexpected profiling info result: {:?}", res),
+    }
+
+    // A specified queue without profiling is rejected:
+    let queue = Queue::new(pro_que.context(), pro_que.queue().device(), None).unwrap();
+    assert!(kernel.cmd().queue(&queue).profiled().enq().is_err());
+}
@@ -0,0 +1,42 @@
+//! Tests parsing of program build logs into `BuildDiagnostic`s.
+
+use standard::{ProgramBuildError, BuildDiagnosticSeverity};
+
+static LOG: &'static str = "\
+<kernel>:4:19: error: use of undeclared identifier 'undeclared'
+    buffer[idx] = undeclared;
+                  ^
+<kernel>:2:9: warning: unused variable 'unused'
+    int unused;
+        ^
+1 warning and 1 error generated.
+";
+
+#[test]
+fn program_build_diagnostics() {
+    let err = ProgramBuildError::from_log(LOG);
+    let diags = err.diagnostics();
+
+    assert_eq!(diags.len(), 2);
+
+    assert_eq!(diags[0].severity, BuildDiagnosticSeverity::Error);
+    assert_eq!(diags[0].file, "<kernel>");
+    assert_eq!(diags[0].line, 4);
+    assert_eq!(diags[0].column, Some(19));
+    assert_eq!(diags[0].message, "use of undeclared identifier 'undeclared'");
+
+    assert_eq!(diags[1].severity, BuildDiagnosticSeverity::Warning);
+    assert_eq!(diags[1].line, 2);
+
+    assert_eq!(err.errors().count(), 1);
+    assert_eq!(err.log(), LOG);
+}
+
+#[test]
+fn program_build_diagnostics_unrecognized() {
+    let log = "Compilation failed.\nSomething unexpected happened on line 7.";
+    let err = ProgramBuildError::from_log(log);
+
+    assert!(err.diagnostics().is_empty());
+    assert_eq!(err.log(), log);
+}
@@ -0,0 +1,61 @@
+//! Tests building a program for a subset of the devices in a context.
+
+use standard::{Platform, Device, Context, Program, Queue, Kernel, Buffer};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn program_build_for() {
+    let platform = Platform::default();
+    let devices = Device::list_all(&platform).unwrap();
+
+    // Only meaningful with more than one device:
+    if devices.len() < 2 { return; }
+
+    let context = Context::builder()
+        .platform(platform)
+        .devices(&devices[..])
+        .build().unwrap();
+
+    let device = devices[devices.len() - 1];
+    let program = Program::builder()
+        .src(SRC)
+        .build_for(&context, &[device]).unwrap();
+
+    let queue = Queue::new(&context, device, None).unwrap();
+    let buffer = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+        .dims(1024)
+        .host_data(&vec![1.0f32; 1024])
+        .build().unwrap();
+
+    Kernel::new("add", &program).unwrap()
+        .queue(queue.clone())
+        .gws(1024)
+        .arg_buf(&buffer)
+        .arg_scl(2.0f32)
+        .enq().unwrap();
+
+    let mut vec = vec![0.0f32; 1024];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 3.0));
+}
+
+#[test]
+fn program_build_for_foreign_device() {
+    let platform = Platform::default();
+    let devices = Device::list_all(&platform).unwrap();
+    if devices.len() < 2 { return; }
+
+    let context = Context::builder()
+        .platform(platform)
+        .devices(devices[0])
+        .build().unwrap();
+
+    assert!(Program::builder().src(SRC).build_for(&context, &[devices[1]]).is_err());
+}
@@ -0,0 +1,22 @@
+//! Tests retrieving the options a program was built with.
+
+use standard::{ProQue, Program};
+
+#[test]
+fn program_build_options() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+
+    let program = Program::builder()
+        .src("__kernel void add(__global float* buffer) { buffer[get_global_id(0)] += 1.0f; }")
+        .cmplr_opt("-cl-fast-relaxed-math")
+        .devices(device)
+        .build(pro_que.context()).unwrap();
+
+    let options = program.build_options(&device).unwrap();
+    assert!(options.contains("-cl-fast-relaxed-math"));
+}
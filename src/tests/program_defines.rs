@@ -0,0 +1,31 @@
+//! Tests formatting of typed compiler definitions.
+
+use standard::{Program, DefineValue};
+
+#[test]
+fn define_value_literals() {
+    assert_eq!(1.5f32.to_define_string(), "1.5f");
+    assert_eq!(2.0f32.to_define_string(), "2.0f");
+    assert_eq!((-0.25f32).to_define_string(), "(-0.25f)");
+    assert_eq!(1.5f64.to_define_string(), "1.5");
+    assert_eq!(3.0f64.to_define_string(), "3.0");
+    assert_eq!(42i32.to_define_string(), "42");
+    assert_eq!((-7i32).to_define_string(), "(-7)");
+    assert_eq!(::std::i32::MIN.to_define_string(), "(-2147483647 - 1)");
+    assert_eq!(42u64.to_define_string(), "42UL");
+    assert_eq!(7u32.to_define_string(), "7U");
+    assert_eq!((-3i64).to_define_string(), "(-3L)");
+}
+
+#[test]
+fn define_typed_options() {
+    let opts = Program::builder()
+        .define_typed("F32_VAL", 0.5f32)
+        .define_typed("F64_VAL", 0.5f64)
+        .define_typed("I32_VAL", -12i32)
+        .define_typed("U64_VAL", 12u64)
+        .get_compiler_options().unwrap();
+
+    assert_eq!(opts.to_str().unwrap(),
+        "-D F32_VAL=0.5f -D F64_VAL=0.5 -D I32_VAL=(-12) -D U64_VAL=12UL");
+}
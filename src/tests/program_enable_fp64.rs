@@ -0,0 +1,35 @@
+//! Tests enabling `double` support with `ProgramBuilder::enable_fp64`.
+
+use core::{DeviceInfo, DeviceInfoResult};
+use standard::{Context, Program, Device};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global double* buffer, double addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+fn supports_fp64(device: &Device) -> bool {
+    match device.info(DeviceInfo::Extensions) {
+        DeviceInfoResult::Extensions(exts) => exts.split_whitespace().any(|e| e == "cl_khr_fp64"),
+        _ => false,
+    }
+}
+
+#[test]
+fn program_enable_fp64() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    let result = Program::builder()
+        .src(SRC)
+        .devices(device)
+        .enable_fp64()
+        .build(&context);
+
+    if supports_fp64(&device) {
+        result.unwrap();
+    } else {
+        assert!(result.is_err());
+    }
+}
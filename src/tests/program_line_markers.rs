@@ -0,0 +1,45 @@
+//! Tests `#line` directives inserted between program source fragments.
+
+use standard::{Program, ProQue};
+
+static FRAGMENT_0: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+// The error (an undeclared identifier) is on line 4 of this fragment:
+static FRAGMENT_1: &'static str = r#"
+    __kernel void mul(__global float* buffer, float coeff) {
+        uint idx = get_global_id(0);
+        buffer[idx] *= not_a_variable;
+    }
+"#;
+
+#[test]
+fn program_line_markers() {
+    let pro_que = ProQue::builder()
+        .src(FRAGMENT_0)
+        .dims([64])
+        .build().unwrap();
+
+    let result = Program::builder()
+        .src(FRAGMENT_0)
+        .src(FRAGMENT_1)
+        .with_line_markers("line_markers.cl")
+        .devices(pro_que.queue().device())
+        .build(pro_que.context());
+
+    let err = result.err().expect("Program build should have failed.").to_string();
+    assert!(err.contains("line_markers.cl[1]:4"), "Unexpected build log: {}", err);
+
+    // Without line markers the reported line refers to the concatenation:
+    let result = Program::builder()
+        .src(FRAGMENT_0)
+        .src(FRAGMENT_1)
+        .devices(pro_que.queue().device())
+        .build(pro_que.context());
+
+    let err = result.err().expect("Program build should have failed.").to_string();
+    assert!(!err.contains("line_markers.cl"));
+}
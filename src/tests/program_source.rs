@@ -0,0 +1,20 @@
+//! Tests retrieving the source of a program built from source.
+
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn program_source() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let source = pro_que.program().source().unwrap();
+    assert_eq!(source.trim(), SRC.trim());
+}
@@ -0,0 +1,52 @@
+//! Tests gating work on one queue using events from another queue.
+
+use standard::{ProQue, Queue, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+
+    __kernel void mul(__global float* buffer, float coeff) {
+        buffer[get_global_id(0)] *= coeff;
+    }
+"#;
+
+#[test]
+fn queue_wait_for_events() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 14])
+        .build().unwrap();
+
+    let queue_b = Queue::new(pro_que.context(), pro_que.queue().device(), None).unwrap();
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let add = pro_que.create_kernel("add").unwrap()
+        .queue(queue_b.clone())
+        .arg_buf(&buffer)
+        .arg_scl(3.0f32);
+
+    let mul = pro_que.create_kernel("mul").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(2.0f32);
+
+    for _ in 0..8 {
+        buffer.cmd().fill(0.0, None).enq().unwrap();
+        pro_que.queue().finish().unwrap();
+
+        // Add on the second queue then gate the first queue on its completion:
+        let mut add_event = Event::empty();
+        add.cmd().enew(&mut add_event).enq().unwrap();
+        queue_b.flush().unwrap();
+        pro_que.queue().wait_for_events(&[add_event]).unwrap();
+        mul.enq().unwrap();
+
+        let mut vec = vec![0.0f32; buffer.len()];
+        buffer.read(&mut vec).enq().unwrap();
+
+        for &val in vec.iter() {
+            assert_eq!(val, 6.0);
+        }
+    }
+}
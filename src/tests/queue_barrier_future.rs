@@ -0,0 +1,41 @@
+//! Tests awaiting a queue marker from host code.
+
+use futures::Future;
+use standard::{ProQue, Event};
+
+#[test]
+fn queue_barrier_future() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1 << 20])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(1.0f32);
+
+    let mut kernel_events = Vec::with_capacity(8);
+    for _ in 0..8 {
+        let mut event = Event::empty();
+        kernel.cmd().enew(&mut event).enq().unwrap();
+        kernel_events.push(event);
+    }
+
+    pro_que.queue().barrier_future().unwrap().wait().unwrap();
+    assert!(kernel_events.iter().all(|event| event.is_complete().unwrap()));
+
+    let mut vec = vec![0.0f32; buffer.len()];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 8.0));
+
+    // An idle queue resolves immediately:
+    pro_que.queue().barrier_future().unwrap().wait().unwrap();
+}
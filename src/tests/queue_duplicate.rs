@@ -0,0 +1,63 @@
+//! Tests duplicating a queue.
+
+use core;
+use standard::{ProQue, Queue, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn queue_duplicate() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 10])
+        .build().unwrap();
+
+    let queue = Queue::new(pro_que.context(), pro_que.queue().device(),
+        Some(core::QUEUE_PROFILING_ENABLE)).unwrap();
+    let dup = queue.duplicate().unwrap();
+
+    assert!(dup.as_ptr() != queue.as_ptr());
+    assert_eq!(dup.device(), queue.device());
+    assert_eq!(dup.properties().unwrap(), queue.properties().unwrap());
+
+    let buffer_a = pro_que.create_buffer::<f32>().unwrap();
+    let buffer_b = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel_a = pro_que.create_kernel("add").unwrap()
+        .queue(queue.clone())
+        .arg_buf(&buffer_a)
+        .arg_scl(1.0f32);
+
+    let kernel_b = pro_que.create_kernel("add").unwrap()
+        .queue(dup.clone())
+        .arg_buf(&buffer_b)
+        .arg_scl(2.0f32);
+
+    // Block the original queue on a user event:
+    let gate = Event::user(pro_que.context()).unwrap();
+    let mut event_a = Event::empty();
+    kernel_a.cmd().ewait(&gate).enew(&mut event_a).enq().unwrap();
+    queue.flush().unwrap();
+
+    // Commands on the duplicate run regardless:
+    let mut event_b = Event::empty();
+    kernel_b.cmd().enew(&mut event_b).enq().unwrap();
+    dup.finish().unwrap();
+
+    assert!(event_b.is_complete().unwrap());
+    assert!(!event_a.is_complete().unwrap());
+
+    gate.set_complete().unwrap();
+    queue.finish().unwrap();
+    assert!(event_a.is_complete().unwrap());
+
+    let mut vec = vec![0.0f32; buffer_a.len()];
+    buffer_a.read(&mut vec).queue(&queue).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 1.0));
+    buffer_b.read(&mut vec).queue(&dup).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 2.0));
+}
@@ -0,0 +1,64 @@
+//! Tests creating queues with priority and throttle hints.
+
+use standard::{ProQue, Queue, Priority, Throttle};
+
+#[test]
+fn queue_priority() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let kernel = pro_que.create_kernel("dummy").unwrap();
+
+    let supported = cfg!(feature = "opencl_version_2_0") &&
+        device.supports_extension("cl_khr_priority_hints").unwrap();
+
+    let queue = Queue::builder(pro_que.context(), device)
+        .priority(Priority::High)
+        .build().unwrap();
+
+    // The hint is applied only when supported (and is otherwise ignored):
+    if supported {
+        assert_eq!(queue.priority_hint(), Some(Priority::High));
+    } else {
+        println!("Device does not support 'cl_khr_priority_hints'. Checking that the hint \
+            was ignored.");
+        assert_eq!(queue.priority_hint(), None);
+    }
+
+    kernel.cmd().queue(&queue).enq().unwrap();
+    queue.finish().unwrap();
+
+    // Queues created without hints report none:
+    assert_eq!(pro_que.queue().priority_hint(), None);
+    assert_eq!(pro_que.queue().throttle_hint(), None);
+}
+
+#[test]
+fn queue_throttle() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let kernel = pro_que.create_kernel("dummy").unwrap();
+
+    let priority_supported = cfg!(feature = "opencl_version_2_0") &&
+        device.supports_extension("cl_khr_priority_hints").unwrap();
+    let throttle_supported = cfg!(feature = "opencl_version_2_0") &&
+        device.supports_extension("cl_khr_throttle_hints").unwrap();
+
+    let queue = Queue::builder(pro_que.context(), device)
+        .priority(Priority::Low)
+        .throttle(Throttle::Low)
+        .build().unwrap();
+
+    assert_eq!(queue.priority_hint(), if priority_supported { Some(Priority::Low) } else { None });
+    assert_eq!(queue.throttle_hint(), if throttle_supported { Some(Throttle::Low) } else { None });
+
+    kernel.cmd().queue(&queue).enq().unwrap();
+    queue.finish().unwrap();
+}
@@ -0,0 +1,46 @@
+//! Tests querying queue properties.
+
+use core;
+use standard::{ProQue, Queue};
+
+static SRC: &'static str = r#"
+    __kernel void nop(__global float* buffer) {}
+"#;
+
+#[test]
+fn queue_is_profiling_enabled() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .build().unwrap();
+
+    let context = pro_que.context();
+    let device = pro_que.queue().device();
+
+    let unprofiled = Queue::new(context, device, None).unwrap();
+    assert!(!unprofiled.is_profiling_enabled().unwrap());
+    assert!(!unprofiled.properties().unwrap().contains(core::QUEUE_PROFILING_ENABLE));
+
+    let profiled = Queue::new(context, device, Some(core::QUEUE_PROFILING_ENABLE)).unwrap();
+    assert!(profiled.is_profiling_enabled().unwrap());
+    assert!(profiled.properties().unwrap().contains(core::QUEUE_PROFILING_ENABLE));
+}
+
+#[test]
+fn pro_que_queue_properties() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .queue_properties(core::QUEUE_PROFILING_ENABLE)
+        .build().unwrap();
+
+    assert!(pro_que.queue().is_profiling_enabled().unwrap());
+
+    // The default is unchanged:
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([64])
+        .build().unwrap();
+
+    assert!(!pro_que.queue().is_profiling_enabled().unwrap());
+}
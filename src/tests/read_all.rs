@@ -0,0 +1,48 @@
+//! Tests reading several buffers across multiple queues.
+
+use standard::{ProQue, Buffer, Queue, read_all};
+
+#[test]
+fn read_all_buffers() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 12])
+        .build().unwrap();
+
+    let queues = [pro_que.queue().clone(),
+        Queue::new(pro_que.context(), pro_que.queue().device(), None).unwrap()];
+
+    let buffers: Vec<Buffer<u32>> = (0..3u32).map(|idx| {
+        Buffer::<u32>::builder()
+            .queue(pro_que.queue().clone())
+            .flags(::flags::MEM_READ_WRITE | ::flags::MEM_COPY_HOST_PTR)
+            .dims(1 << 12)
+            .host_data(&vec![idx + 1; 1 << 12])
+            .build().unwrap()
+    }).collect();
+
+    let mut vec_0 = vec![0u32; 1 << 12];
+    let mut vec_1 = vec![0u32; 1 << 12];
+    let mut vec_2 = vec![0u32; 1 << 12];
+
+    read_all(&mut [(&buffers[0], &mut vec_0), (&buffers[1], &mut vec_1),
+        (&buffers[2], &mut vec_2)], &queues).unwrap();
+
+    assert!(vec_0.iter().all(|&val| val == 1));
+    assert!(vec_1.iter().all(|&val| val == 2));
+    assert!(vec_2.iter().all(|&val| val == 3));
+}
+
+#[test]
+fn read_all_len_mismatch() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1 << 12])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<u32>().unwrap();
+    let mut vec = vec![0u32; 100];
+
+    assert!(read_all(&mut [(&buffer, &mut vec)], &[pro_que.queue().clone()]).is_err());
+    assert!(read_all::<u32>(&mut [], &[]).is_err());
+}
@@ -0,0 +1,36 @@
+//! Tests element-wise minimum, maximum, and clamping of `SpatialDims`.
+
+use standard::SpatialDims;
+
+fn lens<D: Into<SpatialDims>>(dims: D) -> (u32, [usize; 3]) {
+    let dims = dims.into();
+    (dims.dim_count(), dims.to_lens().unwrap())
+}
+
+#[test]
+fn spatial_dims_clamp_2d() {
+    let dims = SpatialDims::Two(5000, 3);
+
+    assert_eq!(lens(dims.component_min([1024, 1024])), (2, [1024, 3, 1]));
+    assert_eq!(lens(dims.component_max([16, 8])), (2, [5000, 8, 1]));
+
+    // Asymmetric bounds:
+    assert_eq!(lens(dims.clamp([64, 4], [1024, 16])), (2, [1024, 4, 1]));
+    assert_eq!(lens(SpatialDims::Two(1, 100).clamp([8, 1], [256, 32])), (2, [8, 32, 1]));
+    assert_eq!(lens(dims.clamp([1, 1], [4096, 4096])), (2, [4096, 3, 1]));
+}
+
+#[test]
+fn spatial_dims_clamp_3d() {
+    let dims = SpatialDims::Three(300, 2, 70);
+
+    assert_eq!(lens(dims.component_min([256, 256, 64])), (3, [256, 2, 64]));
+    assert_eq!(lens(dims.component_max([512, 4, 1])), (3, [512, 4, 70]));
+    assert_eq!(lens(dims.clamp([1, 8, 1], [1024, 16, 64])), (3, [300, 8, 64]));
+
+    // Missing dimensions are treated as 1:
+    assert_eq!(lens(dims.component_min(128)), (3, [128, 1, 1]));
+    assert_eq!(lens(SpatialDims::One(9).component_max([2, 3, 4])), (3, [9, 3, 4]));
+    assert_eq!(lens(SpatialDims::Unspecified.component_max([2, 3])), (2, [2, 3, 1]));
+    assert!(SpatialDims::Unspecified.component_min(SpatialDims::Unspecified).is_unspecified());
+}
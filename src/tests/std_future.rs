@@ -0,0 +1,70 @@
+//! Tests awaiting events as `std::future::Future`s on a minimal executor.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use standard::{ProQue, Event};
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Drives `future` to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn await_kernel_event() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1 << 16])
+        .build().unwrap();
+
+    let buffer = pro_que.create_buffer::<f32>().unwrap();
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffer)
+        .arg_scl(5.0f32);
+
+    buffer.cmd().fill(0.0, None).enq().unwrap();
+
+    for i in 0..8 {
+        let mut event = Event::empty();
+        kernel.cmd().enew(&mut event).enq().unwrap();
+        block_on(event).unwrap();
+
+        let mut vec = vec![0.0f32; buffer.len()];
+        buffer.read(&mut vec).enq().unwrap();
+
+        for &val in vec.iter() {
+            assert_eq!(val, 5.0 * (i + 1) as f32);
+        }
+    }
+
+    // An already complete event must resolve on the first poll:
+    let mut event = Event::empty();
+    kernel.cmd().enew(&mut event).enq().unwrap();
+    pro_que.queue().finish().unwrap();
+    block_on(event).unwrap();
+}
@@ -0,0 +1,49 @@
+//! Tests streaming a dataset through a kernel using a `StreamPipeline`.
+
+use standard::{ProQue, Buffer, StreamPipeline};
+
+static SRC: &'static str = r#"
+    __kernel void increment(__global int* buffer, int addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+const TILE_LEN: usize = 1 << 12;
+const TILE_COUNT: usize = 10;
+
+#[test]
+fn stream_pipeline() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([TILE_LEN])
+        .build().unwrap();
+
+    let mut kernel = pro_que.create_kernel("increment").unwrap()
+        .arg_buf_named::<i32, Buffer<i32>>("buffer", None)
+        .arg_scl(7i32);
+
+    let mut pipeline = StreamPipeline::<i32>::new(pro_que.queue(), TILE_LEN).unwrap();
+
+    // The last tile is intentionally short:
+    let data_len = (TILE_LEN * TILE_COUNT) - (TILE_LEN / 2);
+    let mut data: Vec<i32> = (0..data_len as i32).collect();
+    let mut tile_idxs = Vec::with_capacity(TILE_COUNT);
+
+    pipeline.run(&mut data, |tile, enew| {
+        tile_idxs.push(tile.idx());
+        kernel.set_arg_buf_named("buffer", Some(tile.buffer()))?;
+
+        kernel.cmd()
+            .queue(tile.queue())
+            .gws(tile.len())
+            .ewait(tile.upload_event())
+            .enew(enew)
+            .enq()
+    }).unwrap();
+
+    assert_eq!(tile_idxs, (0..TILE_COUNT).collect::<Vec<_>>());
+
+    for (i, &val) in data.iter().enumerate() {
+        assert_eq!(val, i as i32 + 7);
+    }
+}
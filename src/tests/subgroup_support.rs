@@ -0,0 +1,38 @@
+//! Tests subgroup support detection and enabling the subgroup extension.
+
+use standard::{ProQue, Program};
+
+static SRC: &'static str = r#"
+    __kernel void subgroup_sizes(__global uint* buffer) {
+        buffer[get_global_id(0)] = get_sub_group_size();
+    }
+"#;
+
+#[test]
+fn subgroup_support() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let supported = device.supports_subgroups().unwrap();
+    let has_extension = device.supports_extension("cl_khr_subgroups").unwrap();
+
+    if has_extension {
+        assert!(supported);
+    }
+
+    let result = Program::builder()
+        .src(SRC)
+        .with_subgroup_extension()
+        .devices(device)
+        .build(pro_que.context());
+
+    if has_extension {
+        result.unwrap();
+    } else {
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("does not support the 'cl_khr_subgroups' extension"));
+    }
+}
@@ -0,0 +1,42 @@
+//! Tests subgroup info queries.
+
+use standard::ProQue;
+
+static SRC: &'static str = r#"
+    __kernel void add(__global float* buffer, float addend) {
+        buffer[get_global_id(0)] += addend;
+    }
+"#;
+
+#[test]
+fn subgroup_size_for_ndrange() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims([1024])
+        .build().unwrap();
+
+    let device = pro_que.queue().device();
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&pro_que.create_buffer::<f32>().unwrap())
+        .arg_scl(1.0f32);
+
+    // Core (OpenCL 2.1) subgroup queries require the 'opencl_version_2_1'
+    // feature:
+    let supported = device.supports_extension("cl_khr_subgroups").unwrap() ||
+        (cfg!(feature = "opencl_version_2_1") && device.supports_subgroups().unwrap());
+    if !supported {
+        println!("subgroup_size_for_ndrange: Subgroups not supported. Skipping.");
+        return;
+    }
+
+    let size = kernel.subgroup_size_for_ndrange(&device, [64]).unwrap();
+
+    assert!(size > 0 && size.is_power_of_two(), "Invalid subgroup size: {}", size);
+
+    let count = kernel.subgroup_count_for_ndrange(&device, [64]).unwrap();
+    assert!(count > 0 && count * size >= 64);
+
+    if let Ok(max_count) = device.max_num_subgroups() {
+        assert!(count <= max_count as usize);
+    }
+}
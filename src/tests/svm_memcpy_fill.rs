@@ -0,0 +1,86 @@
+//! Tests filling, copying, and mapping SVM buffers using the queue's SVM
+//! command builders.
+
+use std::{mem, ptr};
+use libc::c_void;
+use ffi;
+use core::OpenclVersion;
+use flags;
+use standard::{Context, Device, Queue, SvmBuffer, EventList};
+
+const LEN: usize = 1024;
+const CL_DEVICE_SVM_CAPABILITIES: ffi::cl_uint = 0x1053;
+const CL_DEVICE_SVM_COARSE_GRAIN_BUFFER: ffi::cl_bitfield = 1 << 0;
+
+/// Returns true if `device` supports coarse-grained buffer SVM.
+fn supports_coarse_grain_svm(device: &Device) -> bool {
+    let mut caps: ffi::cl_bitfield = 0;
+
+    let errcode = unsafe {
+        ffi::clGetDeviceInfo(device.as_core().as_ptr(), CL_DEVICE_SVM_CAPABILITIES,
+            mem::size_of::<ffi::cl_bitfield>(), &mut caps as *mut _ as *mut c_void,
+            ptr::null_mut())
+    };
+
+    errcode == ffi::CL_SUCCESS as ffi::cl_int && (caps & CL_DEVICE_SVM_COARSE_GRAIN_BUFFER) != 0
+}
+
+#[test]
+fn svm_memcpy_fill() {
+    let context = Context::builder().build().unwrap();
+    let device = context.devices()[0];
+
+    // SVM requires OpenCL 2.0 and coarse-grained buffer support on every
+    // device associated with the context:
+    for device in context.devices() {
+        if device.version().unwrap() < OpenclVersion::new(2, 0) ||
+                !supports_coarse_grain_svm(&device)
+        {
+            println!("Coarse-grained buffer SVM not supported. Skipping.");
+            return;
+        }
+    }
+
+    let mut src = SvmBuffer::<i32>::new(&context, LEN).unwrap();
+    let dst = SvmBuffer::<i32>::new(&context, LEN).unwrap();
+    let queue = Queue::new(&context, device, None).unwrap();
+    let bytes = LEN * mem::size_of::<i32>();
+
+    queue.svm_memfill(&src, 7, bytes).enq().unwrap();
+    queue.svm_memfill(&dst, 0, bytes).enq().unwrap();
+
+    // Copy the second half of `src` into the first half of `dst`:
+    queue.svm_memcpy(&dst, &src, bytes / 2).src_offset(bytes / 2).enq().unwrap();
+
+    queue.svm_map(&dst, flags::MAP_READ, bytes).enq().unwrap();
+    {
+        let vals = unsafe { dst.as_slice() };
+        assert!(vals[..LEN / 2].iter().all(|&val| val == 7));
+        assert!(vals[LEN / 2..].iter().all(|&val| val == 0));
+    }
+    queue.svm_unmap(&dst).enq().unwrap();
+
+    // Misaligned fills and out of range copies are rejected without leaving
+    // an event behind:
+    let mut events = EventList::new();
+    assert!(queue.svm_memfill(&src, 1, 3).enew(&mut events).enq().is_err());
+    assert!(queue.svm_memcpy(&dst, &src, bytes).src_offset(4).enew(&mut events).enq().is_err());
+    assert_eq!(events.len(), 0);
+
+    // Write through a map and copy the result back:
+    queue.svm_map(&src, flags::MAP_WRITE, bytes).enq().unwrap();
+    {
+        let vals = unsafe { src.as_mut_slice() };
+        for (idx, val) in vals.iter_mut().enumerate() { *val = idx as i32; }
+    }
+    queue.svm_unmap(&src).enq().unwrap();
+
+    queue.svm_memcpy(&dst, &src, bytes).enq().unwrap();
+
+    queue.svm_map(&dst, flags::MAP_READ, bytes).enq().unwrap();
+    {
+        let vals = unsafe { dst.as_slice() };
+        assert!(vals.iter().enumerate().all(|(idx, &val)| val == idx as i32));
+    }
+    queue.svm_unmap(&dst).enq().unwrap();
+}
@@ -0,0 +1,52 @@
+//! Tests launching a shared kernel from multiple threads.
+
+use std::thread;
+use standard::{ProQue, SyncKernel};
+
+#[test]
+fn sync_kernel() {
+    let src = r#"
+        __kernel void add(__global float* buffer, float addend) {
+            buffer[get_global_id(0)] += addend;
+        }
+    "#;
+
+    let pro_que = ProQue::builder()
+        .src(src)
+        .dims([1 << 14])
+        .build().unwrap();
+
+    let buffers = [pro_que.create_buffer::<f32>().unwrap(),
+        pro_que.create_buffer::<f32>().unwrap()];
+
+    let kernel = pro_que.create_kernel("add").unwrap()
+        .arg_buf(&buffers[0])
+        .arg_scl(0.0f32);
+
+    let sync_kernel = SyncKernel::new(kernel);
+
+    let threads: Vec<_> = (0..2).map(|idx| {
+        let sync_kernel = sync_kernel.clone();
+        let buffer = buffers[idx].clone();
+        let addend = (idx + 1) as f32;
+
+        thread::spawn(move || {
+            for _ in 0..10 {
+                sync_kernel.with_args(|k| {
+                    try!(k.set_arg(0, &buffer));
+                    try!(k.set_arg(1, addend));
+                    k.enq()
+                }).unwrap();
+            }
+        })
+    }).collect();
+
+    for thread in threads { thread.join().unwrap(); }
+    pro_que.queue().finish().unwrap();
+
+    for (idx, buffer) in buffers.iter().enumerate() {
+        let mut vec = vec![0.0f32; buffer.len()];
+        buffer.read(&mut vec).enq().unwrap();
+        assert!(vec.iter().all(|&val| val == 10.0 * (idx + 1) as f32));
+    }
+}
@@ -0,0 +1,81 @@
+//! Tests the buffer-backed fallback of `Texture`.
+
+use standard::{ProQue, Texture};
+use enums::{ImageChannelOrder, ImageChannelDataType};
+
+static SRC: &'static str = r#"
+    __kernel void mark(__global uchar* tex, uint width, uint channels) {
+        uint x = get_global_id(0);
+        uint y = get_global_id(1);
+        uint idx = ((y * width) + x) * channels;
+        tex[idx] = (uchar)x;
+        tex[idx + 1] = (uchar)y;
+    }
+"#;
+
+const DIMS: [usize; 2] = [12, 7];
+
+#[test]
+fn texture_buffer_fallback() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(DIMS)
+        .build().unwrap();
+
+    let texture = Texture::<u8>::new_buffer_backed(pro_que.queue(), ImageChannelOrder::Rgba,
+        ImageChannelDataType::UnsignedInt8, DIMS).unwrap();
+    assert!(texture.is_buffer_backed());
+    assert!(texture.image().is_none());
+    assert_eq!(texture.channels(), 4);
+    assert_eq!(texture.len(), DIMS[0] * DIMS[1] * 4);
+
+    // Host writes and reads use the same layout as the computed index:
+    let mut data = vec![0u8; texture.len()];
+    for y in 0..DIMS[1] {
+        for x in 0..DIMS[0] {
+            let idx = texture.index(x, y);
+            data[idx + 2] = (x + y) as u8;
+            data[idx + 3] = 255;
+        }
+    }
+    texture.write(&data).unwrap();
+
+    // Kernels index the buffer directly:
+    pro_que.create_kernel("mark").unwrap()
+        .arg_buf(texture.buffer().unwrap())
+        .arg_scl(DIMS[0] as u32)
+        .arg_scl(texture.channels() as u32)
+        .enq().unwrap();
+
+    let mut vec = vec![0u8; texture.len()];
+    texture.read(&mut vec).unwrap();
+
+    for y in 0..DIMS[1] {
+        for x in 0..DIMS[0] {
+            let expected = [x as u8, y as u8, (x + y) as u8, 255];
+            let idx = texture.index(x, y);
+            assert_eq!(&vec[idx..idx + 4], &expected);
+            assert_eq!(texture.sample(x, y).unwrap(), expected.to_vec());
+        }
+    }
+
+    assert!(texture.sample(DIMS[0], 0).is_err());
+    assert!(texture.write(&data[1..]).is_err());
+}
+
+#[test]
+fn texture_new() {
+    let pro_que = ProQue::builder()
+        .src(SRC)
+        .dims(DIMS)
+        .build().unwrap();
+
+    // Either storage may be chosen. The interface is the same:
+    let texture = Texture::<u8>::new(pro_que.queue(), ImageChannelOrder::Rgba,
+        ImageChannelDataType::UnsignedInt8, DIMS).unwrap();
+    assert_eq!(texture.is_buffer_backed(), texture.image().is_none());
+
+    let data: Vec<u8> = (0..texture.len()).map(|i| i as u8).collect();
+    texture.write(&data).unwrap();
+    assert_eq!(texture.sample(3, 2).unwrap(), &data[texture.index(3, 2)..][..4]);
+}
@@ -0,0 +1,43 @@
+//! Tests that API calls are traced when the `trace` feature is enabled.
+
+use std::sync::{Arc, Mutex};
+use log::{self, Log, LogRecord, LogLevel, LogMetadata, LogLevelFilter};
+use standard::{ProQue, Buffer};
+
+struct CaptureLogger {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &LogMetadata) -> bool {
+        metadata.level() <= LogLevel::Trace
+    }
+
+    fn log(&self, record: &LogRecord) {
+        self.lines.lock().unwrap().push(format!("{}", record.args()));
+    }
+}
+
+#[test]
+fn trace_buffer_creation() {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let logger_lines = lines.clone();
+
+    log::set_logger(|max_log_level| {
+        max_log_level.set(LogLevelFilter::Trace);
+        Box::new(CaptureLogger { lines: logger_lines })
+    }).unwrap();
+
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .build().unwrap();
+
+    let _buffer = Buffer::<f32>::builder()
+        .queue(pro_que.queue().clone())
+        .dims(64)
+        .build().unwrap();
+
+    let lines = lines.lock().unwrap();
+    assert!(lines.iter().any(|l| l.starts_with("clCreateBuffer(") && l.ends_with("CL_SUCCESS")),
+        "No buffer creation trace found: {:?}", *lines);
+}
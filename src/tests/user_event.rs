@@ -0,0 +1,33 @@
+//! Tests gating a kernel on a user event set from the host.
+
+use std::thread;
+use std::time::Duration;
+use standard::Event;
+use tests;
+
+#[test]
+fn user_event() {
+    let (pro_que, buffer, kernel) = tests::pro_que_buffer_kernel::<f32, _>(tests::ADD_SRC,
+        "add", [1024]);
+    let kernel = kernel.arg_scl(5.0f32);
+
+    let gate = Event::user(pro_que.context()).unwrap();
+    let mut kernel_event = Event::empty();
+    kernel.cmd().ewait(&gate).enew(&mut kernel_event).enq().unwrap();
+    pro_que.queue().flush().unwrap();
+
+    // The kernel cannot run until the gate is opened:
+    thread::sleep(Duration::from_millis(50));
+    assert!(!kernel_event.is_complete().unwrap());
+
+    gate.set_complete().unwrap();
+    kernel_event.wait_for().unwrap();
+
+    let mut vec = vec![0.0f32; 1024];
+    buffer.read(&mut vec).enq().unwrap();
+    assert!(vec.iter().all(|&val| val == 5.0));
+
+    // The status of a user event may only be set once:
+    assert!(gate.set_complete().is_err());
+    assert!(Event::empty().set_complete().is_err());
+}
@@ -0,0 +1,82 @@
+//! Tests the polling-based waits and their `WaitStrategy`.
+
+use std::thread;
+use std::time::{Duration, Instant};
+use standard::{ProQue, Event, EventList, WaitStrategy};
+
+const DELAY_MS: u64 = 200;
+
+/// Returns the CPU time consumed by the calling thread.
+#[cfg(target_os = "linux")]
+fn thread_cpu_time() -> Duration {
+    let mut ts = ::libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    let res = unsafe { ::libc::clock_gettime(::libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+    assert_eq!(res, 0);
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// Returns a user event which is set complete by another thread after
+/// `DELAY_MS` milliseconds.
+fn delayed_event(pro_que: &ProQue) -> Event {
+    let gate = Event::user(pro_que.context()).unwrap();
+    let gate_clone = gate.clone();
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(DELAY_MS));
+        gate_clone.set_complete().unwrap();
+    });
+
+    gate
+}
+
+#[test]
+fn wait_strategy_sleep() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    let gate = delayed_event(&pro_que);
+
+    #[cfg(target_os = "linux")]
+    let cpu_start = thread_cpu_time();
+    let start = Instant::now();
+
+    let strategy = WaitStrategy::Sleep(Duration::from_millis(5));
+    assert!(gate.wait_for_polling(strategy, Some(Duration::from_secs(30))).unwrap());
+
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(DELAY_MS / 2));
+
+    // The waiting thread must have slept for most of the wait:
+    #[cfg(target_os = "linux")]
+    {
+        let cpu = thread_cpu_time() - cpu_start;
+        assert!(cpu < elapsed / 2, "Sleep strategy used {:?} of CPU time over {:?}.", cpu,
+            elapsed);
+    }
+}
+
+#[test]
+fn wait_strategy_timeout_and_any() {
+    let pro_que = ProQue::builder()
+        .src("__kernel void dummy() {}")
+        .dims([1])
+        .build().unwrap();
+
+    assert_eq!(WaitStrategy::default(), WaitStrategy::Sleep(Duration::new(0, 100_000)));
+
+    // Never completed:
+    let pending = Event::user(pro_que.context()).unwrap();
+    assert!(!pending.wait_for_polling(WaitStrategy::Yield, Some(Duration::from_millis(20)))
+        .unwrap());
+
+    let mut list = EventList::new();
+    assert!(list.wait_for_any(WaitStrategy::Spin).is_err());
+
+    list.push(pending.clone());
+    list.push(delayed_event(&pro_que));
+    assert_eq!(list.wait_for_any(WaitStrategy::default()).unwrap(), 1);
+
+    pending.set_complete().unwrap();
+}